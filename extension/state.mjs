@@ -6,8 +6,9 @@ export const PANEL_PORT_NAME = "hourglass-side-panel-v1";
 export const MAX_DURATION_MS = 24 * 60 * 60 * 1000;
 
 const STATUSES = new Set(["idle", "running", "paused", "finished"]);
-const COLOR_MODES = new Set(["static", "random", "rainbow"]);
-const SHAPES = new Set(["classic", "modern", "slim", "wide"]);
+const COLOR_MODES = new Set(["static", "random", "rainbow", "progress"]);
+const GRADIENT_KEYS = new Set(["fraction", "seconds"]);
+const MAX_GRADIENT_STOPS = 16;
 const SHAPE_MODES = new Set(["static", "morphing"]);
 
 const clamp = (value, minimum, maximum) =>
@@ -16,6 +17,44 @@ const clamp = (value, minimum, maximum) =>
 const oneOf = (value, allowed, fallback) =>
   allowed.has(value) ? value : fallback;
 
+// Built-in shapes and those saved from the shape editor are stored by their
+// lowercase name, like `HourglassShape::from_name`.
+const normalizeShape = (value) =>
+  typeof value === "string" && value.trim() ? value.trim().toLowerCase() : "classic";
+
+const normalizeRgba = (value, fallback) =>
+  Array.isArray(value) && value.length === 4
+    ? value.map((channel) => clamp(Number(channel), 0, 1))
+    : fallback;
+
+function defaultGradient() {
+  return {
+    key: "fraction",
+    stops: [
+      { at: 1, colorRgba: [0.2, 0.7, 0.25, 1] },
+      { at: 0.5, colorRgba: [1, 0.65, 0, 1] },
+      { at: 0, colorRgba: [0.85, 0.15, 0.15, 1] }
+    ]
+  };
+}
+
+function normalizeGradient(input) {
+  if (!input || typeof input !== "object" || !Array.isArray(input.stops)) {
+    return defaultGradient();
+  }
+  const key = oneOf(input.key, GRADIENT_KEYS, "fraction");
+  const maximum = key === "fraction" ? 1 : MAX_DURATION_MS / 1000;
+  const stops = input.stops
+    .filter((stop) => stop && typeof stop === "object" && Number.isFinite(stop.at))
+    .slice(0, MAX_GRADIENT_STOPS)
+    .map((stop) => ({
+      at: clamp(stop.at, 0, maximum),
+      colorRgba: normalizeRgba(stop.colorRgba, [0.8, 0.6, 0.2, 1])
+    }))
+    .sort((a, b) => b.at - a.at);
+  return stops.length > 0 ? { key, stops } : defaultGradient();
+}
+
 export function defaultState() {
   return {
     version: SNAPSHOT_VERSION,
@@ -30,6 +69,7 @@ export function defaultState() {
     appearance: {
       colorMode: "static",
       colorRgba: [0.8, 0.6, 0.2, 1],
+      gradient: defaultGradient(),
       shape: "classic",
       shapeMode: "static"
     }
@@ -82,9 +122,7 @@ export function normalizeState(input, now = Date.now()) {
   const appearance = input.appearance && typeof input.appearance === "object"
     ? input.appearance
     : fallback.appearance;
-  const rgba = Array.isArray(appearance.colorRgba) && appearance.colorRgba.length === 4
-    ? appearance.colorRgba.map((channel) => clamp(Number(channel), 0, 1))
-    : fallback.appearance.colorRgba;
+  const rgba = normalizeRgba(appearance.colorRgba, fallback.appearance.colorRgba);
 
   return {
     version: SNAPSHOT_VERSION,
@@ -99,7 +137,8 @@ export function normalizeState(input, now = Date.now()) {
     appearance: {
       colorMode: oneOf(appearance.colorMode, COLOR_MODES, "static"),
       colorRgba: rgba,
-      shape: oneOf(appearance.shape, SHAPES, "classic"),
+      gradient: normalizeGradient(appearance.gradient),
+      shape: normalizeShape(appearance.shape),
       shapeMode: oneOf(appearance.shapeMode, SHAPE_MODES, "static")
     }
   };
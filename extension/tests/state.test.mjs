@@ -134,3 +134,50 @@ test("coercible non-number durations fall back to the safe default", () => {
     assert.deepEqual(result, defaultState());
   }
 });
+
+test("progress gradients are kept sorted, clamped and bounded", () => {
+  const state = defaultState();
+  const result = normalizeState({
+    ...state,
+    appearance: {
+      ...state.appearance,
+      colorMode: "progress",
+      gradient: {
+        key: "seconds",
+        stops: [
+          { at: 0, colorRgba: [2, 0, 0, 1] },
+          { at: 60, colorRgba: [0, 1, 0, 1] },
+          { at: "soon", colorRgba: [0, 0, 1, 1] }
+        ]
+      }
+    }
+  });
+  assert.equal(result.appearance.colorMode, "progress");
+  assert.deepEqual(result.appearance.gradient, {
+    key: "seconds",
+    stops: [
+      { at: 60, colorRgba: [0, 1, 0, 1] },
+      { at: 0, colorRgba: [1, 0, 0, 1] }
+    ]
+  });
+
+  const missing = normalizeState({ ...state, appearance: { ...state.appearance, gradient: null } });
+  assert.deepEqual(missing.appearance.gradient, state.appearance.gradient);
+});
+
+test("shapes saved from the editor survive normalization", () => {
+  const state = defaultState();
+  const withShape = (shape) =>
+    normalizeState({ ...state, appearance: { ...state.appearance, shape } }).appearance.shape;
+  assert.equal(withShape("slim"), "slim");
+  assert.equal(withShape(" Tall Vase "), "tall vase");
+  assert.equal(withShape("  "), "classic");
+  assert.equal(withShape(7), "classic");
+
+  const result = canonicalizePanelState(
+    { ...state, appearance: { ...state.appearance, shape: "tall vase" } },
+    state,
+    "panel-a"
+  );
+  assert.equal(result.appearance.shape, "tall vase");
+});
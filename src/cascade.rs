@@ -0,0 +1,330 @@
+//! Cascade view for long countdowns. Past `CascadeSettings::threshold_secs`
+//! the main hourglass runs through one hour at a time and a row of small
+//! hourglasses below it holds the hours still to come, emptying one by one
+//! as the main glass turns over for the next.
+
+use crate::hourglass::{HourglassPlacement, MainHourglass};
+use crate::resources::{
+    CascadeSettings, HourglassConfig, HourglassShape, PendingFlip, SandColorPreview, ShapePreview,
+    TimerState,
+};
+use crate::shapes::{ShapeDefinition, ShapeLibrary};
+use crate::timer::TimerSystems;
+use bevy::prelude::*;
+use bevy_hourglass::{
+    Hourglass, HourglassMeshBuilder, HourglassMeshSand, HourglassMeshSandConfig,
+    HourglassMeshSandState, update_sand_fill_percent,
+};
+
+/// Height kept free below the main hourglass for the row, in window pixels
+/// at full scale.
+pub(crate) const CASCADE_ROW_HEIGHT: f32 = 40.0;
+/// Centre of the row below the main hourglass's origin, in its units. The
+/// glass and plates span 480 units.
+const ROW_OFFSET: f32 = 240.0 + CASCADE_ROW_HEIGHT / 2.0;
+/// Widest the row gets, matching the main hourglass's width.
+const ROW_WIDTH: f32 = 400.0;
+/// Space per small hourglass until the row is full; after that they shrink.
+const HOUR_SPACING: f32 = 30.0;
+
+pub struct CascadePlugin;
+
+impl Plugin for CascadePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CascadeSettings>()
+            .register_type::<CascadeSettings>()
+            // Ask for the flip before the hourglass systems would refill the
+            // main glass, so it turns over instead of jumping back to full.
+            .add_systems(
+                Update,
+                turn_over_for_the_next_hour
+                    .after(TimerSystems::Tick)
+                    .before(TimerSystems::Observe),
+            )
+            .add_systems(Update, update_cascade_row.after(TimerSystems::Observe));
+    }
+}
+
+#[derive(Component)]
+struct CascadeRow;
+
+/// A small hourglass holding the hour at this index, counted from the left.
+#[derive(Component)]
+struct CascadeHour(usize);
+
+/// Turn the main hourglass over whenever a running countdown moves on to the
+/// next hour.
+fn turn_over_for_the_next_hour(
+    settings: Res<CascadeSettings>,
+    timer_state: Res<TimerState>,
+    mut pending_flip: ResMut<PendingFlip>,
+    mut last_queued: Local<Option<usize>>,
+) {
+    let queued = settings.segment(&timer_state).map(|segment| segment.queued);
+    if timer_state.is_running
+        && let (Some(queued), Some(last)) = (queued, *last_queued)
+        && queued < last
+    {
+        pending_flip.0 = true;
+    }
+    *last_queued = queued;
+}
+
+/// Where the row sits, in the same space as the main hourglass and scaled
+/// with it.
+fn row_transform(main: &Transform) -> Transform {
+    Transform::from_translation(main.translation - Vec3::Y * ROW_OFFSET * main.scale.y)
+        .with_scale(main.scale)
+}
+
+fn hour_fill(hour: usize, queued: usize) -> f32 {
+    // The rightmost full hour is the next one to run.
+    if hour < queued { 1.0 } else { 0.0 }
+}
+
+/// Keep the row in step with the countdown: built for the current number of
+/// hours and shape, following the main hourglass, with the hours already run
+/// emptied and the sand in the current colour.
+fn update_cascade_row(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    settings: Res<CascadeSettings>,
+    timer_state: Res<TimerState>,
+    config: Res<HourglassConfig>,
+    preview: Option<Res<SandColorPreview>>,
+    shape_preview: Res<ShapePreview>,
+    library: Res<ShapeLibrary>,
+    placement: Res<HourglassPlacement>,
+    main: Query<&Transform, (With<MainHourglass>, Without<CascadeRow>)>,
+    mut rows: Query<(Entity, &mut Transform), With<CascadeRow>>,
+    mut hours: Query<(&CascadeHour, &mut HourglassMeshSandState, Option<&Children>)>,
+    sand_query: Query<&MeshMaterial2d<ColorMaterial>, With<HourglassMeshSand>>,
+    mut built: Local<Option<(usize, HourglassShape)>>,
+) {
+    let segment = settings
+        .segment(&timer_state)
+        .filter(|segment| segment.hours > 0);
+    let Some(segment) = segment else {
+        for (row, _) in &rows {
+            commands.entity(row).despawn();
+        }
+        *built = None;
+        return;
+    };
+    let Ok(main) = main.single() else {
+        return;
+    };
+
+    let color = preview.map_or(config.color, |preview| preview.sand_color(&config));
+    let shape = shape_preview.shape(&config).0;
+    let layout = (segment.hours, shape.clone());
+    if built.as_ref() != Some(&layout) || library.is_changed() || rows.is_empty() {
+        for (row, _) in &rows {
+            commands.entity(row).despawn();
+        }
+        spawn_cascade_row(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            library.get(shape),
+            segment.queued,
+            segment.hours,
+            color,
+            row_transform(main),
+            &placement,
+        );
+        *built = Some(layout);
+        return;
+    }
+
+    for (_, mut transform) in &mut rows {
+        transform.set_if_neq(row_transform(main));
+    }
+    for (hour, mut sand_state, children) in &mut hours {
+        update_sand_fill_percent(&mut sand_state, hour_fill(hour.0, segment.queued));
+        if sand_state.sand_config.color == color {
+            continue;
+        }
+        sand_state.sand_config.color = color;
+        for material in sand_query.iter_many(children.into_iter().flatten()) {
+            if let Some(material) = materials.get_mut(&material.0) {
+                material.color = color;
+            }
+        }
+    }
+}
+
+/// Spawn a row of `hours` small hourglasses in the mini shape-row configs of
+/// `definition`, the first `queued` of them full.
+fn spawn_cascade_row(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    definition: &ShapeDefinition,
+    queued: usize,
+    hours: usize,
+    color: Color,
+    transform: Transform,
+    placement: &HourglassPlacement,
+) {
+    let row = commands
+        .spawn((
+            Name::new("Cascade Row"),
+            CascadeRow,
+            transform,
+            Visibility::default(),
+        ))
+        .id();
+    if let Some(root) = placement.root {
+        commands.entity(row).insert(ChildOf(root));
+    }
+
+    let spacing = HOUR_SPACING.min(ROW_WIDTH / hours as f32);
+    let (body_config, plates_config) = definition.mini_configs();
+    for hour in 0..hours {
+        let x = (hour as f32 - (hours - 1) as f32 / 2.0) * spacing;
+        let entity = HourglassMeshBuilder::new(
+            Transform::from_xyz(x, 0.0, 0.0).with_scale(Vec3::splat(spacing / HOUR_SPACING)),
+        )
+        .with_body(body_config.clone())
+        .with_plates(plates_config.clone())
+        .with_sand(HourglassMeshSandConfig {
+            color,
+            fill_percent: hour_fill(hour, queued),
+            wall_offset: 1.0,
+        })
+        .build(commands, meshes, materials);
+        // The countdown sets each one's fill, so none of them time anything.
+        commands.entity(entity).remove::<Hourglass>().insert((
+            CascadeHour(hour),
+            Name::new(format!("Cascade Hour {}", hour + 1)),
+            ChildOf(row),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HourglassTimerPlugin;
+    use crate::resources::CASCADE_SEGMENT_SECS;
+    use approx::assert_abs_diff_eq;
+    use bevy::asset::AssetPlugin;
+    use bevy::input::InputPlugin;
+    use bevy::render::mesh::MeshPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    const HOUR: f32 = CASCADE_SEGMENT_SECS;
+
+    /// A headless three hour countdown, stepping 100 ms a frame.
+    fn cascade_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            MeshPlugin,
+            TransformPlugin,
+        ))
+        .init_asset::<ColorMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .insert_resource(TimerState {
+            duration: 3.0 * HOUR,
+            remaining: 3.0 * HOUR,
+            is_running: false,
+        })
+        .add_plugins(HourglassTimerPlugin {
+            ui: false,
+            ..default()
+        });
+        app.update();
+        app.update();
+        app
+    }
+
+    fn hour_fills(app: &mut App) -> Vec<f32> {
+        let world = app.world_mut();
+        let mut hours: Vec<(usize, f32)> = world
+            .query::<(&CascadeHour, &HourglassMeshSandState)>()
+            .iter(world)
+            .map(|(hour, sand_state)| (hour.0, sand_state.fill_percent))
+            .collect();
+        hours.sort_by_key(|(hour, _)| *hour);
+        hours.into_iter().map(|(_, fill)| fill).collect()
+    }
+
+    fn main_hourglass(app: &mut App) -> Hourglass {
+        let world = app.world_mut();
+        world
+            .query_filtered::<&Hourglass, With<MainHourglass>>()
+            .single(world)
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn long_countdowns_queue_their_hours_below_the_glass() {
+        let mut app = cascade_app();
+        assert_eq!(hour_fills(&mut app), [1.0, 1.0]);
+        let main = main_hourglass(&mut app);
+        assert_eq!(main.total_time, HOUR);
+        assert_eq!(main.upper_chamber, 1.0);
+
+        // Start, and let the first turn-over finish.
+        app.world_mut().resource_mut::<TimerState>().is_running = true;
+        for _ in 0..12 {
+            app.update();
+        }
+        assert!(!main_hourglass(&mut app).flipping);
+
+        // Into the second hour: one small hourglass empties and the main one
+        // turns over for it.
+        app.world_mut().resource_mut::<TimerState>().remaining = 1.5 * HOUR;
+        app.update();
+        assert_eq!(hour_fills(&mut app), [1.0, 0.0]);
+        let main = main_hourglass(&mut app);
+        assert!(main.flipping);
+        // Less the frame that just ran.
+        assert_abs_diff_eq!(main.remaining_time, 0.5 * HOUR, epsilon = 0.2);
+    }
+
+    #[test]
+    fn short_countdowns_keep_a_single_hourglass() {
+        let mut app = cascade_app();
+        assert_eq!(hour_fills(&mut app).len(), 2);
+
+        app.world_mut()
+            .resource_mut::<TimerState>()
+            .set_duration(HOUR);
+        app.update();
+        app.update();
+        assert!(hour_fills(&mut app).is_empty());
+        let world = app.world_mut();
+        assert_eq!(
+            world
+                .query_filtered::<(), With<CascadeRow>>()
+                .iter(world)
+                .count(),
+            0
+        );
+        assert_eq!(main_hourglass(&mut app).total_time, HOUR);
+    }
+
+    #[test]
+    fn hours_follow_the_sand_colour() {
+        let mut app = cascade_app();
+        app.world_mut().resource_mut::<HourglassConfig>().color = Color::WHITE;
+        app.update();
+        let world = app.world_mut();
+        for sand_state in world
+            .query_filtered::<&HourglassMeshSandState, With<CascadeHour>>()
+            .iter(world)
+        {
+            assert_eq!(sand_state.sand_config.color, Color::WHITE);
+        }
+    }
+}
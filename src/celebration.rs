@@ -0,0 +1,380 @@
+//! The end-of-run celebration. When a countdown reaches zero the glass glows
+//! and pulses, sand bursts up from the bottom pile and the window can flash,
+//! until a click or key press acknowledges it.
+
+use crate::hourglass::{HourglassPlacement, MainHourglass};
+use crate::resources::{FinishAcknowledge, FinishSettings, TimerState};
+use crate::timer::{TimerCommand, TimerStateChanged, TimerSystems};
+use bevy::input::mouse::MouseButton;
+use bevy::prelude::*;
+use bevy::sprite::AlphaMode2d;
+use bevy::ui::FocusPolicy;
+use bevy_hourglass::{Hourglass, HourglassMeshSandState};
+use rand::Rng;
+use std::f32::consts::{PI, TAU};
+
+/// One glow pulse, dim to bright and back.
+const PULSE_SECS: f32 = 1.2;
+/// Time between sand bursts.
+const BURST_SECS: f32 = 1.5;
+const BURST_PARTICLES: usize = 24;
+/// How long a burst particle flies before it has shrunk away.
+const PARTICLE_SECS: f32 = 1.2;
+const PARTICLE_SIZE: f32 = 3.0;
+/// Downward pull on burst particles, in hourglass units per second squared.
+const PARTICLE_GRAVITY: f32 = 320.0;
+
+pub struct CelebrationPlugin;
+
+impl Plugin for CelebrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FinishSettings>()
+            .init_resource::<FinishCelebration>()
+            .register_type::<FinishSettings>()
+            // Acknowledging swallows the click or key, so it must run before
+            // the input systems that would otherwise also act on it.
+            .add_systems(
+                Update,
+                acknowledge_finish
+                    .after(TimerSystems::Restore)
+                    .before(TimerSystems::Input),
+            )
+            .add_systems(
+                Update,
+                (start_finish_celebration, animate_finish_celebration)
+                    .chain()
+                    .in_set(TimerSystems::Observe),
+            );
+    }
+}
+
+/// The celebration in progress, if any.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct FinishCelebration(Option<CelebrationClock>);
+
+impl FinishCelebration {
+    pub(crate) fn is_playing(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CelebrationClock {
+    elapsed: f32,
+    next_burst: f32,
+}
+
+#[derive(Component)]
+struct FinishGlow;
+
+#[derive(Component)]
+struct FinishFlash;
+
+#[derive(Component)]
+struct FinishParticle {
+    velocity: Vec2,
+    age: f32,
+}
+
+fn start_finish_celebration(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut changes: EventReader<TimerStateChanged>,
+    settings: Res<FinishSettings>,
+    placement: Res<HourglassPlacement>,
+    mut celebration: ResMut<FinishCelebration>,
+    hourglass: Query<(Entity, &Hourglass, &HourglassMeshSandState), With<MainHourglass>>,
+) {
+    let finished = changes
+        .read()
+        .any(|changed| changed.0 == TimerCommand::Finish);
+    if !finished || !settings.celebrates() || celebration.is_playing() {
+        return;
+    }
+    celebration.0 = Some(CelebrationClock::default());
+
+    if settings.flash {
+        commands.spawn((
+            Name::new("Finish Flash"),
+            FinishFlash,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(Color::NONE),
+            FocusPolicy::Pass,
+            GlobalZIndex(240),
+        ));
+    }
+
+    if settings.glow
+        && let Ok((entity, hourglass, sand_state)) = hourglass.single()
+    {
+        let height = sand_state.body_config.total_height;
+        commands.spawn((
+            Name::new("Finish Glow"),
+            FinishGlow,
+            Mesh2d(meshes.add(Ellipse::new(height * 0.42, height * 0.6))),
+            MeshMaterial2d(materials.add(ColorMaterial {
+                color: hourglass.sand_color.with_alpha(0.0),
+                alpha_mode: AlphaMode2d::Blend,
+                ..default()
+            })),
+            Transform::from_xyz(0.0, 0.0, -0.5),
+            placement.render_layers.clone(),
+            ChildOf(entity),
+        ));
+    }
+}
+
+/// Brightness of the glow pulse, from 0 to 1.
+fn pulse(elapsed: f32) -> f32 {
+    0.5 - 0.5 * (TAU * elapsed / PULSE_SECS).cos()
+}
+
+/// The flash starts bright, fades fast and then breathes with the glow.
+fn flash_alpha(elapsed: f32) -> f32 {
+    0.6 * (-4.0 * elapsed).exp() + 0.08 * pulse(elapsed)
+}
+
+fn animate_finish_celebration(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+    timer_state: Res<TimerState>,
+    settings: Res<FinishSettings>,
+    placement: Res<HourglassPlacement>,
+    mut celebration: ResMut<FinishCelebration>,
+    hourglass: Query<(Entity, &Hourglass, &HourglassMeshSandState), With<MainHourglass>>,
+    mut glows: Query<(Entity, &mut Transform, &MeshMaterial2d<ColorMaterial>), With<FinishGlow>>,
+    mut flashes: Query<(Entity, &mut BackgroundColor), With<FinishFlash>>,
+    mut particles: Query<(Entity, &mut Transform, &mut FinishParticle), Without<FinishGlow>>,
+) {
+    let delta = time.delta_secs();
+
+    // Loose particles fly out their lifetime even after the celebration ends.
+    for (entity, mut transform, mut particle) in &mut particles {
+        particle.age += delta;
+        if particle.age >= PARTICLE_SECS {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        particle.velocity.y -= PARTICLE_GRAVITY * delta;
+        transform.translation += (particle.velocity * delta).extend(0.0);
+        transform.scale = Vec3::splat(1.0 - particle.age / PARTICLE_SECS);
+    }
+
+    // Starting another run, or a reset from anywhere, ends the celebration.
+    if timer_state.is_running || timer_state.remaining > 0.0 {
+        celebration.0 = None;
+    }
+    let Some(clock) = celebration.0.as_mut() else {
+        for (entity, ..) in &glows {
+            commands.entity(entity).despawn();
+        }
+        for (entity, _) in &flashes {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    clock.elapsed += delta;
+    let elapsed = clock.elapsed;
+    let Ok((entity, hourglass, sand_state)) = hourglass.single() else {
+        return;
+    };
+
+    let brightness = pulse(elapsed);
+    for (_, mut transform, material) in &mut glows {
+        transform.scale = Vec3::splat(1.0 + 0.06 * brightness);
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = hourglass.sand_color.with_alpha(0.1 + 0.25 * brightness);
+        }
+    }
+    for (_, mut background) in &mut flashes {
+        background.0 = Color::WHITE.with_alpha(flash_alpha(elapsed));
+    }
+
+    if settings.particles && elapsed >= clock.next_burst {
+        clock.next_burst += BURST_SECS;
+        // The bottom bulb is full, so the pile's crest sits at the neck.
+        let origin = Vec2::new(0.0, -sand_state.body_config.neck_style.height() / 2.0);
+        let mesh = meshes.add(Rectangle::new(PARTICLE_SIZE, PARTICLE_SIZE));
+        let material = materials.add(hourglass.sand_color);
+        let mut rng = rand::thread_rng();
+        for _ in 0..BURST_PARTICLES {
+            let angle = rng.gen_range(0.2 * PI..0.8 * PI);
+            let speed = rng.gen_range(90.0..220.0);
+            commands.spawn((
+                FinishParticle {
+                    velocity: Vec2::from_angle(angle) * speed,
+                    age: 0.0,
+                },
+                Mesh2d(mesh.clone()),
+                MeshMaterial2d(material.clone()),
+                Transform::from_translation(origin.extend(0.3)),
+                placement.render_layers.clone(),
+                ChildOf(entity),
+            ));
+        }
+    }
+}
+
+/// A click or key press while the celebration plays ends it, and resets the
+/// timer when so configured. The press is consumed so it does not also
+/// toggle the timer or trigger a shortcut; UI buttons still see it.
+fn acknowledge_finish(
+    mut celebration: ResMut<FinishCelebration>,
+    settings: Res<FinishSettings>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    mut timer_commands: EventWriter<TimerCommand>,
+) {
+    if !celebration.is_playing() {
+        return;
+    }
+    let pressed_keys: Vec<KeyCode> = keys.get_just_pressed().copied().collect();
+    let pressed_buttons: Vec<MouseButton> = mouse.get_just_pressed().copied().collect();
+    if pressed_keys.is_empty() && pressed_buttons.is_empty() {
+        return;
+    }
+    for key in pressed_keys {
+        keys.clear_just_pressed(key);
+    }
+    for button in pressed_buttons {
+        mouse.clear_just_pressed(button);
+    }
+
+    celebration.0 = None;
+    if settings.acknowledge == FinishAcknowledge::Reset {
+        timer_commands.write(TimerCommand::Reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HourglassTimerPlugin;
+    use approx::assert_abs_diff_eq;
+    use bevy::asset::AssetPlugin;
+    use bevy::render::mesh::MeshPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    /// A headless timer with two seconds left, stepping 100 ms a frame, and a
+    /// Space shortcut that toggles it. Input is pressed by hand, so a press
+    /// stays "just pressed" until something clears it.
+    fn celebration_app(settings: FinishSettings) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            MeshPlugin,
+            TransformPlugin,
+        ))
+        .init_asset::<ColorMaterial>()
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<ButtonInput<MouseButton>>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .insert_resource(settings)
+        .insert_resource(TimerState {
+            duration: 2.0,
+            remaining: 2.0,
+            is_running: true,
+        })
+        .add_plugins(HourglassTimerPlugin {
+            ui: false,
+            ..default()
+        })
+        .add_systems(
+            Update,
+            (|keys: Res<ButtonInput<KeyCode>>, mut timer: EventWriter<TimerCommand>| {
+                if keys.just_pressed(KeyCode::Space) {
+                    timer.write(TimerCommand::Toggle);
+                }
+            })
+            .in_set(TimerSystems::Input),
+        );
+        app
+    }
+
+    fn run_to_finish(app: &mut App) {
+        for _ in 0..30 {
+            app.update();
+        }
+        assert!(app.world().resource::<FinishCelebration>().is_playing());
+    }
+
+    fn count<C: Component>(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query_filtered::<(), With<C>>().iter(world).count()
+    }
+
+    fn press_space(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        app.update();
+    }
+
+    #[test]
+    fn finishing_plays_until_a_key_resets_the_timer() {
+        let mut app = celebration_app(FinishSettings {
+            flash: true,
+            ..default()
+        });
+        run_to_finish(&mut app);
+        assert_eq!(count::<FinishGlow>(&mut app), 1);
+        assert_eq!(count::<FinishFlash>(&mut app), 1);
+        assert!(count::<FinishParticle>(&mut app) > 0);
+
+        press_space(&mut app);
+        app.update();
+        assert!(!app.world().resource::<FinishCelebration>().is_playing());
+        assert_eq!(count::<FinishGlow>(&mut app), 0);
+        assert_eq!(count::<FinishFlash>(&mut app), 0);
+        // Space was consumed, so the reset timer did not also start.
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!(timer.remaining, 2.0);
+        assert!(!timer.is_running);
+    }
+
+    #[test]
+    fn dismissing_leaves_the_finished_timer_alone() {
+        let mut app = celebration_app(FinishSettings {
+            acknowledge: FinishAcknowledge::Dismiss,
+            ..default()
+        });
+        run_to_finish(&mut app);
+        press_space(&mut app);
+        assert!(!app.world().resource::<FinishCelebration>().is_playing());
+        assert_eq!(app.world().resource::<TimerState>().remaining, 0.0);
+    }
+
+    #[test]
+    fn nothing_plays_when_every_effect_is_off() {
+        let mut app = celebration_app(FinishSettings {
+            glow: false,
+            particles: false,
+            flash: false,
+            ..default()
+        });
+        for _ in 0..30 {
+            app.update();
+        }
+        assert!(!app.world().resource::<FinishCelebration>().is_playing());
+    }
+
+    #[test]
+    fn flash_fades_into_the_glow_pulse() {
+        assert_abs_diff_eq!(pulse(0.0), 0.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(pulse(PULSE_SECS / 2.0), 1.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(flash_alpha(0.0), 0.6, epsilon = 1e-6);
+        assert!(flash_alpha(2.0) <= 0.08 + 1e-3);
+    }
+}
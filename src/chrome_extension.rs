@@ -1,10 +1,23 @@
-use crate::resources::{AppearanceStateChanged, TimerState};
-#[cfg(any(test, target_arch = "wasm32"))]
-use crate::resources::{ColorMode, HourglassConfig, HourglassShape, ShapeMode};
-use crate::timer::{TimerCommand, TimerStateChanged, TimerSystems};
+use crate::presets::TimerPresets;
+#[cfg(target_arch = "wasm32")]
+use crate::presets::stored::{presets_from_json, presets_to_json};
+#[cfg(target_arch = "wasm32")]
+use crate::resources::HourglassConfig;
+#[cfg(target_arch = "wasm32")]
+use crate::resources::stored_colors::{colors_from_json, colors_to_json};
+use crate::resources::{AppearanceStateChanged, ColorPalette, RecentColors, TimerState};
+#[cfg(target_arch = "wasm32")]
+use crate::shapes::ShapeDefinition;
+use crate::shapes::ShapeLibrary;
+#[cfg(target_arch = "wasm32")]
+use crate::shapes::stored_shapes::{shapes_from_json, shapes_to_json};
+#[cfg(target_arch = "wasm32")]
+use crate::snapshot::{SnapshotV1, apply_snapshot, snapshot_from_resources};
+use crate::timer::{TimerCommand, TimerStateChanged, TimerSystems, now_ms};
 use bevy::prelude::*;
-use serde::{Deserialize, Serialize};
 
+#[cfg(target_arch = "wasm32")]
+use bevy::winit::{EventLoopProxy, EventLoopProxyWrapper, WakeUp};
 #[cfg(target_arch = "wasm32")]
 use std::cell::RefCell;
 #[cfg(target_arch = "wasm32")]
@@ -12,8 +25,6 @@ use wasm_bindgen::{JsCast, JsValue, closure::Closure};
 #[cfg(target_arch = "wasm32")]
 use web_sys::{CustomEvent, CustomEventInit};
 
-#[cfg(any(test, target_arch = "wasm32"))]
-const SNAPSHOT_VERSION: u8 = 1;
 #[cfg(target_arch = "wasm32")]
 const BOOTSTRAP_PROPERTY: &str = "__HOURGLASS_BOOTSTRAP_V1__";
 #[cfg(target_arch = "wasm32")]
@@ -24,8 +35,16 @@ const RESTORE_EVENT: &str = "hourglass-restore-v1";
 const READY_EVENT: &str = "hourglass-ready-v1";
 #[cfg(target_arch = "wasm32")]
 const STARTUP_STAGE_EVENT: &str = "hourglass-startup-stage-v1";
-#[cfg(any(test, target_arch = "wasm32"))]
-const MAX_DURATION_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+/// Presets outlive the timer snapshot, so they live in the side panel's own
+/// local storage rather than the session state the service worker clears.
+#[cfg(target_arch = "wasm32")]
+const PRESETS_STORAGE_KEY: &str = "hourglass-presets-v1";
+/// The edited palette and recent colours, kept alongside the presets.
+#[cfg(target_arch = "wasm32")]
+const COLORS_STORAGE_KEY: &str = "hourglass-colors-v1";
+/// Shapes saved from the shape editor, kept alongside the presets.
+#[cfg(target_arch = "wasm32")]
+const SHAPES_STORAGE_KEY: &str = "hourglass-shapes-v1";
 
 pub struct ChromeExtensionPlugin;
 
@@ -33,7 +52,19 @@ impl Plugin for ChromeExtensionPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ExtensionDeadline>()
             .init_resource::<ExtensionSyncRevision>()
-            .add_systems(PreStartup, initialize_extension_bridge)
+            .init_resource::<TimerPresets>()
+            .init_resource::<ColorPalette>()
+            .init_resource::<RecentColors>()
+            .init_resource::<ShapeLibrary>()
+            .add_systems(
+                PreStartup,
+                (
+                    initialize_extension_bridge,
+                    restore_presets,
+                    restore_colors,
+                    restore_shapes,
+                ),
+            )
             .add_systems(PostStartup, signal_extension_ready)
             .add_systems(Update, apply_queued_snapshots.in_set(TimerSystems::Restore))
             .add_systems(
@@ -41,7 +72,16 @@ impl Plugin for ChromeExtensionPlugin {
                 update_deadline_from_changes.in_set(TimerSystems::Deadline),
             )
             .add_systems(Update, update_wall_clock_timer.in_set(TimerSystems::Tick))
-            .add_systems(Update, emit_extension_state.in_set(TimerSystems::Observe));
+            .add_systems(
+                Update,
+                (
+                    emit_extension_state,
+                    persist_presets,
+                    persist_colors,
+                    persist_shapes,
+                )
+                    .in_set(TimerSystems::Observe),
+            );
     }
 }
 
@@ -52,88 +92,9 @@ struct ExtensionDeadline(Option<f64>);
 #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
 struct ExtensionSyncRevision(u64);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum ExtensionTimerStatus {
-    Idle,
-    Running,
-    Paused,
-    Finished,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum ExtensionColorMode {
-    Static,
-    Random,
-    Rainbow,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum ExtensionShape {
-    Classic,
-    Modern,
-    Slim,
-    Wide,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum ExtensionShapeMode {
-    Static,
-    Morphing,
-}
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ExtensionAppearanceV1 {
-    color_mode: ExtensionColorMode,
-    color_rgba: [f32; 4],
-    shape: ExtensionShape,
-    shape_mode: ExtensionShapeMode,
-}
-
-/// Versioned wire format shared with the side-panel loader and service worker.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ExtensionSnapshotV1 {
-    version: u8,
-    #[serde(default)]
-    revision: u64,
-    #[serde(default)]
-    source_id: String,
-    duration_ms: f64,
-    remaining_ms: f64,
-    status: ExtensionTimerStatus,
-    deadline_ms: Option<f64>,
-    #[serde(default)]
-    run_id: Option<String>,
-    #[serde(default)]
-    notified_run_id: Option<String>,
-    appearance: ExtensionAppearanceV1,
-}
-
 #[cfg(target_arch = "wasm32")]
 thread_local! {
-    static RESTORE_QUEUE: RefCell<Vec<ExtensionSnapshotV1>> = const { RefCell::new(Vec::new()) };
-}
-
-fn now_ms() -> f64 {
-    #[cfg(target_arch = "wasm32")]
-    {
-        js_sys::Date::now()
-    }
-
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs_f64()
-            * 1000.0
-    }
+    static RESTORE_QUEUE: RefCell<Vec<SnapshotV1>> = const { RefCell::new(Vec::new()) };
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -142,8 +103,9 @@ fn initialize_extension_bridge(
     mut deadline: ResMut<ExtensionDeadline>,
     mut sync_revision: ResMut<ExtensionSyncRevision>,
     mut config: ResMut<HourglassConfig>,
+    wake: Option<Res<EventLoopProxyWrapper<WakeUp>>>,
 ) {
-    install_restore_listener();
+    install_restore_listener(wake.map(|proxy| (*proxy).clone()));
 
     let Some(window) = web_sys::window() else {
         warn!("Chrome extension bridge could not access window");
@@ -155,14 +117,14 @@ fn initialize_extension_bridge(
     let Some(json) = value.as_string() else {
         return;
     };
-    match serde_json::from_str::<ExtensionSnapshotV1>(&json) {
+    match serde_json::from_str::<SnapshotV1>(&json) {
         Ok(snapshot) => {
             let revision = snapshot.revision;
             if apply_snapshot(
                 snapshot,
                 now_ms(),
                 &mut timer_state,
-                &mut deadline,
+                &mut deadline.0,
                 &mut config,
             ) {
                 sync_revision.0 = revision;
@@ -189,7 +151,7 @@ fn signal_extension_ready() {
 }
 
 #[cfg(target_arch = "wasm32")]
-pub(crate) fn report_startup_stage(stage: &str) {
+pub fn report_startup_stage(stage: &str) {
     let init = CustomEventInit::new();
     init.set_detail(&JsValue::from_str(stage));
     if let Ok(event) = CustomEvent::new_with_event_init_dict(STARTUP_STAGE_EVENT, &init) {
@@ -201,22 +163,29 @@ pub(crate) fn report_startup_stage(stage: &str) {
 
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(dead_code)]
-pub(crate) fn report_startup_stage(_stage: &str) {}
+pub fn report_startup_stage(_stage: &str) {}
 
 #[cfg(not(target_arch = "wasm32"))]
 fn signal_extension_ready() {}
 
 #[cfg(target_arch = "wasm32")]
-fn install_restore_listener() {
+/// Queue restored snapshots for `apply_queued_snapshots`, waking the event
+/// loop so a power-saving app applies them now rather than at its next tick.
+fn install_restore_listener(wake: Option<EventLoopProxy<WakeUp>>) {
     let Some(window) = web_sys::window() else {
         return;
     };
-    let listener = Closure::<dyn FnMut(CustomEvent)>::new(|event: CustomEvent| {
+    let listener = Closure::<dyn FnMut(CustomEvent)>::new(move |event: CustomEvent| {
         let Some(json) = event.detail().as_string() else {
             return;
         };
-        match serde_json::from_str::<ExtensionSnapshotV1>(&json) {
-            Ok(snapshot) => RESTORE_QUEUE.with(|queue| queue.borrow_mut().push(snapshot)),
+        match serde_json::from_str::<SnapshotV1>(&json) {
+            Ok(snapshot) => {
+                RESTORE_QUEUE.with(|queue| queue.borrow_mut().push(snapshot));
+                if let Some(wake) = &wake {
+                    let _ = wake.send_event(WakeUp);
+                }
+            }
             Err(error) => warn!("Ignoring invalid extension restore state: {error}"),
         }
     });
@@ -248,7 +217,7 @@ fn apply_queued_snapshots(
             snapshot,
             now_ms(),
             &mut timer_state,
-            &mut deadline,
+            &mut deadline.0,
             &mut config,
         ) {
             sync_revision.0 = revision;
@@ -336,246 +305,136 @@ fn emit_extension_state(
 }
 
 #[cfg(target_arch = "wasm32")]
-fn snapshot_from_resources(
-    timer_state: &TimerState,
-    deadline_ms: Option<f64>,
-    config: &HourglassConfig,
-) -> ExtensionSnapshotV1 {
-    let color = config.color.to_srgba();
-    ExtensionSnapshotV1 {
-        version: SNAPSHOT_VERSION,
-        revision: 0,
-        source_id: String::new(),
-        duration_ms: f64::from(timer_state.duration) * 1000.0,
-        remaining_ms: f64::from(timer_state.remaining) * 1000.0,
-        status: timer_status(timer_state),
-        deadline_ms,
-        run_id: None,
-        notified_run_id: None,
-        appearance: ExtensionAppearanceV1 {
-            color_mode: match config.color_mode {
-                ColorMode::Static => ExtensionColorMode::Static,
-                ColorMode::Random => ExtensionColorMode::Random,
-                ColorMode::Rainbow => ExtensionColorMode::Rainbow,
-            },
-            color_rgba: [color.red, color.green, color.blue, color.alpha],
-            shape: match config.shape_type {
-                HourglassShape::Classic => ExtensionShape::Classic,
-                HourglassShape::Modern => ExtensionShape::Modern,
-                HourglassShape::Slim => ExtensionShape::Slim,
-                HourglassShape::Wide => ExtensionShape::Wide,
-            },
-            shape_mode: match config.shape_mode {
-                ShapeMode::Static => ExtensionShapeMode::Static,
-                ShapeMode::Morphing => ExtensionShapeMode::Morphing,
-            },
-        },
-    }
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
 }
 
-#[cfg(any(test, target_arch = "wasm32"))]
-fn timer_status(timer_state: &TimerState) -> ExtensionTimerStatus {
-    if timer_state.remaining <= 0.0 {
-        ExtensionTimerStatus::Finished
-    } else if timer_state.is_running {
-        ExtensionTimerStatus::Running
-    } else if timer_state.remaining >= timer_state.duration {
-        ExtensionTimerStatus::Idle
-    } else {
-        ExtensionTimerStatus::Paused
+#[cfg(target_arch = "wasm32")]
+fn restore_presets(mut presets: ResMut<TimerPresets>) {
+    let Some(json) =
+        local_storage().and_then(|storage| storage.get_item(PRESETS_STORAGE_KEY).ok().flatten())
+    else {
+        return;
+    };
+    match presets_from_json(&json) {
+        Ok(restored) => *presets = restored,
+        Err(error) => warn!("Ignoring invalid saved presets: {error}"),
     }
 }
 
-#[cfg(any(test, target_arch = "wasm32"))]
-fn apply_snapshot(
-    snapshot: ExtensionSnapshotV1,
-    now_ms: f64,
-    timer_state: &mut TimerState,
-    deadline: &mut ExtensionDeadline,
-    config: &mut HourglassConfig,
-) -> bool {
-    if snapshot.version != SNAPSHOT_VERSION {
-        return false;
-    }
+#[cfg(not(target_arch = "wasm32"))]
+fn restore_presets() {}
 
-    let has_valid_duration = snapshot.duration_ms.is_finite() && snapshot.duration_ms >= 0.0;
-    let duration_ms = if has_valid_duration {
-        finite_clamp(snapshot.duration_ms, 0.0, MAX_DURATION_MS)
-    } else {
-        f64::from(TimerState::default().duration) * 1000.0
-    };
-    let stored_remaining_ms = if has_valid_duration {
-        finite_clamp(snapshot.remaining_ms, 0.0, duration_ms)
-    } else {
-        duration_ms
+#[cfg(target_arch = "wasm32")]
+fn persist_presets(presets: Res<TimerPresets>) {
+    if !presets.is_changed() || presets.is_added() {
+        return;
+    }
+    let Some(storage) = local_storage() else {
+        warn!("Extension local storage is unavailable; presets were not saved");
+        return;
     };
-    let mut resolved_deadline = snapshot.deadline_ms.filter(|value| value.is_finite());
-
-    let (remaining_ms, is_running) = if !has_valid_duration {
-        resolved_deadline = None;
-        (duration_ms, false)
-    } else {
-        match snapshot.status {
-            ExtensionTimerStatus::Running => {
-                let value = resolved_deadline
-                    .map(|value| (value - now_ms).max(0.0))
-                    .unwrap_or(stored_remaining_ms);
-                if resolved_deadline.is_none() && value > 0.0 {
-                    resolved_deadline = Some(now_ms + value);
-                }
-                (value, value > 0.0)
-            }
-            ExtensionTimerStatus::Paused => (stored_remaining_ms, false),
-            ExtensionTimerStatus::Idle => (duration_ms, false),
-            ExtensionTimerStatus::Finished => (0.0, false),
-        }
+    let Ok(json) = presets_to_json(&presets) else {
+        warn!("Could not serialize presets");
+        return;
     };
+    if let Err(error) = storage.set_item(PRESETS_STORAGE_KEY, &json) {
+        warn!("Could not save presets: {error:?}");
+    }
+}
 
-    timer_state.duration = (duration_ms / 1000.0) as f32;
-    timer_state.remaining = (remaining_ms / 1000.0) as f32;
-    timer_state.is_running = is_running;
-    deadline.0 = if is_running { resolved_deadline } else { None };
-
-    let [red, green, blue, alpha] = snapshot.appearance.color_rgba;
-    config.color = Color::srgba(
-        red.clamp(0.0, 1.0),
-        green.clamp(0.0, 1.0),
-        blue.clamp(0.0, 1.0),
-        alpha.clamp(0.0, 1.0),
-    );
-    config.color_mode = match snapshot.appearance.color_mode {
-        ExtensionColorMode::Static => ColorMode::Static,
-        ExtensionColorMode::Random => ColorMode::Random,
-        ExtensionColorMode::Rainbow => ColorMode::Rainbow,
-    };
-    config.shape_type = match snapshot.appearance.shape {
-        ExtensionShape::Classic => HourglassShape::Classic,
-        ExtensionShape::Modern => HourglassShape::Modern,
-        ExtensionShape::Slim => HourglassShape::Slim,
-        ExtensionShape::Wide => HourglassShape::Wide,
-    };
-    config.shape_mode = match snapshot.appearance.shape_mode {
-        ExtensionShapeMode::Static => ShapeMode::Static,
-        ExtensionShapeMode::Morphing => ShapeMode::Morphing,
+#[cfg(not(target_arch = "wasm32"))]
+fn persist_presets(_presets: Res<TimerPresets>) {}
+
+#[cfg(target_arch = "wasm32")]
+fn restore_colors(mut palette: ResMut<ColorPalette>, mut recent: ResMut<RecentColors>) {
+    let Some(json) =
+        local_storage().and_then(|storage| storage.get_item(COLORS_STORAGE_KEY).ok().flatten())
+    else {
+        return;
     };
-    true
+    match colors_from_json(&json) {
+        Ok((restored_palette, restored_recent)) => {
+            *palette = restored_palette;
+            *recent = restored_recent;
+        }
+        Err(error) => warn!("Ignoring invalid saved colours: {error}"),
+    }
 }
 
-#[cfg(any(test, target_arch = "wasm32"))]
-fn finite_clamp(value: f64, minimum: f64, maximum: f64) -> f64 {
-    if value.is_finite() {
-        value.clamp(minimum, maximum)
-    } else {
-        minimum
+#[cfg(not(target_arch = "wasm32"))]
+fn restore_colors() {}
+
+#[cfg(target_arch = "wasm32")]
+fn persist_colors(palette: Res<ColorPalette>, recent: Res<RecentColors>) {
+    let edited = (palette.is_changed() && !palette.is_added())
+        || (recent.is_changed() && !recent.is_added());
+    if !edited {
+        return;
+    }
+    let Some(storage) = local_storage() else {
+        warn!("Extension local storage is unavailable; colours were not saved");
+        return;
+    };
+    let Ok(json) = colors_to_json(&palette, &recent) else {
+        warn!("Could not serialize colours");
+        return;
+    };
+    if let Err(error) = storage.set_item(COLORS_STORAGE_KEY, &json) {
+        warn!("Could not save colours: {error:?}");
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[cfg(not(target_arch = "wasm32"))]
+fn persist_colors(_palette: Res<ColorPalette>, _recent: Res<RecentColors>) {}
 
-    fn snapshot(status: ExtensionTimerStatus, deadline_ms: Option<f64>) -> ExtensionSnapshotV1 {
-        ExtensionSnapshotV1 {
-            version: SNAPSHOT_VERSION,
-            revision: 2,
-            source_id: "test".to_string(),
-            duration_ms: 180_000.0,
-            remaining_ms: 90_000.0,
-            status,
-            deadline_ms,
-            run_id: Some("run".to_string()),
-            notified_run_id: None,
-            appearance: ExtensionAppearanceV1 {
-                color_mode: ExtensionColorMode::Static,
-                color_rgba: [0.8, 0.6, 0.2, 1.0],
-                shape: ExtensionShape::Classic,
-                shape_mode: ExtensionShapeMode::Static,
-            },
-        }
+#[cfg(target_arch = "wasm32")]
+fn restore_shapes(mut library: ResMut<ShapeLibrary>) {
+    let Some(json) =
+        local_storage().and_then(|storage| storage.get_item(SHAPES_STORAGE_KEY).ok().flatten())
+    else {
+        return;
+    };
+    match shapes_from_json(&json) {
+        Ok(shapes) => library.set_saved(shapes),
+        Err(error) => warn!("Ignoring invalid saved shapes: {error}"),
     }
+}
 
-    #[test]
-    fn running_restore_uses_absolute_deadline() {
-        let mut timer = TimerState::default();
-        let mut deadline = ExtensionDeadline::default();
-        let mut config = HourglassConfig::default();
-        assert!(apply_snapshot(
-            snapshot(ExtensionTimerStatus::Running, Some(160_000.0)),
-            100_000.0,
-            &mut timer,
-            &mut deadline,
-            &mut config,
-        ));
-        assert_eq!(timer.remaining, 60.0);
-        assert!(timer.is_running);
-        assert_eq!(deadline.0, Some(160_000.0));
-    }
+#[cfg(not(target_arch = "wasm32"))]
+fn restore_shapes() {}
 
-    #[test]
-    fn expired_restore_finishes_immediately() {
-        let mut timer = TimerState::default();
-        let mut deadline = ExtensionDeadline::default();
-        let mut config = HourglassConfig::default();
-        apply_snapshot(
-            snapshot(ExtensionTimerStatus::Running, Some(99_000.0)),
-            100_000.0,
-            &mut timer,
-            &mut deadline,
-            &mut config,
-        );
-        assert_eq!(timer.remaining, 0.0);
-        assert!(!timer.is_running);
-        assert_eq!(deadline.0, None);
+/// The library also changes while the shape editor previews a draft, so only
+/// write when the saved shapes themselves differ.
+#[cfg(target_arch = "wasm32")]
+fn persist_shapes(library: Res<ShapeLibrary>, mut saved: Local<Option<Vec<ShapeDefinition>>>) {
+    let Some(saved) = saved.as_mut() else {
+        *saved = Some(library.saved().to_vec());
+        return;
+    };
+    if !library.is_changed() || saved.as_slice() == library.saved() {
+        return;
     }
-
-    #[test]
-    fn unsupported_snapshot_version_is_ignored() {
-        let mut value = snapshot(ExtensionTimerStatus::Paused, None);
-        value.version = 9;
-        let mut timer = TimerState::default();
-        let before = timer.clone();
-        assert!(!apply_snapshot(
-            value,
-            100_000.0,
-            &mut timer,
-            &mut ExtensionDeadline::default(),
-            &mut HourglassConfig::default(),
-        ));
-        assert_eq!(timer, before);
+    *saved = library.saved().to_vec();
+    let Some(storage) = local_storage() else {
+        warn!("Extension local storage is unavailable; shapes were not saved");
+        return;
+    };
+    let Ok(json) = shapes_to_json(library.saved()) else {
+        warn!("Could not serialize shapes");
+        return;
+    };
+    if let Err(error) = storage.set_item(SHAPES_STORAGE_KEY, &json) {
+        warn!("Could not save shapes: {error:?}");
     }
+}
 
-    #[test]
-    fn zero_duration_restore_is_preserved() {
-        let mut value = snapshot(ExtensionTimerStatus::Finished, None);
-        value.duration_ms = 0.0;
-        value.remaining_ms = 0.0;
-        let mut timer = TimerState::default();
-        let mut deadline = ExtensionDeadline::default();
-        let mut config = HourglassConfig::default();
-        assert!(apply_snapshot(
-            value,
-            100_000.0,
-            &mut timer,
-            &mut deadline,
-            &mut config,
-        ));
-        assert_eq!(timer.duration, 0.0);
-        assert_eq!(timer.remaining, 0.0);
-        assert!(!timer.is_running);
-        assert_eq!(deadline.0, None);
-    }
+#[cfg(not(target_arch = "wasm32"))]
+fn persist_shapes(_library: Res<ShapeLibrary>) {}
 
-    #[test]
-    fn zero_remaining_serializes_as_finished_even_if_running_is_inconsistent() {
-        assert_eq!(
-            timer_status(&TimerState {
-                duration: 0.0,
-                remaining: 0.0,
-                is_running: true,
-            }),
-            ExtensionTimerStatus::Finished
-        );
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn restart_replaces_stale_deadline_before_wall_clock_tick() {
@@ -605,16 +464,4 @@ mod tests {
         let deadline = app.world().resource::<ExtensionDeadline>().0.unwrap();
         assert!(deadline > now_ms() + 179_000.0);
     }
-
-    #[test]
-    fn snapshot_round_trip_preserves_wire_shape() {
-        let value = snapshot(ExtensionTimerStatus::Paused, None);
-        let json = serde_json::to_string(&value).unwrap();
-        assert!(json.contains("\"durationMs\":180000.0"));
-        assert!(json.contains("\"shapeMode\":\"static\""));
-        assert_eq!(
-            serde_json::from_str::<ExtensionSnapshotV1>(&json).unwrap(),
-            value
-        );
-    }
 }
@@ -0,0 +1,283 @@
+//! Command-line flags for native launches, so scripts and window-manager
+//! keybindings can open the hourglass with a preset already applied.
+
+use crate::resources::{
+    COLOR_PALETTE_NAMES, ColorMode, HourglassConfig, HourglassShape, LaunchOverrides, TimerState,
+    ViewMode, parse_color,
+};
+use crate::timer::parse_duration;
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, WindowMode, WindowResolution};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "hourglass-timer",
+    version,
+    about = "A visual hourglass countdown timer"
+)]
+pub struct Cli {
+    /// Countdown length, e.g. `25m`, `1h30m`, `90s`, `25:00` or `25` (minutes).
+    #[arg(long, value_parser = parse_duration_arg)]
+    pub duration: Option<f32>,
+
+    /// Start the countdown immediately.
+    #[arg(long)]
+    pub start: bool,
+
+    /// Hourglass shape: classic, modern, slim, wide or the name of a shape in
+    /// `assets/shapes`.
+    #[arg(long, value_parser = parse_shape_arg)]
+    pub shape: Option<HourglassShape>,
+
+    /// Sand colour as `#rrggbb` hex or a palette name such as `blue`.
+    #[arg(long, value_parser = parse_color_arg)]
+    pub color: Option<Color>,
+
+    /// Colour mode: static, random, rainbow, or progress to shift through the
+    /// `gradient` in `assets/hourglass.ron` as the timer runs down.
+    #[arg(long, value_parser = parse_color_mode_arg)]
+    pub color_mode: Option<ColorMode>,
+
+    /// Start in morphing mode, blending between shapes.
+    #[arg(long)]
+    pub morphing: bool,
+
+    /// View: 2d, or 3d for a lit glass you can orbit with the right mouse
+    /// button and zoom with the scroll wheel.
+    #[arg(long, value_parser = parse_view_arg)]
+    pub view: Option<ViewMode>,
+
+    /// Initial window size in logical pixels, e.g. `480x720`.
+    #[arg(long, value_name = "WIDTHxHEIGHT", value_parser = parse_window_size_arg)]
+    pub window_size: Option<Vec2>,
+
+    /// Open in borderless fullscreen on the current monitor.
+    #[arg(long)]
+    pub fullscreen: bool,
+}
+
+/// Window flags from the command line, read by `AppPlugin` when it builds the
+/// primary window.
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Resource, Default)]
+pub struct WindowOptions {
+    pub size: Option<Vec2>,
+    pub fullscreen: bool,
+}
+
+impl WindowOptions {
+    pub fn apply(&self, window: &mut Window) {
+        if let Some(size) = self.size {
+            window.resolution = WindowResolution::new(size.x, size.y);
+        }
+        if self.fullscreen {
+            window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+        }
+    }
+}
+
+impl Cli {
+    /// Insert the launch resources before `HourglassTimerPlugin` initialises
+    /// its defaults.
+    pub fn insert_into(&self, app: &mut App) {
+        app.register_type::<WindowOptions>()
+            .register_type::<LaunchOverrides>()
+            .insert_resource(self.hourglass_config())
+            .insert_resource(self.timer_state())
+            .insert_resource(WindowOptions {
+                size: self.window_size,
+                fullscreen: self.fullscreen,
+            });
+        let overrides = self.launch_overrides();
+        if overrides != LaunchOverrides::default() {
+            app.insert_resource(overrides);
+        }
+    }
+
+    pub fn launch_overrides(&self) -> LaunchOverrides {
+        LaunchOverrides {
+            duration: self.duration,
+            start: self.start,
+            shape: self.shape.clone(),
+            color: self.color,
+            color_mode: self.color_mode,
+            morphing: self.morphing,
+            view: self.view,
+        }
+    }
+
+    pub fn hourglass_config(&self) -> HourglassConfig {
+        let mut config = HourglassConfig::default();
+        self.launch_overrides().apply_to_config(&mut config);
+        config
+    }
+
+    pub fn timer_state(&self) -> TimerState {
+        let mut timer = TimerState::default();
+        self.launch_overrides().apply_to_timer(&mut timer);
+        timer
+    }
+}
+
+fn parse_duration_arg(text: &str) -> Result<f32, String> {
+    match parse_duration(text) {
+        Some(seconds) if seconds > 0.0 => Ok(seconds),
+        _ => Err(format!(
+            "`{text}` is not a duration between 1s and 24h (try `25m`, `1h30m` or `90s`)"
+        )),
+    }
+}
+
+/// Shape files are only read once the app is running, so any name is taken
+/// here; one that never loads is drawn as classic with a warning.
+fn parse_shape_arg(text: &str) -> Result<HourglassShape, String> {
+    HourglassShape::from_name(text).ok_or_else(|| {
+        let names: Vec<_> = HourglassShape::BUILTIN
+            .iter()
+            .map(|shape| shape.name())
+            .collect();
+        format!(
+            "`{text}` is not a shape name (try {}, or a shape from `assets/shapes`)",
+            names.join(", ")
+        )
+    })
+}
+
+fn parse_color_mode_arg(text: &str) -> Result<ColorMode, String> {
+    ColorMode::from_name(text).ok_or_else(|| {
+        let names: Vec<_> = ColorMode::ALL.iter().map(|mode| mode.name()).collect();
+        format!(
+            "unknown color mode `{text}` (expected one of: {})",
+            names.join(", ")
+        )
+    })
+}
+
+fn parse_view_arg(text: &str) -> Result<ViewMode, String> {
+    ViewMode::from_name(text).ok_or_else(|| {
+        let names: Vec<_> = ViewMode::ALL.iter().map(|view| view.name()).collect();
+        format!(
+            "unknown view `{text}` (expected one of: {})",
+            names.join(", ")
+        )
+    })
+}
+
+fn parse_color_arg(text: &str) -> Result<Color, String> {
+    parse_color(text).ok_or_else(|| {
+        format!(
+            "`{text}` is not a hex colour like `#ff8800` or one of: {}",
+            COLOR_PALETTE_NAMES.join(", ")
+        )
+    })
+}
+
+fn parse_window_size_arg(text: &str) -> Result<Vec2, String> {
+    let invalid = || format!("`{text}` is not a window size like `480x720`");
+    let (width, height) = text.trim().split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width: f32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: f32 = height.trim().parse().map_err(|_| invalid())?;
+    if !(width.is_finite() && height.is_finite() && width >= 1.0 && height >= 1.0) {
+        return Err(invalid());
+    }
+    Ok(Vec2::new(width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{ShapeMode, palette_color_by_name};
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("hourglass-timer").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn flags_initialise_config_and_timer() {
+        let cli = parse(&[
+            "--duration",
+            "25m",
+            "--start",
+            "--shape",
+            "slim",
+            "--color",
+            "#ff8800",
+            "--color-mode",
+            "rainbow",
+            "--morphing",
+            "--view",
+            "3D",
+        ])
+        .unwrap();
+
+        let timer = cli.timer_state();
+        assert_eq!(timer.duration, 1500.0);
+        assert_eq!(timer.remaining, 1500.0);
+        assert!(timer.is_running);
+
+        let config = cli.hourglass_config();
+        assert_eq!(config.shape_type, HourglassShape::SLIM);
+        assert_eq!(config.color_mode, ColorMode::Rainbow);
+        assert_eq!(config.shape_mode, ShapeMode::Morphing);
+        assert_eq!(config.view, ViewMode::ThreeD);
+        assert_eq!(config.color, Color::from(Srgba::rgb_u8(0xff, 0x88, 0x00)));
+        assert_ne!(cli.launch_overrides(), LaunchOverrides::default());
+    }
+
+    #[test]
+    fn no_flags_keep_defaults() {
+        let cli = parse(&[]).unwrap();
+        assert_eq!(cli.timer_state(), TimerState::default());
+        assert_eq!(
+            cli.hourglass_config().color,
+            HourglassConfig::default().color
+        );
+        assert_eq!(cli.launch_overrides(), LaunchOverrides::default());
+    }
+
+    #[test]
+    fn window_flags_shape_the_primary_window() {
+        let cli = parse(&["--window-size", "480x720", "--fullscreen"]).unwrap();
+        let mut window = Window::default();
+        WindowOptions {
+            size: cli.window_size,
+            fullscreen: cli.fullscreen,
+        }
+        .apply(&mut window);
+        assert_eq!(window.resolution.width(), 480.0);
+        assert_eq!(window.resolution.height(), 720.0);
+        assert!(matches!(window.mode, WindowMode::BorderlessFullscreen(_)));
+        assert_eq!(cli.launch_overrides(), LaunchOverrides::default());
+    }
+
+    #[test]
+    fn invalid_values_are_reported() {
+        for args in [
+            ["--duration", "soon"],
+            ["--duration", "25h"],
+            ["--shape", " "],
+            ["--color", "#ggg"],
+            ["--color-mode", "sparkly"],
+            ["--view", "4d"],
+            ["--window-size", "wide"],
+        ] {
+            let error = parse(&args).unwrap_err();
+            assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
+            assert!(error.to_string().contains(args[1]), "{error}");
+        }
+        assert!(parse(&["--colour", "red"]).is_err());
+    }
+
+    #[test]
+    fn shapes_from_asset_files_are_accepted_by_name() {
+        let cli = parse(&["--shape", "Tall Vase"]).unwrap();
+        assert_eq!(cli.hourglass_config().shape_type.name(), "tall vase");
+    }
+
+    #[test]
+    fn palette_names_are_accepted_as_colors() {
+        let cli = parse(&["--color", "Blue"]).unwrap();
+        assert_eq!(cli.color, palette_color_by_name("blue"));
+    }
+}
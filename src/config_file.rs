@@ -0,0 +1,1178 @@
+//! Declarative `assets/hourglass.ron` config. It is loaded through the asset
+//! server, so builds with `file_watcher` (such as `dev_native`) apply edits
+//! while the app is running. Invalid files are reported in a toast and the
+//! previous settings stay in effect.
+
+use crate::resources::{
+    AppearanceStateChanged, CascadeSettings, ColorGradient, ColorMode, ColorPalette,
+    FinishAcknowledge, FinishSettings, GlassSettings, GradientKey, GradientStop,
+    GraduationSettings, HourglassConfig, HourglassShape, LaunchOverrides, MAX_GRADIENT_STOPS,
+    MAX_SAND_LAYERS, MorphEasing, MorphSettings, MorphSync, SandLayer, SandLayers, ShapeMode,
+    TimerState, UrgencySettings, ViewMode, parse_color,
+};
+use crate::shapes::ShapeLibrary;
+use crate::timer::{TimerCommand, TimerSystems, parse_duration};
+use crate::ui::shortcuts::{KeyBindings, KeyChord};
+use crate::ui::toast::ShowToast;
+use crate::ui::{AppearancePanelVisible, TimerPanelVisible};
+use bevy::asset::io::{AssetReaderError, Reader};
+use bevy::asset::{AssetLoadError, AssetLoadFailedEvent, AssetLoader, LoadContext, ron};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fmt;
+
+/// Path of the config file inside the asset directory.
+const CONFIG_FILE_PATH: &str = "hourglass.ron";
+
+pub struct ConfigFilePlugin;
+
+impl Plugin for ConfigFilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<HourglassConfigFile>()
+            .init_asset_loader::<HourglassConfigFileLoader>()
+            .add_event::<ShowToast>()
+            .init_resource::<ColorPalette>()
+            .init_resource::<ShapeLibrary>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<MorphSettings>()
+            .init_resource::<FinishSettings>()
+            .init_resource::<UrgencySettings>()
+            .init_resource::<GraduationSettings>()
+            .init_resource::<CascadeSettings>()
+            .init_resource::<SandLayers>()
+            .init_resource::<GlassSettings>()
+            .init_resource::<AppliedConfigFile>()
+            .add_systems(Startup, load_config_file)
+            .add_systems(
+                Update,
+                (report_config_load_failures, apply_config_file).in_set(TimerSystems::Input),
+            );
+    }
+}
+
+/// The file as written. Every field is optional; anything left out keeps the
+/// built-in default.
+#[derive(Asset, TypePath, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HourglassConfigFile {
+    /// Default countdown, e.g. `"25m"` or `"1h30m"`.
+    pub duration: Option<String>,
+    /// `#rrggbb` hex or a palette name.
+    pub color: Option<String>,
+    pub color_mode: Option<String>,
+    /// Stops for the `progress` colour mode: `[("100%", "green"), ...]` keyed
+    /// to the fraction left, or `[("5m", "#ffaa00"), ...]` to the time left.
+    pub gradient: Option<Vec<(String, String)>>,
+    /// Stripes the sand, first layer to drain first: `[("25m", "red"),
+    /// ("5m", "#3399cc")]`. Sizes are durations or percentages, scaled to
+    /// fill the glass.
+    pub layers: Option<Vec<(String, String)>>,
+    pub shape: Option<String>,
+    pub morphing: Option<bool>,
+    /// `"2d"` or `"3d"`.
+    pub view: Option<String>,
+    pub morph: MorphFile,
+    /// Replaces the colour row swatches: `[("sand", "#cc9933"), ...]`.
+    pub palette: Option<Vec<(String, String)>>,
+    pub keybindings: KeyBindingsFile,
+    pub finish: FinishFile,
+    pub urgency: UrgencyFile,
+    pub graduations: GraduationsFile,
+    pub cascade: CascadeFile,
+    pub glass: GlassFile,
+    pub ui: UiTogglesFile,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MorphFile {
+    /// Shape names in the order to morph through; every shape when left out.
+    pub shapes: Option<Vec<String>>,
+    /// One pass through `shapes` when following the clock, e.g. `"8s"`.
+    pub period: Option<String>,
+    pub easing: Option<String>,
+    /// `"clock"` or `"progress"`.
+    pub sync: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FinishFile {
+    pub glow: Option<bool>,
+    pub particles: Option<bool>,
+    pub flash: Option<bool>,
+    /// `"reset"` or `"dismiss"`.
+    pub acknowledge: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrgencyFile {
+    /// How long before the deadline the effects start, e.g. `"10s"`.
+    pub threshold: Option<String>,
+    /// From 0 to 1.
+    pub intensity: Option<f32>,
+    pub reduced_motion: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GraduationsFile {
+    pub enabled: Option<bool>,
+    /// Time between marks, e.g. `"5m"`, or `"auto"`.
+    pub interval: Option<String>,
+    pub labels: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CascadeFile {
+    /// Countdowns longer than this run an hour at a time, e.g. `"2h"`, or
+    /// `"0s"` for never.
+    pub threshold: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GlassFile {
+    pub shaded: Option<bool>,
+    /// `#rrggbb` hex or a palette name.
+    pub tint: Option<String>,
+    /// From 0 to 1.
+    pub highlight: Option<f32>,
+    /// From 0 to 1.
+    pub refraction: Option<f32>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindingsFile {
+    pub command_palette: Option<String>,
+    pub start_pause: Option<String>,
+    pub reset: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiTogglesFile {
+    pub timer_panel: Option<bool>,
+    pub appearance_panel: Option<bool>,
+}
+
+/// A config file whose values have all been parsed and checked.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ConfigSettings {
+    duration: Option<f32>,
+    color: Option<Color>,
+    color_mode: Option<ColorMode>,
+    gradient: Option<ColorGradient>,
+    layers: SandLayers,
+    shape: Option<HourglassShape>,
+    morphing: Option<bool>,
+    view: Option<ViewMode>,
+    morph: MorphSettings,
+    palette: Option<ColorPalette>,
+    keybindings: KeyBindings,
+    finish: FinishSettings,
+    urgency: UrgencySettings,
+    graduations: GraduationSettings,
+    cascade: CascadeSettings,
+    glass: GlassSettings,
+    timer_panel: Option<bool>,
+    appearance_panel: Option<bool>,
+}
+
+impl HourglassConfigFile {
+    /// Check every value, collecting all problems rather than stopping at the
+    /// first so one edit-reload cycle can fix them together.
+    fn validate(&self, shapes: &ShapeLibrary) -> Result<ConfigSettings, Vec<String>> {
+        let mut errors = Vec::new();
+
+        let duration = self.duration.as_deref().and_then(|text| {
+            let seconds = parse_duration(text);
+            if seconds.is_none() {
+                errors.push(format!("duration: `{text}` is not a duration up to 24h"));
+            }
+            seconds
+        });
+
+        let palette = self.palette.as_ref().and_then(|entries| {
+            if entries.is_empty() {
+                errors.push("palette: needs at least one colour".to_string());
+                return None;
+            }
+            let mut swatches = Vec::with_capacity(entries.len());
+            for (name, text) in entries {
+                match parse_color(text) {
+                    Some(color) => swatches.push((name.clone(), color)),
+                    None => errors.push(format!("palette: `{text}` ({name}) is not a colour")),
+                }
+            }
+            Some(ColorPalette(swatches))
+        });
+
+        let named_color = |text: &str| {
+            palette
+                .as_ref()
+                .and_then(|palette| palette.color_by_name(text))
+                .or_else(|| parse_color(text))
+        };
+
+        let color = self.color.as_deref().and_then(|text| {
+            let color = named_color(text);
+            if color.is_none() {
+                errors.push(format!(
+                    "color: `{text}` is not a hex colour or palette name"
+                ));
+            }
+            color
+        });
+
+        let color_mode = self.color_mode.as_deref().and_then(|text| {
+            let mode = ColorMode::from_name(text);
+            if mode.is_none() {
+                errors.push(format!(
+                    "color_mode: `{text}` is not one of static, random, rainbow, progress"
+                ));
+            }
+            mode
+        });
+
+        let view = self.view.as_deref().and_then(|text| {
+            let view = ViewMode::from_name(text);
+            if view.is_none() {
+                errors.push(format!("view: `{text}` is not one of 2d, 3d"));
+            }
+            view
+        });
+
+        let gradient = self.gradient.as_ref().and_then(|entries| {
+            if entries.is_empty() || entries.len() > MAX_GRADIENT_STOPS {
+                errors.push(format!(
+                    "gradient: needs between 1 and {MAX_GRADIENT_STOPS} stops"
+                ));
+                return None;
+            }
+            let mut keys = Vec::with_capacity(entries.len());
+            let mut stops = Vec::with_capacity(entries.len());
+            for (at, text) in entries {
+                let position = match at.trim().strip_suffix('%') {
+                    Some(percent) => percent
+                        .trim()
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|percent| (0.0..=100.0).contains(percent))
+                        .map(|percent| (GradientKey::Fraction, percent / 100.0)),
+                    None => parse_duration(at).map(|seconds| (GradientKey::Seconds, seconds)),
+                };
+                let Some((key, at_value)) = position else {
+                    errors.push(format!(
+                        "gradient: `{at}` is not a percentage or a duration"
+                    ));
+                    continue;
+                };
+                let Some(color) = named_color(text) else {
+                    errors.push(format!("gradient: `{text}` ({at}) is not a colour"));
+                    continue;
+                };
+                keys.push(key);
+                stops.push(GradientStop {
+                    at: at_value,
+                    color,
+                });
+            }
+            if keys.windows(2).any(|pair| pair[0] != pair[1]) {
+                errors.push("gradient: mixes percentages and durations".to_string());
+                return None;
+            }
+            let key = *keys.first()?;
+            Some(ColorGradient { key, stops }.sanitized())
+        });
+
+        let mut layers = SandLayers::default();
+        if let Some(entries) = &self.layers {
+            if entries.len() > MAX_SAND_LAYERS {
+                errors.push(format!("layers: needs at most {MAX_SAND_LAYERS} layers"));
+            }
+            let mut percentages = Vec::with_capacity(entries.len());
+            for (size, text) in entries.iter().take(MAX_SAND_LAYERS) {
+                let share = match size.trim().strip_suffix('%') {
+                    Some(percent) => percent.trim().parse::<f32>().ok().map(|p| (true, p)),
+                    None => parse_duration(size).map(|seconds| (false, seconds)),
+                };
+                let Some((percentage, share)) = share.filter(|(_, share)| *share > 0.0) else {
+                    errors.push(format!(
+                        "layers: `{size}` is not a positive percentage or duration"
+                    ));
+                    continue;
+                };
+                let Some(color) = named_color(text) else {
+                    errors.push(format!("layers: `{text}` ({size}) is not a colour"));
+                    continue;
+                };
+                percentages.push(percentage);
+                layers.0.push(SandLayer { share, color });
+            }
+            if percentages.windows(2).any(|pair| pair[0] != pair[1]) {
+                errors.push("layers: mixes percentages and durations".to_string());
+            }
+        }
+
+        let shape = self.shape.as_deref().and_then(|text| {
+            let shape = HourglassShape::from_name(text).filter(|shape| shapes.accepts(shape));
+            if shape.is_none() {
+                errors.push(format!("shape: `{text}` is not one of {}", shapes.names()));
+            }
+            shape
+        });
+
+        let mut morph = MorphSettings::default();
+        for text in self.morph.shapes.iter().flatten() {
+            match HourglassShape::from_name(text).filter(|shape| shapes.accepts(shape)) {
+                Some(shape) => morph.sequence.push(shape),
+                None => errors.push(format!(
+                    "morph.shapes: `{text}` is not one of {}",
+                    shapes.names()
+                )),
+            }
+        }
+        if let Some(text) = &self.morph.period {
+            match parse_duration(text).filter(|seconds| *seconds > 0.0) {
+                Some(seconds) => morph.period_secs = seconds,
+                None => errors.push(format!("morph.period: `{text}` is not a duration")),
+            }
+        }
+        if let Some(text) = &self.morph.easing {
+            match MorphEasing::from_name(text) {
+                Some(easing) => morph.easing = easing,
+                None => errors.push(format!(
+                    "morph.easing: `{text}` is not one of linear, smooth, ease-in, ease-out"
+                )),
+            }
+        }
+        if let Some(text) = &self.morph.sync {
+            match MorphSync::from_name(text) {
+                Some(sync) => morph.sync = sync,
+                None => errors.push(format!(
+                    "morph.sync: `{text}` is not one of clock, progress"
+                )),
+            }
+        }
+
+        let mut keybindings = KeyBindings::default();
+        for (name, text, slot) in [
+            (
+                "command_palette",
+                &self.keybindings.command_palette,
+                &mut keybindings.command_palette,
+            ),
+            (
+                "start_pause",
+                &self.keybindings.start_pause,
+                &mut keybindings.start_pause,
+            ),
+            ("reset", &self.keybindings.reset, &mut keybindings.reset),
+        ] {
+            if let Some(text) = text {
+                match KeyChord::parse(text) {
+                    Ok(chord) => *slot = chord,
+                    Err(error) => errors.push(format!("keybindings.{name}: {error}")),
+                }
+            }
+        }
+
+        let defaults = FinishSettings::default();
+        let mut finish = FinishSettings {
+            glow: self.finish.glow.unwrap_or(defaults.glow),
+            particles: self.finish.particles.unwrap_or(defaults.particles),
+            flash: self.finish.flash.unwrap_or(defaults.flash),
+            acknowledge: defaults.acknowledge,
+        };
+        if let Some(text) = &self.finish.acknowledge {
+            match FinishAcknowledge::from_name(text) {
+                Some(acknowledge) => finish.acknowledge = acknowledge,
+                None => errors.push(format!(
+                    "finish.acknowledge: `{text}` is not one of reset, dismiss"
+                )),
+            }
+        }
+
+        let mut urgency = UrgencySettings {
+            reduced_motion: self
+                .urgency
+                .reduced_motion
+                .unwrap_or(UrgencySettings::default().reduced_motion),
+            ..default()
+        };
+        if let Some(text) = &self.urgency.threshold {
+            match parse_duration(text) {
+                Some(seconds) => urgency.threshold_secs = seconds,
+                None => errors.push(format!("urgency.threshold: `{text}` is not a duration")),
+            }
+        }
+        if let Some(intensity) = self.urgency.intensity {
+            if (0.0..=1.0).contains(&intensity) {
+                urgency.intensity = intensity;
+            } else {
+                errors.push(format!(
+                    "urgency.intensity: `{intensity}` is not between 0 and 1"
+                ));
+            }
+        }
+
+        let defaults = GraduationSettings::default();
+        let mut graduations = GraduationSettings {
+            enabled: self.graduations.enabled.unwrap_or(defaults.enabled),
+            interval_secs: defaults.interval_secs,
+            labels: self.graduations.labels.unwrap_or(defaults.labels),
+        };
+        if let Some(text) = &self.graduations.interval
+            && !text.trim().eq_ignore_ascii_case("auto")
+        {
+            match parse_duration(text).filter(|seconds| *seconds > 0.0) {
+                Some(seconds) => graduations.interval_secs = Some(seconds),
+                None => errors.push(format!(
+                    "graduations.interval: `{text}` is not a duration or auto"
+                )),
+            }
+        }
+
+        let mut cascade = CascadeSettings::default();
+        if let Some(text) = &self.cascade.threshold {
+            match parse_duration(text) {
+                Some(seconds) => cascade.threshold_secs = seconds,
+                None => errors.push(format!("cascade.threshold: `{text}` is not a duration")),
+            }
+        }
+
+        let mut glass = GlassSettings {
+            shaded: self.glass.shaded.unwrap_or(GlassSettings::default().shaded),
+            ..default()
+        };
+        if let Some(text) = &self.glass.tint {
+            match named_color(text) {
+                Some(tint) => glass.tint = tint,
+                None => errors.push(format!(
+                    "glass.tint: `{text}` is not a hex colour or palette name"
+                )),
+            }
+        }
+        for (name, value, slot) in [
+            ("highlight", self.glass.highlight, &mut glass.highlight),
+            ("refraction", self.glass.refraction, &mut glass.refraction),
+        ] {
+            match value {
+                Some(value) if (0.0..=1.0).contains(&value) => *slot = value,
+                Some(value) => {
+                    errors.push(format!("glass.{name}: `{value}` is not between 0 and 1"))
+                }
+                None => {}
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(ConfigSettings {
+            duration,
+            color,
+            color_mode,
+            gradient,
+            layers,
+            shape,
+            morphing: self.morphing,
+            view,
+            morph,
+            palette,
+            keybindings,
+            finish,
+            urgency,
+            graduations,
+            cascade,
+            glass,
+            timer_panel: self.ui.timer_panel,
+            appearance_panel: self.ui.appearance_panel,
+        })
+    }
+}
+
+#[derive(Default)]
+struct HourglassConfigFileLoader;
+
+#[derive(Debug)]
+enum ConfigFileLoadError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for ConfigFileLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read config: {error}"),
+            Self::Ron(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigFileLoadError {}
+
+impl AssetLoader for HourglassConfigFileLoader {
+    type Asset = HourglassConfigFile;
+    type Settings = ();
+    type Error = ConfigFileLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(ConfigFileLoadError::Io)?;
+        parse_config_file(&bytes).map_err(ConfigFileLoadError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+/// Optional fields may be written bare (`shape: "slim"`) rather than as
+/// `Some("slim")`.
+fn parse_config_file(bytes: &[u8]) -> Result<HourglassConfigFile, ron::error::SpannedError> {
+    ron::Options::default()
+        .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+        .from_bytes(bytes)
+}
+
+#[derive(Resource)]
+struct ConfigFileHandle(Handle<HourglassConfigFile>);
+
+/// The settings applied from the last valid load, so a reload only touches
+/// the values that were actually edited.
+#[derive(Resource, Default)]
+struct AppliedConfigFile(Option<ConfigSettings>);
+
+fn load_config_file(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ConfigFileHandle(asset_server.load(CONFIG_FILE_PATH)));
+}
+
+fn report_config_load_failures(
+    mut failures: EventReader<AssetLoadFailedEvent<HourglassConfigFile>>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for failure in failures.read() {
+        // Running without a config file is the normal case.
+        if matches!(
+            failure.error,
+            AssetLoadError::AssetReaderError(AssetReaderError::NotFound(_))
+        ) {
+            debug!("No config file at {}", failure.path);
+            continue;
+        }
+        warn!(
+            "Config file {} failed to load: {}",
+            failure.path, failure.error
+        );
+        toasts.write(ShowToast(format!("{}: {}", failure.path, failure.error)));
+    }
+}
+
+fn apply_config_file(
+    mut asset_events: EventReader<AssetEvent<HourglassConfigFile>>,
+    handle: Option<Res<ConfigFileHandle>>,
+    files: Res<Assets<HourglassConfigFile>>,
+    mut applied: ResMut<AppliedConfigFile>,
+    mut config: ResMut<HourglassConfig>,
+    timer_state: Res<TimerState>,
+    mut palette: ResMut<ColorPalette>,
+    shapes: Res<ShapeLibrary>,
+    mut keybindings: ResMut<KeyBindings>,
+    (mut morph, mut finish, mut urgency, mut graduations, mut cascade, mut layers, mut glass): (
+        ResMut<MorphSettings>,
+        ResMut<FinishSettings>,
+        ResMut<UrgencySettings>,
+        ResMut<GraduationSettings>,
+        ResMut<CascadeSettings>,
+        ResMut<SandLayers>,
+        ResMut<GlassSettings>,
+    ),
+    mut timer_panel_visible: ResMut<TimerPanelVisible>,
+    mut appearance_panel_visible: ResMut<AppearancePanelVisible>,
+    mut timer_commands: EventWriter<TimerCommand>,
+    mut appearance_changed: EventWriter<AppearanceStateChanged>,
+    mut toasts: EventWriter<ShowToast>,
+    launch_overrides: Option<Res<LaunchOverrides>>,
+) {
+    let Some(handle) = handle else {
+        asset_events.clear();
+        return;
+    };
+    let reloaded = asset_events.read().any(|event| match event {
+        AssetEvent::Added { id } | AssetEvent::Modified { id } => *id == handle.0.id(),
+        _ => false,
+    });
+    let Some(file) = reloaded.then(|| files.get(&handle.0)).flatten() else {
+        return;
+    };
+    let settings = match file.validate(&shapes) {
+        Ok(settings) => settings,
+        Err(errors) => {
+            warn!("Ignoring invalid {CONFIG_FILE_PATH}: {}", errors.join("; "));
+            toasts.write(ShowToast(format!(
+                "{CONFIG_FILE_PATH}: {}",
+                errors.join("\n")
+            )));
+            return;
+        }
+    };
+
+    let previous = applied.0.take();
+    let edited = |unchanged: &dyn Fn(&ConfigSettings) -> bool| {
+        previous
+            .as_ref()
+            .is_none_or(|previous| !unchanged(previous))
+    };
+
+    // On launch the file only provides defaults: a restored session wins, and
+    // command-line flags are put back on top. Later edits always apply.
+    let launch_overrides = launch_overrides
+        .filter(|_| previous.is_none())
+        .map(|overrides| overrides.clone())
+        .unwrap_or_default();
+    let mut launch_timer = TimerState::default();
+    let mut launch_config = HourglassConfig::default();
+    launch_overrides.apply_to_timer(&mut launch_timer);
+    launch_overrides.apply_to_config(&mut launch_config);
+    let apply_session =
+        previous.is_some() || (*timer_state == launch_timer && *config == launch_config);
+
+    if apply_session {
+        if let Some(seconds) = settings.duration
+            && edited(&|p| p.duration == settings.duration)
+            // Setting the duration stops the timer, so leave a `--start` run.
+            && launch_overrides.duration.is_none()
+            && !launch_overrides.start
+        {
+            timer_commands.write(TimerCommand::SetDuration(seconds));
+        }
+
+        let mut appearance = config.clone();
+        if let Some(color) = settings.color
+            && edited(&|p| p.color == settings.color)
+        {
+            appearance.color = color;
+        }
+        if let Some(color_mode) = settings.color_mode
+            && edited(&|p| p.color_mode == settings.color_mode)
+        {
+            appearance.color_mode = color_mode;
+        }
+        if let Some(gradient) = &settings.gradient
+            && edited(&|p| p.gradient == settings.gradient)
+        {
+            appearance.gradient = gradient.clone();
+        }
+        if let Some(shape) = &settings.shape
+            && edited(&|p| p.shape == settings.shape)
+        {
+            appearance.shape_type = shape.clone();
+        }
+        if let Some(morphing) = settings.morphing
+            && edited(&|p| p.morphing == settings.morphing)
+        {
+            appearance.shape_mode = if morphing {
+                ShapeMode::Morphing
+            } else {
+                ShapeMode::Static
+            };
+        }
+        if let Some(view) = settings.view
+            && edited(&|p| p.view == settings.view)
+        {
+            appearance.view = view;
+        }
+        launch_overrides.apply_to_config(&mut appearance);
+        if appearance != *config {
+            *config = appearance;
+            appearance_changed.write_default();
+        }
+    }
+
+    // Palette, shortcuts, morphing, sand layers, glass shading, graduations,
+    // cascading, the urgency and finish effects and UI toggles are
+    // preferences rather than session state, so they follow the file. The
+    // palette can also be edited in the colour row and is restored from the
+    // last session, so on launch the file only replaces the built-in one, and
+    // later only a changed palette in the file replaces it.
+    let replace_palette = match &previous {
+        None => settings.palette.is_some() && *palette == ColorPalette::default(),
+        Some(previous) => previous.palette != settings.palette,
+    };
+    if replace_palette {
+        palette.set_if_neq(settings.palette.clone().unwrap_or_default());
+    }
+    keybindings.set_if_neq(settings.keybindings);
+    morph.set_if_neq(settings.morph.clone());
+    finish.set_if_neq(settings.finish.clone());
+    urgency.set_if_neq(settings.urgency.clone());
+    graduations.set_if_neq(settings.graduations.clone());
+    cascade.set_if_neq(settings.cascade.clone());
+    layers.set_if_neq(settings.layers.clone());
+    glass.set_if_neq(settings.glass.clone());
+    if let Some(visible) = settings.timer_panel
+        && edited(&|p| p.timer_panel == settings.timer_panel)
+    {
+        timer_panel_visible.0 = visible;
+    }
+    if let Some(visible) = settings.appearance_panel
+        && edited(&|p| p.appearance_panel == settings.appearance_panel)
+    {
+        appearance_panel_visible.0 = visible;
+    }
+
+    applied.0 = Some(settings);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::TimerPlugin;
+
+    const SHIPPED_CONFIG: &str = include_str!("../assets/hourglass.ron");
+
+    fn parse(text: &str) -> HourglassConfigFile {
+        parse_config_file(text.as_bytes()).unwrap()
+    }
+
+    fn config_app() -> (App, Handle<HourglassConfigFile>) {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), TimerPlugin))
+            .init_asset::<HourglassConfigFile>()
+            .add_event::<ShowToast>()
+            .add_event::<AppearanceStateChanged>()
+            .init_resource::<HourglassConfig>()
+            .init_resource::<TimerState>()
+            .init_resource::<ColorPalette>()
+            .init_resource::<ShapeLibrary>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<MorphSettings>()
+            .init_resource::<FinishSettings>()
+            .init_resource::<UrgencySettings>()
+            .init_resource::<GraduationSettings>()
+            .init_resource::<CascadeSettings>()
+            .init_resource::<SandLayers>()
+            .init_resource::<GlassSettings>()
+            .init_resource::<TimerPanelVisible>()
+            .init_resource::<AppearancePanelVisible>()
+            .init_resource::<AppliedConfigFile>()
+            .add_systems(Update, apply_config_file.in_set(TimerSystems::Input));
+        let handle = app
+            .world()
+            .resource::<Assets<HourglassConfigFile>>()
+            .reserve_handle();
+        app.insert_resource(ConfigFileHandle(handle.clone()));
+        (app, handle)
+    }
+
+    /// Store `file` as the loaded asset, as a (re)load from disk would.
+    fn load(app: &mut App, handle: &Handle<HourglassConfigFile>, file: HourglassConfigFile) {
+        app.world_mut()
+            .resource_mut::<Assets<HourglassConfigFile>>()
+            .insert(handle.id(), file);
+        // Asset events are flushed after the frame that inserts the asset.
+        app.update();
+        app.update();
+    }
+
+    fn toasts(app: &mut App) -> Vec<ShowToast> {
+        app.world_mut()
+            .resource_mut::<Events<ShowToast>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn shipped_config_is_valid_and_matches_builtin_defaults() {
+        let settings = parse(SHIPPED_CONFIG)
+            .validate(&ShapeLibrary::default())
+            .unwrap();
+        let defaults = HourglassConfig::default();
+        assert_eq!(settings.duration, Some(TimerState::default().duration));
+        assert_eq!(settings.color_mode, Some(defaults.color_mode));
+        let gradient = settings.gradient.unwrap();
+        assert_eq!(gradient.key, defaults.gradient.key);
+        assert_eq!(gradient.stops.len(), defaults.gradient.stops.len());
+        for (stop, builtin) in gradient.stops.iter().zip(&defaults.gradient.stops) {
+            let (a, b) = (stop.color.to_srgba(), builtin.color.to_srgba());
+            assert_eq!(stop.at, builtin.at);
+            assert!((a.red - b.red).abs() < 0.01 && (a.green - b.green).abs() < 0.01);
+        }
+        assert_eq!(settings.shape, Some(defaults.shape_type));
+        assert_eq!(settings.view, Some(defaults.view));
+        assert_eq!(settings.keybindings, KeyBindings::default());
+        assert_eq!(settings.finish, FinishSettings::default());
+        assert_eq!(settings.urgency, UrgencySettings::default());
+        assert_eq!(settings.graduations, GraduationSettings::default());
+        assert_eq!(settings.cascade, CascadeSettings::default());
+        assert_eq!(settings.layers, SandLayers::default());
+        assert_eq!(settings.glass, GlassSettings::default());
+        assert_eq!(
+            settings.morph,
+            MorphSettings {
+                sequence: HourglassShape::BUILTIN.into(),
+                ..default()
+            }
+        );
+        // The built-in swatches are exact constants that presets and saved
+        // colours compare against, so the shipped file leaves them alone.
+        assert_eq!(settings.palette, None);
+    }
+
+    #[test]
+    fn validation_collects_every_problem() {
+        let file = parse(
+            r##"(
+                duration: "soon",
+                color: "mauve",
+                gradient: [("100%", "green"), ("10s", "red")],
+                shape: "round",
+                view: "4d",
+                palette: [("teal", "#00808")],
+                keybindings: (reset: "Hyper+R"),
+                morph: (shapes: ["slim", "blob"], period: "0s", easing: "bouncy", sync: "moon"),
+                finish: (acknowledge: "later"),
+                urgency: (threshold: "shortly", intensity: 2.0),
+                graduations: (interval: "often"),
+                cascade: (threshold: "ages"),
+                layers: [("0s", "red"), ("5m", "sky")],
+                glass: (tint: "smoky", highlight: 1.5),
+            )"##,
+        );
+        let errors = file.validate(&ShapeLibrary::default()).unwrap_err();
+        assert_eq!(errors.len(), 20, "{errors:?}");
+        assert!(errors.iter().any(|e| e.starts_with("keybindings.reset")));
+    }
+
+    #[test]
+    fn gradient_stops_are_keyed_by_percentage_or_time_left() {
+        let validate = |text: &str| parse(text).validate(&ShapeLibrary::default());
+        let settings = validate(r##"(gradient: [("0s", "red"), ("5m", "#00ff00")])"##).unwrap();
+        let gradient = settings.gradient.unwrap();
+        assert_eq!(gradient.key, GradientKey::Seconds);
+        assert_eq!(
+            gradient.stops.iter().map(|s| s.at).collect::<Vec<_>>(),
+            vec![300.0, 0.0]
+        );
+
+        let settings = validate(r##"(gradient: [("75%", "red")])"##).unwrap();
+        assert_eq!(settings.gradient.unwrap().stops[0].at, 0.75);
+
+        let errors = validate(r##"(gradient: [("50%", "red"), ("1m", "red")])"##).unwrap_err();
+        assert_eq!(errors, vec!["gradient: mixes percentages and durations"]);
+    }
+
+    #[test]
+    fn sand_layers_are_sized_by_percentage_or_duration() {
+        let validate = |text: &str| parse(text).validate(&ShapeLibrary::default());
+        let settings = validate(r##"(layers: [("25m", "red"), ("5m", "#3399cc")])"##).unwrap();
+        let shares: Vec<f32> = settings.layers.0.iter().map(|layer| layer.share).collect();
+        assert_eq!(shares, [1500.0, 300.0]);
+        assert_eq!(settings.layers.0[0].color, parse_color("red").unwrap());
+
+        let settings = validate(r##"(layers: [("60%", "red"), ("40%", "blue")])"##).unwrap();
+        assert_eq!(settings.layers.0[1].share, 40.0);
+
+        let errors = validate(r##"(layers: [("50%", "red"), ("1m", "red")])"##).unwrap_err();
+        assert_eq!(errors, vec!["layers: mixes percentages and durations"]);
+    }
+
+    #[test]
+    fn graduations_follow_the_file() {
+        let validate = |text: &str| parse(text).validate(&ShapeLibrary::default());
+        let settings = validate(r#"(graduations: (enabled: true, interval: "5m"))"#).unwrap();
+        assert_eq!(
+            settings.graduations,
+            GraduationSettings {
+                enabled: true,
+                interval_secs: Some(300.0),
+                labels: true,
+            }
+        );
+        let settings = validate(r#"(graduations: (interval: "Auto", labels: false))"#).unwrap();
+        assert_eq!(settings.graduations.interval_secs, None);
+        assert!(!settings.graduations.labels);
+
+        let settings = validate(r#"(cascade: (threshold: "3h"))"#).unwrap();
+        assert_eq!(settings.cascade.threshold_secs, 10800.0);
+        let settings = validate(r#"(cascade: (threshold: "0s"))"#).unwrap();
+        assert_eq!(
+            settings.cascade.segment(&TimerState {
+                duration: 86400.0,
+                remaining: 86400.0,
+                is_running: true,
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn urgency_follows_the_file() {
+        let (mut app, handle) = config_app();
+        app.update();
+        load(
+            &mut app,
+            &handle,
+            parse(r#"(urgency: (threshold: "30s", intensity: 0.5, reduced_motion: true))"#),
+        );
+        assert_eq!(
+            *app.world().resource::<UrgencySettings>(),
+            UrgencySettings {
+                threshold_secs: 30.0,
+                intensity: 0.5,
+                reduced_motion: true,
+            }
+        );
+    }
+
+    #[test]
+    fn finish_effect_follows_the_file() {
+        let (mut app, handle) = config_app();
+        app.update();
+        load(
+            &mut app,
+            &handle,
+            parse(r#"(finish: (particles: false, flash: true, acknowledge: "Dismiss"))"#),
+        );
+        assert_eq!(
+            *app.world().resource::<FinishSettings>(),
+            FinishSettings {
+                glow: true,
+                particles: false,
+                flash: true,
+                acknowledge: FinishAcknowledge::Dismiss,
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected_by_the_parser() {
+        assert!(parse_config_file(br#"(colour: "red")"#).is_err());
+    }
+
+    #[test]
+    fn first_load_applies_defaults_and_custom_palette() {
+        let (mut app, handle) = config_app();
+        app.update();
+        load(
+            &mut app,
+            &handle,
+            parse(
+                r##"(
+                    duration: "25m",
+                    color: "teal",
+                    shape: "slim",
+                    palette: [("teal", "#008080"), ("sand", "#cc9933")],
+                    keybindings: (start_pause: "Ctrl+Enter"),
+                    ui: (timer_panel: true),
+                )"##,
+            ),
+        );
+
+        let world = app.world();
+        assert_eq!(world.resource::<TimerState>().duration, 1500.0);
+        let config = world.resource::<HourglassConfig>();
+        assert_eq!(config.shape_type, HourglassShape::SLIM);
+        assert_eq!(config.color, Color::from(Srgba::hex("#008080").unwrap()));
+        assert_eq!(world.resource::<ColorPalette>().0.len(), 2);
+        assert_eq!(
+            world.resource::<KeyBindings>().start_pause,
+            KeyChord::ctrl(KeyCode::Enter)
+        );
+        assert!(world.resource::<TimerPanelVisible>().0);
+    }
+
+    #[test]
+    fn restored_session_is_not_overridden_on_launch() {
+        let (mut app, handle) = config_app();
+        app.world_mut()
+            .resource_mut::<TimerState>()
+            .set_duration(60.0);
+        app.update();
+        load(
+            &mut app,
+            &handle,
+            parse(r##"(duration: "25m", palette: [("red", "#ff0000")])"##),
+        );
+        assert_eq!(app.world().resource::<TimerState>().duration, 60.0);
+        assert_eq!(app.world().resource::<ColorPalette>().0.len(), 1);
+    }
+
+    #[test]
+    fn command_line_flags_win_over_the_file_on_launch() {
+        let (mut app, handle) = config_app();
+        let overrides = LaunchOverrides {
+            shape: Some(HourglassShape::WIDE),
+            ..default()
+        };
+        overrides.apply_to_config(&mut app.world_mut().resource_mut::<HourglassConfig>());
+        app.insert_resource(overrides);
+        app.update();
+        load(
+            &mut app,
+            &handle,
+            parse(r##"(duration: "25m", color: "#008080", shape: "slim")"##),
+        );
+
+        let world = app.world();
+        assert_eq!(world.resource::<TimerState>().duration, 1500.0);
+        let config = world.resource::<HourglassConfig>();
+        assert_eq!(config.shape_type, HourglassShape::WIDE);
+        assert_eq!(config.color, Color::from(Srgba::hex("#008080").unwrap()));
+    }
+
+    #[test]
+    fn palette_edited_in_the_row_survives_unrelated_reloads() {
+        let (mut app, handle) = config_app();
+        app.update();
+        load(&mut app, &handle, parse(r#"(shape: "slim")"#));
+        assert_eq!(
+            *app.world().resource::<ColorPalette>(),
+            ColorPalette::default()
+        );
+
+        let teal = Color::srgb(0.0, 0.5, 0.5);
+        app.world_mut().resource_mut::<ColorPalette>().add(teal);
+        load(&mut app, &handle, parse(r#"(shape: "wide")"#));
+        assert!(app.world().resource::<ColorPalette>().contains(teal));
+
+        load(
+            &mut app,
+            &handle,
+            parse(r##"(shape: "wide", palette: [("red", "#ff0000")])"##),
+        );
+        assert_eq!(app.world().resource::<ColorPalette>().0.len(), 1);
+        load(&mut app, &handle, parse(r#"(shape: "wide")"#));
+        assert_eq!(
+            *app.world().resource::<ColorPalette>(),
+            ColorPalette::default()
+        );
+    }
+
+    #[cfg(all(feature = "persistence", not(target_arch = "wasm32")))]
+    #[test]
+    fn restored_palette_is_not_replaced_on_launch() {
+        use crate::persistence::{StateFilePath, colors_path, restore_colors};
+        use crate::resources::RecentColors;
+        use crate::resources::stored_colors::colors_to_json;
+
+        let dir = std::env::temp_dir().join(format!(
+            "hourglass-timer-test-{}-restored-palette",
+            std::process::id()
+        ));
+        let state_path = dir.join("state-v1.json");
+        let mut edited = ColorPalette::default();
+        edited.add(Color::srgb(0.0, 0.5, 0.5));
+        edited.move_swatch(edited.0.len() - 1, 0);
+        edited.remove(1);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            colors_path(&state_path),
+            colors_to_json(&edited, &RecentColors::default()).unwrap(),
+        )
+        .unwrap();
+
+        let (mut app, handle) = config_app();
+        app.init_resource::<RecentColors>()
+            .insert_resource(StateFilePath(Some(state_path)))
+            .add_systems(
+                Update,
+                restore_colors
+                    .run_if(run_once)
+                    .in_set(TimerSystems::Restore),
+            );
+        app.update();
+        assert_eq!(*app.world().resource::<ColorPalette>(), edited);
+        load(&mut app, &handle, parse(SHIPPED_CONFIG));
+        assert_eq!(*app.world().resource::<ColorPalette>(), edited);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reload_applies_only_edited_values() {
+        let (mut app, handle) = config_app();
+        app.update();
+        load(
+            &mut app,
+            &handle,
+            parse(r#"(duration: "10m", shape: "wide")"#),
+        );
+        app.world_mut().send_event(TimerCommand::Start);
+        app.world_mut().resource_mut::<HourglassConfig>().shape_type = HourglassShape::MODERN;
+        app.update();
+
+        load(
+            &mut app,
+            &handle,
+            parse(r#"(duration: "10m", shape: "wide", morphing: true, view: "3d")"#),
+        );
+        let world = app.world();
+        assert!(world.resource::<TimerState>().is_running);
+        let config = world.resource::<HourglassConfig>();
+        assert_eq!(config.shape_type, HourglassShape::MODERN);
+        assert_eq!(config.shape_mode, ShapeMode::Morphing);
+        assert_eq!(config.view, ViewMode::ThreeD);
+    }
+
+    #[test]
+    fn morph_settings_follow_the_file() {
+        let (mut app, handle) = config_app();
+        app.update();
+        load(
+            &mut app,
+            &handle,
+            parse(
+                r#"(morph: (shapes: ["wide", "Slim"], period: "30s", easing: "smooth", sync: "progress"))"#,
+            ),
+        );
+        assert_eq!(
+            *app.world().resource::<MorphSettings>(),
+            MorphSettings {
+                sequence: vec![HourglassShape::WIDE, HourglassShape::SLIM],
+                period_secs: 30.0,
+                easing: MorphEasing::Smooth,
+                sync: MorphSync::Progress,
+            }
+        );
+
+        load(&mut app, &handle, parse(r#"(morph: (period: "4s"))"#));
+        assert_eq!(
+            *app.world().resource::<MorphSettings>(),
+            MorphSettings {
+                period_secs: 4.0,
+                ..default()
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_reload_shows_a_toast_and_keeps_settings() {
+        let (mut app, handle) = config_app();
+        app.update();
+        load(&mut app, &handle, parse(r#"(shape: "slim")"#));
+        toasts(&mut app);
+
+        load(&mut app, &handle, parse(r#"(shape: "round")"#));
+        let shown = toasts(&mut app);
+        assert_eq!(shown.len(), 1);
+        assert!(shown[0].0.contains("round"));
+        assert_eq!(
+            app.world().resource::<HourglassConfig>().shape_type,
+            HourglassShape::SLIM
+        );
+    }
+}
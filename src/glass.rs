@@ -0,0 +1,242 @@
+//! Shaded glass. A `GlassMaterial` overlay in the outline of the main
+//! hourglass's glass is drawn over the sand, adding a rim highlight, a
+//! specular streak and a tinted darkening of the sand behind the walls. The
+//! builder's flat glass stays underneath, so where the shader is unsupported,
+//! or with `GlassSettings::shaded` off, the glass is the flat tint it was.
+
+use crate::hourglass::{HourglassPlacement, MainHourglass, ShapeTransition};
+use crate::morph_mesh::{ShapeProfile, glass_mesh};
+use crate::resources::{GlassSettings, HourglassConfig, HourglassShape, ShapeMode, ShapePreview};
+use crate::shapes::ShapeLibrary;
+use crate::timer::TimerSystems;
+use bevy::asset::embedded_asset;
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+use bevy::render::view::NoFrustumCulling;
+use bevy::sprite::{AlphaMode2d, Material2d, Material2dPlugin};
+use bevy_hourglass::{HourglassMeshPlatesConfig, HourglassMeshSandState};
+
+const GLASS_SHADER: &str = "embedded://hourglass_timer/glass.wgsl";
+/// Plain web builds render through WebGL2, where the flat glass is kept.
+const SHADER_SUPPORTED: bool = cfg!(not(all(
+    target_arch = "wasm32",
+    not(feature = "chrome_extension")
+)));
+/// In front of the sand and its layers, behind the graduation marks.
+const OVERLAY_Z: f32 = 0.18;
+
+pub struct GlassPlugin;
+
+impl Plugin for GlassPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlassSettings>()
+            .register_type::<GlassSettings>();
+        if !SHADER_SUPPORTED {
+            return;
+        }
+        embedded_asset!(app, "glass.wgsl");
+        app.add_plugins(Material2dPlugin::<GlassMaterial>::default())
+            .add_systems(Update, update_glass_overlay.after(TimerSystems::Observe));
+    }
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone, PartialEq)]
+pub(crate) struct GlassMaterial {
+    #[uniform(0)]
+    tint: LinearRgba,
+    /// Highlight and refraction strengths in `x` and `y`.
+    #[uniform(1)]
+    strengths: Vec4,
+}
+
+impl From<&GlassSettings> for GlassMaterial {
+    fn from(settings: &GlassSettings) -> Self {
+        Self {
+            tint: settings.tint.to_linear(),
+            strengths: Vec4::new(
+                settings.highlight.clamp(0.0, 1.0),
+                settings.refraction.clamp(0.0, 1.0),
+                0.0,
+                0.0,
+            ),
+        }
+    }
+}
+
+impl Material2d for GlassMaterial {
+    fn fragment_shader() -> ShaderRef {
+        GLASS_SHADER.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+#[derive(Component)]
+struct GlassOverlay;
+
+/// Keep the overlay on the main hourglass while the glass is shaded, in the
+/// outline of its glass: sampled again when the shape changes and every frame
+/// while morphing or changing shape.
+fn update_glass_overlay(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut glass_materials: ResMut<Assets<GlassMaterial>>,
+    settings: Res<GlassSettings>,
+    config: Res<HourglassConfig>,
+    preview: Res<ShapePreview>,
+    library: Res<ShapeLibrary>,
+    placement: Res<HourglassPlacement>,
+    hourglass: Query<(Entity, &HourglassMeshSandState, Has<ShapeTransition>), With<MainHourglass>>,
+    overlays: Query<(Entity, &Mesh2d, &MeshMaterial2d<GlassMaterial>), With<GlassOverlay>>,
+    mut shaped_for: Local<Option<HourglassShape>>,
+) {
+    if !settings.shaded {
+        for (overlay, ..) in &overlays {
+            commands.entity(overlay).despawn();
+        }
+        *shaped_for = None;
+        return;
+    }
+    let Ok((entity, sand_state, transitioning)) = hourglass.single() else {
+        return;
+    };
+    let (shape, shape_mode) = preview.shape(&config);
+    let profile = || {
+        ShapeProfile::new(
+            &sand_state.body_config,
+            &HourglassMeshPlatesConfig::default(),
+        )
+    };
+
+    let Ok((_, mesh, material)) = overlays.single() else {
+        for (overlay, ..) in &overlays {
+            commands.entity(overlay).despawn();
+        }
+        let mut mesh = glass_mesh();
+        profile().write_glass(&mut mesh);
+        commands.spawn((
+            GlassOverlay,
+            Mesh2d(meshes.add(mesh)),
+            MeshMaterial2d(glass_materials.add(GlassMaterial::from(&*settings))),
+            Transform::from_xyz(0.0, 0.0, OVERLAY_Z),
+            // Vertices move with the shape, so bounds would go stale.
+            NoFrustumCulling,
+            placement.render_layers.clone(),
+            ChildOf(entity),
+        ));
+        *shaped_for = Some(shape.clone());
+        return;
+    };
+
+    if settings.is_changed()
+        && let Some(material) = glass_materials.get_mut(&material.0)
+    {
+        *material = GlassMaterial::from(&*settings);
+    }
+    let reshape = shape_mode == ShapeMode::Morphing
+        || transitioning
+        || library.is_changed()
+        || shaped_for.as_ref() != Some(shape);
+    if reshape && let Some(mesh) = meshes.get_mut(&mesh.0) {
+        profile().write_glass(mesh);
+        *shaped_for = Some(shape.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HourglassTimerPlugin;
+    use bevy::asset::AssetPlugin;
+    use bevy::input::InputPlugin;
+    use bevy::render::mesh::{MeshPlugin, VertexAttributeValues};
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn glass_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            MeshPlugin,
+            TransformPlugin,
+        ))
+        .init_asset::<ColorMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .add_plugins(HourglassTimerPlugin {
+            ui: false,
+            ..default()
+        });
+        app.update();
+        app.update();
+        app
+    }
+
+    fn overlay(app: &mut App) -> Option<(Mesh, GlassMaterial)> {
+        let world = app.world_mut();
+        let (mesh, material) = world
+            .query_filtered::<(&Mesh2d, &MeshMaterial2d<GlassMaterial>), With<GlassOverlay>>()
+            .iter(world)
+            .map(|(mesh, material)| (mesh.0.clone(), material.0.clone()))
+            .next()?;
+        Some((
+            world.resource::<Assets<Mesh>>().get(&mesh)?.clone(),
+            world
+                .resource::<Assets<GlassMaterial>>()
+                .get(&material)?
+                .clone(),
+        ))
+    }
+
+    fn widest(mesh: &Mesh) -> f32 {
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => {
+                positions.iter().map(|p| p[0]).fold(f32::MIN, f32::max)
+            }
+            _ => panic!("overlay has no positions"),
+        }
+    }
+
+    #[test]
+    fn overlay_runs_wall_to_wall_over_the_glass() {
+        let mut app = glass_app();
+        let (mesh, material) = overlay(&mut app).unwrap();
+        assert_eq!(material, GlassMaterial::from(&GlassSettings::default()));
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("overlay has no uvs");
+        };
+        assert_eq!(uvs.first(), Some(&[0.0, 0.0]));
+        assert_eq!(uvs.last(), Some(&[1.0, 1.0]));
+
+        let classic = widest(&mesh);
+        assert!(classic > 0.0);
+
+        app.world_mut().resource_mut::<HourglassConfig>().shape_type = HourglassShape::SLIM;
+        for _ in 0..12 {
+            app.update();
+        }
+        assert!(widest(&overlay(&mut app).unwrap().0) < classic);
+    }
+
+    #[test]
+    fn settings_restyle_or_remove_the_overlay() {
+        let mut app = glass_app();
+        app.world_mut().resource_mut::<GlassSettings>().highlight = 0.25;
+        app.update();
+        assert_eq!(overlay(&mut app).unwrap().1.strengths.x, 0.25);
+
+        app.world_mut().resource_mut::<GlassSettings>().shaded = false;
+        app.update();
+        assert!(overlay(&mut app).is_none());
+
+        app.world_mut().resource_mut::<GlassSettings>().shaded = true;
+        app.update();
+        assert!(overlay(&mut app).is_some());
+    }
+}
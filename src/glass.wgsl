@@ -0,0 +1,33 @@
+// Shading drawn over the sand of the main hourglass: a bright rim inside
+// both walls, a specular streak down each bulb and a tinted darkening of the
+// sand seen through the curved sides. `uv.x` runs across the glass from the
+// left wall to the right one and `uv.y` from the top to the bottom.
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(2) @binding(0) var<uniform> tint: vec4<f32>;
+// x: highlight strength, y: refraction strength.
+@group(2) @binding(1) var<uniform> strengths: vec4<f32>;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let across = mesh.uv.x * 2.0 - 1.0;
+    let edge = abs(across);
+    // 0 at the neck, 1 at the top and bottom of the glass.
+    let bulb = abs(mesh.uv.y * 2.0 - 1.0);
+
+    // The walls are seen most obliquely near the edges, where they bend the
+    // most light away from the sand behind them.
+    let shade = strengths.y * 0.6 * smoothstep(0.35, 1.0, edge);
+    let rim = strengths.x * smoothstep(0.84, 0.95, edge) * (1.0 - smoothstep(0.96, 1.0, edge));
+    let offset = (across + 0.55) / 0.08;
+    let streak = strengths.x * 0.5 * exp(-offset * offset) * smoothstep(0.15, 0.6, bulb);
+    let light = clamp(rim + streak, 0.0, 1.0);
+
+    let alpha = clamp(shade + light, 0.0, 1.0);
+    if alpha <= 0.0 {
+        return vec4<f32>(0.0);
+    }
+    let color = (tint.rgb * shade + vec3<f32>(light)) / (shade + light);
+    return vec4<f32>(color, alpha);
+}
@@ -0,0 +1,430 @@
+//! Graduation marks. Ticks along both bulbs show where the sand stands when a
+//! given amount of time is left, laid out from the glass outline of the shape
+//! the main hourglass currently has.
+
+use crate::hourglass::{HourglassPlacement, MainHourglass, ShapeTransition};
+use crate::morph_mesh::ShapeProfile;
+use crate::resources::{
+    CascadeSettings, GraduationSettings, HourglassConfig, HourglassShape, ShapeMode, ShapePreview,
+    TimerState,
+};
+use crate::shapes::ShapeLibrary;
+use crate::timer::TimerSystems;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy_hourglass::{HourglassMeshBodyConfig, HourglassMeshPlatesConfig, HourglassMeshSandState};
+
+/// Round steps the automatic interval picks from, in seconds.
+const AUTO_STEPS: [f32; 13] = [
+    5.0, 10.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1800.0, 3600.0, 7200.0, 14400.0,
+];
+/// The automatic interval gives at most this many steps over the duration.
+const AUTO_STEP_COUNT: f32 = 6.0;
+/// A configured interval that would crowd the glass with more marks than
+/// this falls back to the automatic one.
+const MAX_MARKS: usize = 48;
+const TICK_LENGTH: f32 = 10.0;
+const TICK_THICKNESS: f32 = 1.5;
+const LABEL_GAP: f32 = 6.0;
+const LABEL_FONT_SIZE: f32 = 12.0;
+const MARK_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.6);
+/// In front of the sand, which sits at 0.1.
+const MARK_Z: f32 = 0.2;
+
+pub struct GraduationsPlugin;
+
+impl Plugin for GraduationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GraduationSettings>()
+            .register_type::<GraduationSettings>()
+            // The shape systems settle the glass outline first.
+            .add_systems(Update, layout_graduations.after(TimerSystems::Observe));
+    }
+}
+
+/// One side of the glass at one mark.
+#[derive(Component)]
+struct GraduationTick {
+    mark: usize,
+    side: f32,
+}
+
+#[derive(Component)]
+struct GraduationLabel {
+    mark: usize,
+}
+
+/// A mark on the glass, in the main hourglass's local space.
+#[derive(Debug, Clone, PartialEq)]
+struct Mark {
+    y: f32,
+    half_width: f32,
+    text: String,
+}
+
+/// What the marks were last laid out for. Anything else changing, such as
+/// the sand level or a rainbow colour, leaves them where they are.
+#[derive(PartialEq)]
+struct LayoutKey {
+    settings: GraduationSettings,
+    duration: f32,
+    shape: HourglassShape,
+    shape_mode: ShapeMode,
+}
+
+#[derive(Default)]
+struct SpawnedMarks {
+    key: Option<LayoutKey>,
+    /// The text of every spawned mark, and whether it was printed.
+    texts: Vec<String>,
+    labelled: bool,
+    assets: Option<(Handle<Mesh>, Handle<ColorMaterial>)>,
+}
+
+/// Time between marks for a `duration` countdown: the configured interval,
+/// or the smallest round step that keeps the marks few enough to read.
+fn mark_interval(duration: f32, settings: &GraduationSettings) -> f32 {
+    if let Some(interval) = settings.interval_secs
+        && interval > 0.0
+        && duration / interval <= MAX_MARKS as f32
+    {
+        return interval;
+    }
+    AUTO_STEPS
+        .into_iter()
+        .find(|step| duration / step <= AUTO_STEP_COUNT)
+        .unwrap_or(AUTO_STEPS[AUTO_STEPS.len() - 1])
+}
+
+/// Remaining times to mark, every `interval` strictly between the start and
+/// the deadline.
+fn mark_times(duration: f32, interval: f32) -> Vec<f32> {
+    (1..)
+        .map(|step| step as f32 * interval)
+        .take_while(|remaining| *remaining < duration - 1e-3)
+        .collect()
+}
+
+/// `seconds` the way durations are typed, e.g. `5m`, `1h30m` or `45s`.
+fn mark_text(seconds: f32) -> String {
+    let total = seconds.round() as u32;
+    let (hours, minutes, seconds) = (total / 3600, total % 3600 / 60, total % 60);
+    let mut text = String::new();
+    for (value, unit) in [(hours, "h"), (minutes, "m"), (seconds, "s")] {
+        if value > 0 {
+            text.push_str(&format!("{value}{unit}"));
+        }
+    }
+    text
+}
+
+/// Marks for both bulbs of a hourglass shaped by `body`, each at the height
+/// the sand surface has when its time is left.
+fn graduation_marks(
+    duration: f32,
+    settings: &GraduationSettings,
+    body: &HourglassMeshBodyConfig,
+) -> Vec<Mark> {
+    if !settings.enabled || duration <= 0.0 {
+        return Vec::new();
+    }
+    let profile = ShapeProfile::new(body, &HourglassMeshPlatesConfig::default());
+    let times = mark_times(duration, mark_interval(duration, settings));
+    let top = times.iter().map(|remaining| {
+        let fill = remaining / duration;
+        (profile.top_fill_line(fill), *remaining)
+    });
+    let bottom = times.iter().map(|remaining| {
+        let fill = remaining / duration;
+        (profile.bottom_fill_line(fill), *remaining)
+    });
+    top.chain(bottom)
+        .map(|(y, remaining)| Mark {
+            y,
+            half_width: profile.glass_at(y),
+            text: mark_text(remaining),
+        })
+        .collect()
+}
+
+fn tick_transform(mark: &Mark, side: f32) -> Transform {
+    Transform::from_xyz(side * (mark.half_width - TICK_LENGTH / 2.0), mark.y, MARK_Z)
+        .with_scale(Vec3::new(TICK_LENGTH, TICK_THICKNESS, 1.0))
+}
+
+fn label_transform(mark: &Mark) -> Transform {
+    Transform::from_xyz(mark.half_width + LABEL_GAP, mark.y, MARK_Z)
+}
+
+/// Lay the marks out again when the duration, the shape or the settings
+/// change, and every frame while morphing or changing shape. Marks that only
+/// move are moved in place; a different set of marks is spawned afresh.
+fn layout_graduations(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    settings: Res<GraduationSettings>,
+    timer_state: Res<TimerState>,
+    cascade: Option<Res<CascadeSettings>>,
+    config: Res<HourglassConfig>,
+    preview: Res<ShapePreview>,
+    library: Res<ShapeLibrary>,
+    placement: Res<HourglassPlacement>,
+    hourglass: Query<(Entity, &HourglassMeshSandState, Has<ShapeTransition>), With<MainHourglass>>,
+    mut ticks: Query<(Entity, &GraduationTick, &mut Transform), Without<GraduationLabel>>,
+    mut labels: Query<(Entity, &GraduationLabel, &mut Transform), Without<GraduationTick>>,
+    mut spawned: Local<SpawnedMarks>,
+) {
+    let Ok((entity, sand_state, transitioning)) = hourglass.single() else {
+        return;
+    };
+    // A cascading glass only ever holds the current segment.
+    let duration = cascade
+        .and_then(|cascade| cascade.segment(&timer_state))
+        .map_or(timer_state.duration, |segment| segment.duration);
+    let (shape, shape_mode) = preview.shape(&config);
+    let key = LayoutKey {
+        settings: settings.clone(),
+        duration,
+        shape: shape.clone(),
+        shape_mode,
+    };
+    let morphing = shape_mode == ShapeMode::Morphing || transitioning;
+    if spawned.key.as_ref() == Some(&key) && !library.is_changed() && !morphing {
+        return;
+    }
+
+    let marks = graduation_marks(duration, &settings, &sand_state.body_config);
+    let texts: Vec<String> = marks.iter().map(|mark| mark.text.clone()).collect();
+    spawned.key = Some(key);
+
+    if texts == spawned.texts && settings.labels == spawned.labelled {
+        for (_, tick, mut transform) in &mut ticks {
+            if let Some(mark) = marks.get(tick.mark) {
+                transform.set_if_neq(tick_transform(mark, tick.side));
+            }
+        }
+        for (_, label, mut transform) in &mut labels {
+            if let Some(mark) = marks.get(label.mark) {
+                transform.set_if_neq(label_transform(mark));
+            }
+        }
+        return;
+    }
+
+    for (stale, ..) in &ticks {
+        commands.entity(stale).despawn();
+    }
+    for (stale, ..) in &labels {
+        commands.entity(stale).despawn();
+    }
+    let (mesh, material) = spawned
+        .assets
+        .get_or_insert_with(|| {
+            (
+                meshes.add(Rectangle::new(1.0, 1.0)),
+                materials.add(MARK_COLOR),
+            )
+        })
+        .clone();
+    for (index, mark) in marks.iter().enumerate() {
+        for side in [-1.0, 1.0] {
+            commands.spawn((
+                GraduationTick { mark: index, side },
+                Mesh2d(mesh.clone()),
+                MeshMaterial2d(material.clone()),
+                tick_transform(mark, side),
+                placement.render_layers.clone(),
+                ChildOf(entity),
+            ));
+        }
+        if settings.labels {
+            commands.spawn((
+                GraduationLabel { mark: index },
+                Text2d::new(mark.text.clone()),
+                TextFont {
+                    font_size: LABEL_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(MARK_COLOR),
+                Anchor::CenterLeft,
+                label_transform(mark),
+                placement.render_layers.clone(),
+                ChildOf(entity),
+            ));
+        }
+    }
+    spawned.texts = texts;
+    spawned.labelled = settings.labels;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HourglassTimerPlugin;
+    use crate::morph_mesh::SAND_WALL_OFFSET;
+    use approx::assert_abs_diff_eq;
+    use bevy::asset::AssetPlugin;
+    use bevy::input::InputPlugin;
+    use bevy::render::mesh::MeshPlugin;
+
+    fn enabled() -> GraduationSettings {
+        GraduationSettings {
+            enabled: true,
+            ..default()
+        }
+    }
+
+    fn classic() -> HourglassMeshBodyConfig {
+        ShapeLibrary::default()
+            .get(&HourglassShape::CLASSIC)
+            .main_configs()
+            .0
+    }
+
+    #[test]
+    fn automatic_interval_gives_a_handful_of_round_marks() {
+        let settings = enabled();
+        assert_eq!(mark_interval(1800.0, &settings), 300.0);
+        assert_eq!(mark_interval(1500.0, &settings), 300.0);
+        assert_eq!(mark_interval(3600.0, &settings), 600.0);
+        assert_eq!(mark_interval(60.0, &settings), 10.0);
+        assert_eq!(
+            mark_times(1800.0, 300.0),
+            [300.0, 600.0, 900.0, 1200.0, 1500.0]
+        );
+
+        let every_minute = GraduationSettings {
+            interval_secs: Some(60.0),
+            ..enabled()
+        };
+        assert_eq!(mark_interval(1800.0, &every_minute), 60.0);
+        // Every minute of a day would bury the glass.
+        assert_eq!(mark_interval(86_400.0, &every_minute), 14_400.0);
+    }
+
+    #[test]
+    fn marks_read_like_typed_durations() {
+        assert_eq!(mark_text(300.0), "5m");
+        assert_eq!(mark_text(5400.0), "1h30m");
+        assert_eq!(mark_text(45.0), "45s");
+        assert_eq!(mark_text(90.0), "1m30s");
+    }
+
+    #[test]
+    fn sand_reaches_each_mark_when_its_time_is_left() {
+        let body = classic();
+        let marks = graduation_marks(1800.0, &enabled(), &body);
+        assert_eq!(marks.len(), 10);
+
+        let half_height = body.total_height / 2.0;
+        let neck_bottom = -body.neck_style.height() / 2.0;
+        // 15 minutes left of 30 is half of each bulb's sand.
+        let top = &marks[2];
+        assert_eq!(top.text, "15m");
+        assert_abs_diff_eq!(top.y, half_height / 2.0, epsilon = 1e-3);
+        let bottom = &marks[7];
+        assert_eq!(bottom.text, "15m");
+        assert_abs_diff_eq!(bottom.y, (-half_height + neck_bottom) / 2.0, epsilon = 1e-3);
+        // The ticks sit on the glass, outside the sand.
+        let profile = ShapeProfile::new(&body, &HourglassMeshPlatesConfig::default());
+        for mark in &marks {
+            assert_abs_diff_eq!(mark.half_width, profile.glass_at(mark.y));
+            assert!(mark.half_width > SAND_WALL_OFFSET);
+        }
+    }
+
+    #[test]
+    fn no_marks_unless_enabled() {
+        assert!(graduation_marks(1800.0, &GraduationSettings::default(), &classic()).is_empty());
+        assert!(graduation_marks(0.0, &enabled(), &classic()).is_empty());
+    }
+
+    fn graduation_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            MeshPlugin,
+            TransformPlugin,
+        ))
+        .init_asset::<ColorMaterial>()
+        .insert_resource(enabled())
+        .insert_resource(TimerState {
+            duration: 1800.0,
+            remaining: 1800.0,
+            is_running: false,
+        })
+        .add_plugins(HourglassTimerPlugin {
+            ui: false,
+            ..default()
+        });
+        app.update();
+        app.update();
+        app
+    }
+
+    fn tick_positions(app: &mut App) -> Vec<Vec2> {
+        let world = app.world_mut();
+        let mut positions: Vec<Vec2> = world
+            .query_filtered::<&Transform, With<GraduationTick>>()
+            .iter(world)
+            .map(|transform| transform.translation.truncate())
+            .collect();
+        positions.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        positions
+    }
+
+    fn label_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world
+            .query_filtered::<(), With<GraduationLabel>>()
+            .iter(world)
+            .count()
+    }
+
+    #[test]
+    fn marks_follow_the_duration_and_settings() {
+        let mut app = graduation_app();
+        assert_eq!(tick_positions(&mut app).len(), 20);
+        assert_eq!(label_count(&mut app), 10);
+
+        // Ten minutes is marked every two.
+        app.world_mut()
+            .resource_mut::<TimerState>()
+            .set_duration(600.0);
+        app.update();
+        assert_eq!(tick_positions(&mut app).len(), 16);
+        assert_eq!(label_count(&mut app), 8);
+
+        app.world_mut().resource_mut::<GraduationSettings>().labels = false;
+        app.update();
+        assert_eq!(tick_positions(&mut app).len(), 16);
+        assert_eq!(label_count(&mut app), 0);
+
+        app.world_mut().resource_mut::<GraduationSettings>().enabled = false;
+        app.update();
+        assert!(tick_positions(&mut app).is_empty());
+    }
+
+    #[test]
+    fn marks_move_with_the_shape_and_survive_its_rebuild() {
+        let mut app = graduation_app();
+        let classic = tick_positions(&mut app);
+
+        app.world_mut().resource_mut::<HourglassConfig>().shape_type = HourglassShape::SLIM;
+        app.update();
+        app.update();
+        let slim = tick_positions(&mut app);
+        // Rebuilding the glass left the marks in place, moved to the new
+        // outline.
+        assert_eq!(slim.len(), classic.len());
+        assert_ne!(slim, classic);
+
+        app.world_mut().resource_mut::<HourglassConfig>().shape_mode = ShapeMode::Morphing;
+        app.update();
+        app.update();
+        assert_eq!(tick_positions(&mut app).len(), classic.len());
+    }
+}
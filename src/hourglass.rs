@@ -1,15 +1,22 @@
+use crate::cascade::CASCADE_ROW_HEIGHT;
+use crate::morph_mesh::{MorphKeyframes, MorphMeshes, MorphPart, SAND_WALL_OFFSET, ShapeProfile};
 use crate::resources::{
-    ColorMode, HourglassConfig, HourglassShape, PendingFlip, ShapeMode, TimerState,
+    CascadeSettings, HourglassConfig, HourglassShape, MorphEasing, MorphSettings, MorphSync,
+    PendingFlip, SandColorPreview, SandLayers, ShapeMode, ShapePreview, TimerState,
 };
+use crate::shapes::{ShapeDefinition, ShapeLibrary};
 use crate::timer::{TimerCommand, TimerSystems};
 use crate::ui::shape_panel::MiniHourglass;
-#[cfg(feature = "chrome_extension")]
 use crate::ui::{AppearancePanelVisible, TimerPanelVisible};
+use crate::urgency::Urgency;
 use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
 use bevy_hourglass::{
-    BulbStyle, Hourglass, HourglassMeshBodyConfig, HourglassMeshBuilder, HourglassMeshPlatesConfig,
-    HourglassMeshSandConfig, HourglassPlugin as BevyHourglassPlugin, NeckStyle, SandSplash,
-    SandSplashConfig,
+    BulbStyle, Hourglass, HourglassMesh, HourglassMeshBody, HourglassMeshBodyConfig,
+    HourglassMeshBuilder, HourglassMeshPlate, HourglassMeshPlatesConfig, HourglassMeshSand,
+    HourglassMeshSandConfig, HourglassMeshSandState, HourglassPlugin as BevyHourglassPlugin,
+    NeckStyle, SandSplash, SandSplashConfig, SandSplashParticle,
 };
 
 pub struct HourglassPlugin;
@@ -18,6 +25,12 @@ impl Plugin for HourglassPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(BevyHourglassPlugin)
             .init_resource::<PendingFlip>()
+            .init_resource::<HourglassPlacement>()
+            .init_resource::<ShapeLibrary>()
+            .init_resource::<MorphSettings>()
+            .init_resource::<ShapePreview>()
+            .register_type::<PendingFlip>()
+            .register_type::<MorphSettings>()
             .add_systems(Startup, spawn_hourglass)
             .add_systems(
                 Update,
@@ -33,19 +46,32 @@ impl Plugin for HourglassPlugin {
                 )
                     .in_set(TimerSystems::Observe),
             )
-            .add_systems(Update, handle_hourglass_click.in_set(TimerSystems::Input));
-
-        #[cfg(feature = "chrome_extension")]
-        app.add_systems(
-            Update,
-            update_sidebar_hourglass_scale.in_set(TimerSystems::Observe),
-        );
+            .add_systems(Update, handle_hourglass_click.in_set(TimerSystems::Input))
+            .add_systems(
+                Update,
+                update_main_hourglass_scale.in_set(TimerSystems::Observe),
+            )
+            .add_systems(PostUpdate, apply_render_layers);
     }
 }
 
 #[derive(Component)]
 pub struct MainHourglass;
 
+/// Marks the camera that renders the hourglass. Clicks, drags and the mini
+/// shape buttons are hit-tested through it, so an app with several cameras
+/// must tag exactly one.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct HourglassCamera;
+
+/// Where the main hourglass lives in the host's scene, set from
+/// `HourglassTimerPlugin`.
+#[derive(Resource, Debug, Default, Clone)]
+pub(crate) struct HourglassPlacement {
+    pub root: Option<Entity>,
+    pub render_layers: RenderLayers,
+}
+
 #[derive(Component, Default, Clone)]
 struct DragState {
     is_active: bool,
@@ -126,238 +152,83 @@ pub(crate) fn exceeds_drag_threshold(start: Vec2, current: Vec2, threshold: f32)
     current.distance(start) > threshold
 }
 
-fn main_hourglass_hit_radius(scale: f32) -> f32 {
-    if cfg!(feature = "chrome_extension") {
-        220.0 * scale.max(0.0)
+/// The main hourglass's hit radius at render scale `scale`.
+pub(crate) fn main_hourglass_hit_radius(scale: f32) -> f32 {
+    let radius = if cfg!(feature = "chrome_extension") {
+        220.0
     } else {
         400.0
-    }
+    };
+    radius * scale.max(0.0)
 }
 
+/// Heights kept free above and below the hourglass for the panels.
 #[cfg(feature = "chrome_extension")]
-fn sidebar_hourglass_scale(
+fn reserved_panel_heights(appearance_open: bool, timer_adjustments_open: bool) -> (f32, f32) {
+    let top_reserved = if appearance_open { 92.0 } else { 42.0 };
+    let bottom_reserved = if timer_adjustments_open { 190.0 } else { 42.0 };
+    (top_reserved, bottom_reserved)
+}
+
+/// Heights kept free above and below the hourglass for the panels. The colour
+/// and shape rows stay laid out while the appearance controls are hidden.
+#[cfg(not(feature = "chrome_extension"))]
+fn reserved_panel_heights(_appearance_open: bool, timer_panel_open: bool) -> (f32, f32) {
+    let bottom_reserved = if timer_panel_open { 210.0 } else { 40.0 };
+    (84.0, bottom_reserved)
+}
+
+/// Scale that fits the 400x480 hourglass into the window between the open
+/// panels, never growing past its natural size.
+fn main_hourglass_scale(
     window_width: f32,
     window_height: f32,
     appearance_open: bool,
-    timer_adjustments_open: bool,
+    timer_panel_open: bool,
 ) -> f32 {
-    let top_reserved = if appearance_open { 92.0 } else { 42.0 };
-    let bottom_reserved = if timer_adjustments_open { 190.0 } else { 42.0 };
+    let (top_reserved, bottom_reserved) = reserved_panel_heights(appearance_open, timer_panel_open);
     let horizontal = (window_width - 24.0) / 400.0;
     let vertical = (window_height - top_reserved - bottom_reserved - 24.0) / 480.0;
     horizontal.min(vertical).clamp(0.35, 1.0)
 }
 
-#[cfg(feature = "chrome_extension")]
-fn update_sidebar_hourglass_scale(
-    windows: Query<&Window>,
-    appearance_visible: Res<AppearancePanelVisible>,
-    timer_panel_visible: Res<TimerPanelVisible>,
+/// Fit the main hourglass to the window and apply the urgency pulse on top.
+fn update_main_hourglass_scale(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    placement: Res<HourglassPlacement>,
+    appearance_visible: Option<Res<AppearancePanelVisible>>,
+    timer_panel_visible: Option<Res<TimerPanelVisible>>,
+    urgency: Option<Res<Urgency>>,
+    timer_state: Res<TimerState>,
+    cascade: Option<Res<CascadeSettings>>,
     mut query: Query<&mut Transform, With<MainHourglass>>,
 ) {
-    let Ok(window) = windows.single() else {
-        return;
+    // A host that parents the hourglass under its own root sizes the root.
+    let fitted = if placement.root.is_some() {
+        1.0
+    } else {
+        let Ok(window) = windows.single() else {
+            return;
+        };
+        // The glass stays centred, so a cascade row below it is kept free
+        // above it as well.
+        let cascading = cascade
+            .and_then(|cascade| cascade.segment(&timer_state))
+            .is_some_and(|segment| segment.hours > 0);
+        let row_height = if cascading { CASCADE_ROW_HEIGHT } else { 0.0 };
+        // Without the UI plugin there are no panels to make room for.
+        main_hourglass_scale(
+            window.width(),
+            window.height() - 2.0 * row_height,
+            appearance_visible.is_some_and(|visible| visible.0),
+            timer_panel_visible.is_some_and(|visible| visible.0),
+        )
     };
-    let scale = sidebar_hourglass_scale(
-        window.width(),
-        window.height(),
-        appearance_visible.0,
-        timer_panel_visible.0,
-    );
+    let scale = fitted * urgency.map_or(1.0, |urgency| urgency.pulse);
     for mut transform in &mut query {
-        transform.scale = Vec3::splat(scale);
-    }
-}
-
-// Helper function to create main hourglass configurations for different shapes
-fn get_main_shape_config(
-    shape: HourglassShape,
-) -> (HourglassMeshBodyConfig, HourglassMeshPlatesConfig) {
-    let base_height = 400.0; // Full size for main hourglass
-
-    match shape {
-        HourglassShape::Classic => (
-            HourglassMeshBodyConfig {
-                total_height: base_height,
-                bulb_style: BulbStyle::Circular {
-                    curvature: 1.0,
-                    width_factor: 1.0,
-                    curve_resolution: 20,
-                },
-                neck_style: NeckStyle::Curved {
-                    curvature: 1.0,
-                    width: 14.0,
-                    height: 20.0,
-                    curve_resolution: 10,
-                },
-                color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-            },
-            HourglassMeshPlatesConfig {
-                width: 400.0,
-                height: 10.0,
-                ..Default::default()
-            },
-        ),
-        HourglassShape::Modern => (
-            HourglassMeshBodyConfig {
-                total_height: base_height,
-                bulb_style: BulbStyle::Circular {
-                    curvature: 0.0,
-                    width_factor: 1.0,
-                    curve_resolution: 10,
-                },
-                neck_style: NeckStyle::Straight {
-                    width: 12.0,
-                    height: 32.0,
-                },
-                color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-            },
-            HourglassMeshPlatesConfig {
-                width: 380.0,
-                height: 12.0,
-                ..Default::default()
-            },
-        ),
-        HourglassShape::Slim => (
-            HourglassMeshBodyConfig {
-                total_height: base_height * 1.2, // Taller
-                bulb_style: BulbStyle::Circular {
-                    curvature: 1.5,
-                    width_factor: 0.7, // Narrower
-                    curve_resolution: 18,
-                },
-                neck_style: NeckStyle::Curved {
-                    curvature: 1.5,
-                    width: 12.0, // Thinner neck
-                    height: 24.0,
-                    curve_resolution: 8,
-                },
-                color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-            },
-            HourglassMeshPlatesConfig {
-                width: 340.0, // Narrower plates
-                height: 8.0,
-                ..Default::default()
-            },
-        ),
-        HourglassShape::Wide => (
-            HourglassMeshBodyConfig {
-                total_height: base_height * 0.8, // Shorter
-                bulb_style: BulbStyle::Circular {
-                    curvature: 1.0,
-                    width_factor: 1.2, // Wider
-                    curve_resolution: 24,
-                },
-                neck_style: NeckStyle::Curved {
-                    curvature: 0.7,
-                    width: 20.0, // Thicker neck
-                    height: 16.0,
-                    curve_resolution: 12,
-                },
-                color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-            },
-            HourglassMeshPlatesConfig {
-                width: 390.0, // Wider plates
-                height: 14.0,
-                ..Default::default()
-            },
-        ),
-    }
-}
-
-// Helper function to create mini hourglass configurations for different shapes (for UI panels)
-pub fn get_mini_shape_config(
-    shape: HourglassShape,
-) -> (HourglassMeshBodyConfig, HourglassMeshPlatesConfig) {
-    let base_height = 25.0; // Smaller size for mini hourglasses
-
-    match shape {
-        HourglassShape::Classic => (
-            HourglassMeshBodyConfig {
-                total_height: base_height,
-                bulb_style: BulbStyle::Circular {
-                    curvature: 1.0,
-                    width_factor: 1.0,
-                    curve_resolution: 10, // Lower resolution for performance
-                },
-                neck_style: NeckStyle::Curved {
-                    curvature: 1.0,
-                    width: 3.0,
-                    height: 4.0,
-                    curve_resolution: 5,
-                },
-                color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-            },
-            HourglassMeshPlatesConfig {
-                width: 25.0,
-                height: 2.0,
-                ..Default::default()
-            },
-        ),
-        HourglassShape::Modern => (
-            HourglassMeshBodyConfig {
-                total_height: base_height,
-                bulb_style: BulbStyle::Circular {
-                    curvature: 0.0,
-                    width_factor: 1.0,
-                    curve_resolution: 5,
-                },
-                neck_style: NeckStyle::Straight {
-                    width: 2.5,
-                    height: 6.0,
-                },
-                color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-            },
-            HourglassMeshPlatesConfig {
-                width: 22.0,
-                height: 2.5,
-                ..Default::default()
-            },
-        ),
-        HourglassShape::Slim => (
-            HourglassMeshBodyConfig {
-                total_height: base_height * 1.2,
-                bulb_style: BulbStyle::Circular {
-                    curvature: 1.5,
-                    width_factor: 0.7,
-                    curve_resolution: 8,
-                },
-                neck_style: NeckStyle::Curved {
-                    curvature: 1.5,
-                    width: 2.0,
-                    height: 5.0,
-                    curve_resolution: 4,
-                },
-                color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-            },
-            HourglassMeshPlatesConfig {
-                width: 20.0,
-                height: 1.5,
-                ..Default::default()
-            },
-        ),
-        HourglassShape::Wide => (
-            HourglassMeshBodyConfig {
-                total_height: base_height * 0.8,
-                bulb_style: BulbStyle::Circular {
-                    curvature: 1.0,
-                    width_factor: 1.2,
-                    curve_resolution: 10,
-                },
-                neck_style: NeckStyle::Curved {
-                    curvature: 0.7,
-                    width: 4.0,
-                    height: 3.0,
-                    curve_resolution: 6,
-                },
-                color: Color::srgba(0.85, 0.95, 1.0, 0.2),
-            },
-            HourglassMeshPlatesConfig {
-                width: 28.0,
-                height: 3.0,
-                ..Default::default()
-            },
-        ),
+        if transform.scale != Vec3::splat(scale) {
+            transform.scale = Vec3::splat(scale);
+        }
     }
 }
 
@@ -367,8 +238,10 @@ fn spawn_hourglass(
     mut materials: ResMut<Assets<ColorMaterial>>,
     config: Res<HourglassConfig>,
     timer_state: Res<TimerState>,
+    placement: Res<HourglassPlacement>,
+    library: Res<ShapeLibrary>,
 ) {
-    let (body_config, plates_config) = get_main_shape_config(config.shape_type);
+    let (body_config, plates_config) = library.get(&config.shape_type).main_configs();
 
     // Create an hourglass with body, plates, and automatic timing using the builder pattern
     let entity = HourglassMeshBuilder::new(Transform::from_xyz(0.0, 0.0, 0.0))
@@ -377,7 +250,7 @@ fn spawn_hourglass(
         .with_sand(HourglassMeshSandConfig {
             color: config.color,
             fill_percent: 0.0, // Start with bottom bulb filled (empty top)
-            wall_offset: 4.0,
+            wall_offset: SAND_WALL_OFFSET,
         })
         .with_sand_splash(SandSplashConfig {
             particle_color: config.color,
@@ -387,162 +260,355 @@ fn spawn_hourglass(
         })
         .with_timing(timer_state.duration)
         .build(&mut commands, &mut meshes, &mut materials);
-    commands
-        .entity(entity)
-        .insert((MainHourglass, DragState::new(), Name::new("Main Hourglass")));
+    place_main_hourglass(&mut commands, entity, DragState::new(), &placement);
+}
+
+/// Tag a freshly built hourglass as the main one and attach it to the host's
+/// root entity, if any.
+fn place_main_hourglass(
+    commands: &mut Commands,
+    entity: Entity,
+    drag_state: DragState,
+    placement: &HourglassPlacement,
+) {
+    let mut hourglass = commands.entity(entity);
+    hourglass.insert((MainHourglass, drag_state, Name::new("Main Hourglass")));
+    if let Some(root) = placement.root {
+        hourglass.insert(ChildOf(root));
+    }
 }
 
+/// `RenderLayers` are not inherited, so copy the configured layers onto every
+/// new hourglass mesh, its children, rebuilt glass and plates and the loose
+/// sand particles.
+fn apply_render_layers(
+    mut commands: Commands,
+    placement: Res<HourglassPlacement>,
+    hourglasses: Query<Entity, Added<HourglassMesh>>,
+    parts: Query<
+        Entity,
+        Or<(
+            Added<HourglassMeshBody>,
+            Added<HourglassMeshPlate>,
+            Added<MorphPart>,
+        )>,
+    >,
+    particles: Query<Entity, Added<SandSplashParticle>>,
+    children: Query<&Children>,
+) {
+    if placement.render_layers == RenderLayers::default() {
+        return;
+    }
+    let meshes = hourglasses
+        .iter()
+        .flat_map(|entity| std::iter::once(entity).chain(children.iter_descendants(entity)));
+    for entity in meshes.chain(&parts).chain(&particles) {
+        commands
+            .entity(entity)
+            .insert(placement.render_layers.clone());
+    }
+}
+
+/// Recolour the sand, its material and the splash particles in place. With
+/// sand layers they take the colour of the layer running through the neck.
 fn update_hourglass_color(
     config: Res<HourglassConfig>,
-    mut hourglass_query: Query<&mut Hourglass, With<MainHourglass>>,
-    mut splash_query: Query<&mut SandSplash, With<MainHourglass>>,
+    preview: Option<Res<SandColorPreview>>,
+    layers: Option<Res<SandLayers>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut hourglass_query: Query<
+        (
+            &mut Hourglass,
+            Option<&mut SandSplash>,
+            Option<&mut HourglassMeshSandState>,
+            Option<&Children>,
+            Option<&MorphMeshes>,
+        ),
+        With<MainHourglass>,
+    >,
+    sand_query: Query<&MeshMaterial2d<ColorMaterial>, With<HourglassMeshSand>>,
 ) {
-    if config.is_changed() {
-        // Update sand color
-        for mut hourglass in hourglass_query.iter_mut() {
-            hourglass.sand_color = config.color;
+    let layers_changed = layers.as_ref().is_some_and(|layers| layers.is_changed());
+    let sand_color = preview.map_or(config.color, |preview| preview.sand_color(&config));
+    for (mut hourglass, splash, sand_state, children, morph_meshes) in &mut hourglass_query {
+        let color = layers
+            .as_ref()
+            .and_then(|layers| layers.draining(hourglass.lower_chamber))
+            .unwrap_or(sand_color);
+        if !config.is_changed() && !layers_changed && hourglass.sand_color == color {
+            continue;
+        }
+        hourglass.sand_color = color;
+        if let Some(mut splash) = splash {
+            splash.config.particle_color = color;
         }
+        // An emptied bulb gets its material back from the sand state.
+        if let Some(mut sand_state) = sand_state
+            && sand_state.sand_config.color != color
+        {
+            sand_state.sand_config.color = color;
+        }
+        let sand_materials = sand_query
+            .iter_many(children.into_iter().flatten())
+            .map(|material| &material.0)
+            .chain(morph_meshes.map(|parts| &parts.sand_material));
+        for material in sand_materials {
+            if let Some(material) = materials.get_mut(material)
+                && material.color != color
+            {
+                material.color = color;
+            }
+        }
+    }
+}
 
-        // Update particle color for sand splash
-        for mut sand_splash in splash_query.iter_mut() {
-            sand_splash.config.particle_color = config.color;
+/// How long the hourglass takes to change into a newly selected shape.
+const SHAPE_TRANSITION_SECS: f32 = 0.4;
+
+type ShapeConfigs = (HourglassMeshBodyConfig, HourglassMeshPlatesConfig);
+
+/// The main hourglass changing into a newly selected shape. Selecting another
+/// shape on the way sets off again from wherever the glass has got to. The
+/// tween is drawn with the morphing meshes, whose vertices are blended in
+/// place between the two profiles.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct ShapeTransition {
+    from: ShapeConfigs,
+    to: ShapeConfigs,
+    profiles: [ShapeProfile; 2],
+    elapsed: f32,
+}
+
+impl ShapeTransition {
+    fn new(from: ShapeConfigs, to: ShapeConfigs) -> Self {
+        let profiles = [&from, &to].map(|(body, plates)| ShapeProfile::new(body, plates));
+        Self {
+            from,
+            to,
+            profiles,
+            elapsed: 0.0,
         }
     }
+
+    fn t(&self) -> f32 {
+        MorphEasing::Smooth.ease(self.elapsed / SHAPE_TRANSITION_SECS)
+    }
+
+    fn configs(&self) -> ShapeConfigs {
+        blend_shape_configs(&self.from, &self.to, self.t())
+    }
+
+    /// Blend the profiles into `profile`, reusing its buffers.
+    fn blend_profile(&self, profile: &mut ShapeProfile) {
+        let [from, to] = &self.profiles;
+        profile.blend(from, to, self.t());
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= SHAPE_TRANSITION_SECS
+    }
 }
 
+/// Redraw the static shape when it is switched, edited or reloaded. A newly
+/// selected shape is tweened to over `SHAPE_TRANSITION_SECS` on the morphing
+/// meshes and built once it is reached; edits, reloads and leaving morphing
+/// redraw straight away. Colour changes never come through here;
+/// `update_hourglass_color` handles them.
 fn update_hourglass_shape(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     config: Res<HourglassConfig>,
-    timer_state: Res<TimerState>,
     time: Res<Time>,
-    query: Query<(Entity, &Hourglass, &DragState), With<MainHourglass>>,
+    mut query: Query<
+        (
+            Entity,
+            &Hourglass,
+            &mut HourglassMeshSandState,
+            Option<&MorphMeshes>,
+            Option<&mut ShapeTransition>,
+        ),
+        With<MainHourglass>,
+    >,
+    mut plates: Query<&mut Transform, With<MorphPart>>,
+    library: Res<ShapeLibrary>,
+    preview: Res<ShapePreview>,
     mut last_shape_type: Local<Option<HourglassShape>>,
     mut last_shape_mode: Local<Option<ShapeMode>>,
-    mut last_recreation_time: Local<f32>,
-    mut last_color_mode: Local<Option<ColorMode>>,
+    mut profile: Local<ShapeProfile>,
 ) {
-    // Only handle static shape mode
-    if config.shape_mode == ShapeMode::Static {
-        // Check if shape type or shape mode actually changed
-        let shape_changed = last_shape_type.is_none_or(|last| last != config.shape_type);
-        let mode_changed = last_shape_mode.is_none_or(|last| last != config.shape_mode);
-        let color_mode_changed = last_color_mode.is_none_or(|last| last != config.color_mode);
-
-        // For shape/mode changes, recreate immediately
-        if shape_changed || mode_changed || color_mode_changed {
-            *last_shape_type = Some(config.shape_type);
-            *last_shape_mode = Some(config.shape_mode);
-            *last_color_mode = Some(config.color_mode);
-            *last_recreation_time = time.elapsed_secs();
-        }
-        // For color-only changes in rainbow mode, throttle recreation to allow particles but update colors
-        else if config.is_changed() && config.color_mode == ColorMode::Rainbow {
-            let current_time = time.elapsed_secs();
-            // Only recreate every 0.1 seconds (10 FPS) to balance color updates with particle visibility
-            if current_time - *last_recreation_time < 0.01 {
-                return; // Throttle recreation to prevent particle issues
+    let (shape, shape_mode) = preview.shape(&config);
+    let mode_changed = last_shape_mode.replace(shape_mode) != Some(shape_mode);
+    if shape_mode != ShapeMode::Static {
+        // Morphing takes over from a transition under way.
+        for (entity, .., transition) in &query {
+            if transition.is_some() {
+                commands.entity(entity).remove::<ShapeTransition>();
             }
-            *last_recreation_time = current_time;
         }
-        // For static color changes, recreate to ensure color is applied properly
-        else if config.is_changed() && config.color_mode == ColorMode::Static {
-            // Always recreate for static color changes to ensure proper color update
-            *last_recreation_time = time.elapsed_secs();
-        }
-        // For other cases where nothing changed, return early
-        else if !shape_changed && !mode_changed && !config.is_changed() {
-            return;
-        }
-        // Preserve current hourglass state and drag state
-        let (
-            _current_upper,
-            _current_lower,
-            _current_running,
-            _current_remaining,
-            current_flipping,
-            current_drag_state,
-        ) = if let Ok((_, hourglass, drag_state)) = query.single() {
-            (
-                hourglass.upper_chamber,
-                hourglass.lower_chamber,
-                hourglass.running,
-                hourglass.remaining_time,
-                hourglass.flipping,
-                drag_state.clone(),
-            )
-        } else {
-            (
-                0.0,
-                1.0,
-                false,
-                timer_state.duration,
-                false,
-                DragState::new(),
-            )
-        };
-
-        // Don't interrupt the hourglass if it's currently flipping
-        if current_flipping {
-            return;
+        return;
+    }
+    // A reloaded or edited shape redraws the hourglass like a shape change.
+    let shape_changed =
+        library.is_changed() || last_shape_type.as_ref().is_none_or(|last| last != shape);
+    let selected_from = last_shape_type
+        .clone()
+        .filter(|_| shape_changed && !mode_changed && !library.is_changed());
+    if shape_changed || mode_changed {
+        if library.find(shape).is_none() {
+            warn!("No shape named `{}`, drawing classic", shape.name());
         }
+        *last_shape_type = Some(shape.clone());
+    }
 
-        // Despawn the old hourglass
-        for (entity, _, _) in query.iter() {
-            commands.entity(entity).despawn();
+    let target = library.get(shape).main_configs();
+    for (entity, hourglass, mut sand_state, parts, transition) in &mut query {
+        let fill = hourglass.upper_chamber;
+        if let Some(previous) = &selected_from {
+            let from = match &transition {
+                Some(transition) => transition.configs(),
+                None => library.get(previous).main_configs(),
+            };
+            let transition = ShapeTransition::new(from, target.clone());
+            if parts.is_none() {
+                transition.blend_profile(&mut profile);
+                spawn_morph_meshes(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    entity,
+                    &profile,
+                    fill,
+                    sand_state.sand_config.color,
+                );
+            }
+            commands.entity(entity).insert(transition);
+            continue;
         }
 
-        // Calculate correct fill percentage based on timer state
-        // fill_percent 1.0 = top chamber full, 0.0 = bottom chamber full
-        let fill_percent = if timer_state.duration > 0.0 {
-            timer_state.remaining / timer_state.duration
+        let (body_config, plates_config) = if shape_changed || mode_changed {
+            if transition.is_some() {
+                commands.entity(entity).remove::<ShapeTransition>();
+            }
+            target.clone()
+        } else if let Some(mut transition) = transition {
+            transition.elapsed += time.delta_secs();
+            if transition.is_finished() {
+                commands.entity(entity).remove::<ShapeTransition>();
+                transition.to.clone()
+            } else {
+                sand_state.body_config = transition.configs().0;
+                if let Some(parts) = parts {
+                    transition.blend_profile(&mut profile);
+                    draw_morph_meshes(
+                        parts,
+                        &profile,
+                        fill,
+                        &mut meshes,
+                        &mut materials,
+                        &mut plates,
+                    );
+                }
+                continue;
+            }
         } else {
-            1.0
+            continue;
         };
 
-        // Spawn a new hourglass with the new shape and preserved state
-        let (body_config, plates_config) = get_main_shape_config(config.shape_type);
-
-        let entity = HourglassMeshBuilder::new(Transform::from_xyz(0.0, 0.0, 0.0))
-            .with_body(body_config)
-            .with_plates(plates_config)
-            .with_sand(HourglassMeshSandConfig {
-                color: config.color,
-                fill_percent,
-                wall_offset: 4.0,
-            })
-            .with_sand_splash(SandSplashConfig {
-                particle_color: config.color,
-                splash_radius: 20.0,
-                particle_size: 2.0,
-                ..Default::default()
-            })
-            .with_timing(timer_state.duration)
-            .build(&mut commands, &mut meshes, &mut materials);
+        // The morphing meshes of a tween or of morphing mode give way to the
+        // builder's, sand included.
+        let sand_config = parts.is_some().then(|| sand_state.sand_config.clone());
+        rebuild_hourglass_geometry(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            entity,
+            &mut sand_state,
+            body_config,
+            plates_config,
+            sand_config,
+        );
+    }
+}
 
-        commands.entity(entity).insert((
-            MainHourglass,
-            current_drag_state, // Use the preserved drag state
-            Name::new("Main Hourglass"),
-        ));
+/// Swap the glass and plate meshes of `entity` for ones built from the new
+/// configs, and have the sand regenerate inside the new outline. The entity
+/// itself survives, so its timer, flip animation, drag gesture and splash
+/// particles carry on undisturbed. Passing `sand_config` also replaces the
+/// sand, along with any morphing-mode parts.
+fn rebuild_hourglass_geometry(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    entity: Entity,
+    sand_state: &mut HourglassMeshSandState,
+    body_config: HourglassMeshBodyConfig,
+    plates_config: HourglassMeshPlatesConfig,
+    sand_config: Option<HourglassMeshSandConfig>,
+) {
+    sand_state.body_config = body_config.clone();
+    sand_state.needs_update = true;
 
-        // Note: State will be restored by update_hourglass_timer system
+    // The builder only spawns whole hourglasses, so build the new parts under
+    // a scratch parent and move them across.
+    let replace_sand = sand_config.is_some();
+    let mut builder = HourglassMeshBuilder::new(Transform::default())
+        .with_body(body_config)
+        .with_plates(plates_config);
+    if let Some(sand_config) = sand_config {
+        builder = builder.with_sand(sand_config);
     }
+    let scratch = builder.build(commands, meshes, materials);
+    commands.queue(move |world: &mut World| {
+        let parts: Vec<Entity> = world
+            .get::<Children>(scratch)
+            .map(|children| children.to_vec())
+            .unwrap_or_default();
+        let Ok(hourglass) = world.get_entity(entity) else {
+            world.despawn(scratch);
+            return;
+        };
+        let stale: Vec<Entity> = hourglass
+            .get::<Children>()
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&child| {
+                world.get::<HourglassMeshBody>(child).is_some()
+                    || world.get::<HourglassMeshPlate>(child).is_some()
+                    || world.get::<MorphPart>(child).is_some()
+                    || (replace_sand && world.get::<HourglassMeshSand>(child).is_some())
+            })
+            .collect();
+        let mut hourglass = world.entity_mut(entity);
+        hourglass.add_children(&parts);
+        if replace_sand {
+            hourglass.remove::<MorphMeshes>();
+        }
+        for part in stale.into_iter().chain([scratch]) {
+            world.despawn(part);
+        }
+    });
 }
 
 fn update_hourglass_timer(
     timer_state: Res<TimerState>,
+    cascade: Option<Res<CascadeSettings>>,
     mut query: Query<&mut Hourglass, With<MainHourglass>>,
 ) {
+    // A cascading countdown only shows its current hour in the main glass.
+    let (duration, remaining) = match cascade.and_then(|cascade| cascade.segment(&timer_state)) {
+        Some(segment) => (segment.duration, segment.remaining),
+        None => (timer_state.duration, timer_state.remaining),
+    };
     for mut hourglass in query.iter_mut() {
-        hourglass.total_time = timer_state.duration;
-        hourglass.remaining_time = timer_state.remaining;
+        hourglass.total_time = duration;
+        hourglass.remaining_time = remaining;
         hourglass.running = timer_state.is_running;
 
         // Always update chamber levels based on timer state, regardless of running state
-        if timer_state.duration > 0.0 && !hourglass.flipping {
-            let progress = timer_state.remaining / timer_state.duration;
+        if duration > 0.0 && !hourglass.flipping {
+            let progress = remaining / duration;
             hourglass.upper_chamber = progress; // Amount of time remaining
             hourglass.lower_chamber = 1.0 - progress; // Amount of time elapsed
         }
@@ -551,15 +617,19 @@ fn update_hourglass_timer(
 
 fn handle_hourglass_click(
     mouse_input: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
-    mut hourglass_query: Query<(&Transform, &mut DragState, &mut Hourglass), With<MainHourglass>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<HourglassCamera>>,
+    mut hourglass_query: Query<
+        (&GlobalTransform, &mut DragState, &mut Hourglass),
+        With<MainHourglass>,
+    >,
     mut timer_commands: EventWriter<TimerCommand>,
     mini_button_query: Query<
         (&Transform, &Visibility),
         (With<MiniHourglass>, Without<MainHourglass>),
     >,
     ui_interaction_query: Query<&Interaction>,
+    urgency: Option<Res<Urgency>>,
 ) {
     let (Ok(window), Ok((camera, camera_transform))) = (windows.single(), camera_query.single())
     else {
@@ -595,9 +665,12 @@ fn handle_hourglass_click(
             let over_ui_button = ui_interaction_query
                 .iter()
                 .any(|interaction| *interaction != Interaction::None);
-            let hourglass_pos = hourglass_transform.translation.truncate();
+            let hourglass_pos = hourglass_transform.translation().truncate();
+            // Hit-test the resting size so the urgency pulse does not move
+            // the edge of the target under the pointer.
+            let pulse = urgency.map_or(1.0, |urgency| urgency.pulse);
             let over_hourglass = world_position.distance(hourglass_pos)
-                < main_hourglass_hit_radius(hourglass_transform.scale.x);
+                < main_hourglass_hit_radius(hourglass_transform.scale().x / pulse);
 
             if over_hourglass && !over_mini_button && !over_ui_button {
                 drag_state.begin(cursor_position);
@@ -633,6 +706,8 @@ fn handle_hourglass_click(
     }
 }
 
+/// Morph between the keyframe shapes by blending the vertices of meshes that
+/// are built once on entering morphing mode.
 fn update_morphing_shape(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -640,133 +715,210 @@ fn update_morphing_shape(
     config: Res<HourglassConfig>,
     timer_state: Res<TimerState>,
     time: Res<Time>,
-    query: Query<(Entity, &Hourglass, &DragState), With<MainHourglass>>,
+    mut query: Query<
+        (
+            Entity,
+            &Hourglass,
+            &mut HourglassMeshSandState,
+            Option<&MorphMeshes>,
+        ),
+        With<MainHourglass>,
+    >,
+    mut plates: Query<&mut Transform, With<MorphPart>>,
+    library: Res<ShapeLibrary>,
+    morph: Res<MorphSettings>,
+    preview: Res<ShapePreview>,
+    mut keyframes: Local<MorphKeyframes>,
+    mut keyframe_configs: Local<Vec<ShapeConfigs>>,
     mut last_update_time: Local<f32>,
+    mut last_frame: Local<Option<(f32, f32)>>,
 ) {
-    // Only handle morphing shape mode, and throttle updates to avoid excessive recreation
-    if config.shape_mode == ShapeMode::Morphing {
-        let current_time = time.elapsed_secs();
-        if current_time - *last_update_time < 0.01 {
-            return;
-        }
-        *last_update_time = current_time;
-
-        // Preserve current hourglass state and drag state
-        let (
-            _current_upper,
-            _current_lower,
-            _current_running,
-            _current_remaining,
-            current_flipping,
-            current_drag_state,
-        ) = if let Ok((_, hourglass, drag_state)) = query.single() {
-            (
-                hourglass.upper_chamber,
-                hourglass.lower_chamber,
-                hourglass.running,
-                hourglass.remaining_time,
-                hourglass.flipping,
-                drag_state.clone(),
-            )
-        } else {
-            (
-                0.0,
-                1.0,
-                timer_state.is_running,
-                timer_state.remaining,
-                false,
-                DragState::new(),
-            )
-        };
+    if preview.shape(&config).1 != ShapeMode::Morphing {
+        // Switching back to morphing redraws straight away.
+        *last_frame = None;
+        return;
+    }
+    // Throttle updates to avoid excessive rebuilds
+    let current_time = time.elapsed_secs();
+    if current_time - *last_update_time < 0.01 {
+        return;
+    }
+    *last_update_time = current_time;
+
+    if keyframes.is_empty() || library.is_changed() || morph.is_changed() {
+        *keyframe_configs = morph_sequence(&library, &morph)
+            .into_iter()
+            .map(ShapeDefinition::main_configs)
+            .collect();
+        keyframes.set(
+            keyframe_configs
+                .iter()
+                .map(|(body, plates)| ShapeProfile::new(body, plates)),
+        );
+        *last_frame = None;
+    }
 
-        // Don't interrupt the hourglass if it's currently flipping
-        if current_flipping {
-            return;
+    let t = morph.phase(current_time, &timer_state);
+    for (entity, hourglass, mut sand_state, parts) in &mut query {
+        // The sand follows the chambers, which lag the timer during a flip.
+        let fill = hourglass.upper_chamber;
+        if parts.is_some() && *last_frame == Some((t, fill)) {
+            continue;
         }
-
-        // Cycle through shapes over time (complete cycle every 8 seconds)
-        let cycle_time = 8.0;
-        let t = (current_time % cycle_time) / cycle_time;
-
-        // Create morphed shape parameters
-        let (body_config, plates_config) = get_morphed_shape_config(t);
-
-        // Despawn the old hourglass
-        for (entity, _, _) in query.iter() {
-            commands.entity(entity).despawn();
+        let reshaped = parts.is_none() || last_frame.is_none_or(|(last_t, _)| last_t != t);
+        *last_frame = Some((t, fill));
+
+        let (from, to, local_t) = morph_segment(t, keyframes.len(), &morph);
+        let profile = keyframes.blend(from, to, local_t);
+        if reshaped {
+            // Splash particles land on the bottom pile, so keep its height in
+            // step.
+            sand_state.body_config =
+                blend_shape_configs(&keyframe_configs[from], &keyframe_configs[to], local_t).0;
         }
 
-        // Calculate correct fill percentage based on timer state
-        // fill_percent 1.0 = top chamber full, 0.0 = bottom chamber full
-        let fill_percent = if timer_state.duration > 0.0 {
-            timer_state.remaining / timer_state.duration
-        } else {
-            1.0
+        let Some(parts) = parts else {
+            spawn_morph_meshes(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                entity,
+                profile,
+                fill,
+                sand_state.sand_config.color,
+            );
+            continue;
         };
+        draw_morph_meshes(
+            parts,
+            profile,
+            fill,
+            &mut meshes,
+            &mut materials,
+            &mut plates,
+        );
+    }
+}
 
-        // Spawn a new hourglass with the morphed shape and correct sand level
-        let entity = HourglassMeshBuilder::new(Transform::from_xyz(0.0, 0.0, 0.0))
-            .with_body(body_config)
-            .with_plates(plates_config)
-            .with_sand(HourglassMeshSandConfig {
-                color: config.color,
-                fill_percent,
-                wall_offset: 4.0,
-            })
-            .with_sand_splash(SandSplashConfig {
-                particle_color: config.color,
-                splash_radius: 20.0,
-                particle_size: 2.0,
-                ..Default::default()
+/// Spawn the morphing meshes under `entity` at `profile` in place of the
+/// builder's glass, plates and sand.
+fn spawn_morph_meshes(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    entity: Entity,
+    profile: &ShapeProfile,
+    fill: f32,
+    sand_color: Color,
+) {
+    let parts = MorphMeshes::spawn(
+        commands, meshes, materials, entity, profile, fill, sand_color,
+    );
+    commands.entity(entity).insert(parts);
+    commands.queue(move |world: &mut World| {
+        let stale: Vec<Entity> = world
+            .get::<Children>(entity)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&child| {
+                world.get::<HourglassMeshBody>(child).is_some()
+                    || world.get::<HourglassMeshPlate>(child).is_some()
+                    || world.get::<HourglassMeshSand>(child).is_some()
             })
-            .with_timing(timer_state.duration)
-            .build(&mut commands, &mut meshes, &mut materials);
+            .collect();
+        for part in stale {
+            world.despawn(part);
+        }
+    });
+}
 
-        commands.entity(entity).insert((
-            MainHourglass,
-            current_drag_state, // Use the preserved drag state
-            Name::new("Main Hourglass"),
-        ));
+/// Write `profile` into the morphing meshes in place, with the top bulb
+/// holding `fill`.
+fn draw_morph_meshes(
+    parts: &MorphMeshes,
+    profile: &ShapeProfile,
+    fill: f32,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    plates: &mut Query<&mut Transform, With<MorphPart>>,
+) {
+    if let Some(mesh) = meshes.get_mut(&parts.glass) {
+        profile.write_glass(mesh);
+    }
+    if let Some(mesh) = meshes.get_mut(&parts.top_sand) {
+        profile.write_top_sand(mesh, fill);
+    }
+    if let Some(mesh) = meshes.get_mut(&parts.bottom_sand) {
+        profile.write_bottom_sand(mesh, fill);
+    }
+    for (plate, side) in parts.plates.into_iter().zip([1.0, -1.0]) {
+        if let Ok(mut transform) = plates.get_mut(plate) {
+            transform.set_if_neq(profile.plate_transform(side));
+        }
+    }
+    for (material, color) in [
+        (&parts.glass_material, profile.glass_color),
+        (&parts.plate_material, profile.plate_color),
+    ] {
+        if let Some(material) = materials.get_mut(material)
+            && material.color != color
+        {
+            material.color = color;
+        }
+    }
+}
 
-        // Note: State will be restored by update_hourglass_timer system
+/// The shapes `settings` morphs through: its sequence where those shapes are
+/// loaded, otherwise the whole library.
+fn morph_sequence<'a>(
+    library: &'a ShapeLibrary,
+    settings: &MorphSettings,
+) -> Vec<&'a ShapeDefinition> {
+    let sequence: Vec<_> = settings
+        .sequence
+        .iter()
+        .filter_map(|shape| library.find(shape))
+        .collect();
+    if sequence.is_empty() {
+        library.definitions().iter().collect()
+    } else {
+        sequence
     }
 }
 
-// Helper function to create morphed shape configurations
-fn get_morphed_shape_config(t: f32) -> (HourglassMeshBodyConfig, HourglassMeshPlatesConfig) {
-    // Define the 4 shape configurations
-    let shapes = [
-        HourglassShape::Classic,
-        HourglassShape::Modern,
-        HourglassShape::Slim,
-        HourglassShape::Wide,
-    ];
+/// The two of `count` shapes in the morph sequence to blend between at phase
+/// `t`, and how far (eased) to blend.
+fn morph_segment(t: f32, count: usize, settings: &MorphSettings) -> (usize, usize, f32) {
+    // A clock-driven morph loops back to the first shape; a progress-synced
+    // one runs from the first shape to the last exactly once.
+    let segments = match settings.sync {
+        MorphSync::Clock => count,
+        MorphSync::Progress => count.saturating_sub(1).max(1),
+    };
 
     // Determine which shapes to interpolate between
-    let segment = t * 4.0; // 0-4 range
-    let segment_index = segment.floor() as usize % 4;
-    let next_index = (segment_index + 1) % 4;
-    let local_t = segment - segment.floor(); // 0-1 within the segment
-
-    let shape1 = shapes[segment_index];
-    let shape2 = shapes[next_index];
+    let segment = t.clamp(0.0, 1.0) * segments as f32; // 0-segments range
+    let segment_index = (segment.floor() as usize).min(segments - 1);
+    let local_t = settings.easing.ease(segment - segment_index as f32); // 0-1 within the segment
 
-    // Get the base configurations for both shapes
-    let (config1, plates1) = get_main_shape_config(shape1);
-    let (config2, plates2) = get_main_shape_config(shape2);
+    (segment_index % count, (segment_index + 1) % count, local_t)
+}
 
-    // Interpolate between the configurations
+/// Configs `t` of the way from one shape's to another's.
+fn blend_shape_configs(from: &ShapeConfigs, to: &ShapeConfigs, t: f32) -> ShapeConfigs {
+    let ((config1, plates1), (config2, plates2)) = (from, to);
     let interpolated_body = HourglassMeshBodyConfig {
-        total_height: lerp_f32(config1.total_height, config2.total_height, local_t),
-        bulb_style: interpolate_bulb_style(&config1.bulb_style, &config2.bulb_style, local_t),
-        neck_style: interpolate_neck_style(&config1.neck_style, &config2.neck_style, local_t),
-        color: Color::srgba(0.85, 0.95, 1.0, 0.2),
+        total_height: lerp_f32(config1.total_height, config2.total_height, t),
+        bulb_style: interpolate_bulb_style(&config1.bulb_style, &config2.bulb_style, t),
+        neck_style: interpolate_neck_style(&config1.neck_style, &config2.neck_style, t),
+        color: config1.color.mix(&config2.color, t),
     };
 
     let interpolated_plates = HourglassMeshPlatesConfig {
-        width: lerp_f32(plates1.width, plates2.width, local_t),
-        height: lerp_f32(plates1.height, plates2.height, local_t),
-        ..Default::default()
+        width: lerp_f32(plates1.width, plates2.width, t),
+        height: lerp_f32(plates1.height, plates2.height, t),
+        color: plates1.color.mix(&plates2.color, t),
     };
 
     (interpolated_body, interpolated_plates)
@@ -878,28 +1030,26 @@ fn interpolate_neck_style(style1: &NeckStyle, style2: &NeckStyle, t: f32) -> Nec
     }
 }
 
-// Flip the newly (re)spawned main hourglass when a color/shape change requested
-// it. Shape/color changes despawn and respawn the hourglass entity, so the flip
-// must land on the fresh entity (matched by `Added<MainHourglass>` the frame after
-// the recreation command flushes), never the about-to-be-despawned old one.
+// Flip the main hourglass when a color/shape change requested it. Appearance
+// changes update the hourglass in place, so the flip lands on the same entity.
 fn apply_pending_flip(
     mut pending: ResMut<PendingFlip>,
-    mut query: Query<&mut Hourglass, (With<MainHourglass>, Added<MainHourglass>)>,
+    mut query: Query<&mut Hourglass, With<MainHourglass>>,
 ) {
     if !pending.0 {
         return;
     }
-    // Clear the flag only once we actually flip, so a request made while a previous
-    // flip was blocking recreation survives to the real respawn.
-    if let Ok(mut hourglass) = query.single_mut() {
-        if hourglass.can_flip() {
-            // Mirror the drag-flip (493-498): start with all sand in the bottom so
-            // the crate's end-of-flip chamber swap leaves the top full.
-            hourglass.upper_chamber = 0.0;
-            hourglass.lower_chamber = 1.0;
-            hourglass.flip();
-            pending.0 = false;
-        }
+    // Clear the flag only once we actually flip, so a request made during a
+    // previous flip waits for it to finish.
+    if let Ok(mut hourglass) = query.single_mut()
+        && hourglass.can_flip()
+    {
+        // Mirror the drag-flip (493-498): start with all sand in the bottom so
+        // the crate's end-of-flip chamber swap leaves the top full.
+        hourglass.upper_chamber = 0.0;
+        hourglass.lower_chamber = 1.0;
+        hourglass.flip();
+        pending.0 = false;
     }
 }
 
@@ -915,9 +1065,8 @@ fn handle_timer_start(
         // Only flip on the very first start (when timer hasn't been started before)
         if !*has_ever_started {
             // Skip the first-start flip when a color/shape change already queued a
-            // flip: that flip is owned by `apply_pending_flip` on the recreated
-            // entity. Flipping the old entity here would set `flipping=true` and trip
-            // the recreation guard, dropping the shape/color change.
+            // flip: that flip is owned by `apply_pending_flip`, and flipping here
+            // as well would leave the pending request waiting for a second flip.
             if !pending.0 {
                 for mut hourglass in hourglass_query.iter_mut() {
                     if hourglass.can_flip() {
@@ -941,6 +1090,7 @@ fn handle_timer_start(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resources::{ColorMode, MorphEasing};
     use approx::assert_abs_diff_eq;
 
     // --- lerp_f32 ---------------------------------------------------------
@@ -1134,10 +1284,36 @@ mod tests {
 
     // --- get_morphed_shape_config -----------------------------------------
 
+    /// Shape configs for phase `t` of the morph, blended between the two
+    /// keyframes' settings rather than their meshes.
+    fn get_morphed_shape_config(
+        t: f32,
+        library: &ShapeLibrary,
+        settings: &MorphSettings,
+    ) -> ShapeConfigs {
+        let shapes = morph_sequence(library, settings);
+        let (from, to, local_t) = morph_segment(t, shapes.len(), settings);
+        let shape1 = shapes[from];
+        let shape2 = shapes[to];
+
+        blend_shape_configs(&shape1.main_configs(), &shape2.main_configs(), local_t)
+    }
+
+    fn builtin_main(shape: HourglassShape) -> (HourglassMeshBodyConfig, HourglassMeshPlatesConfig) {
+        ShapeLibrary::default().get(&shape).main_configs()
+    }
+
+    fn clock_morph(
+        t: f32,
+        library: &ShapeLibrary,
+    ) -> (HourglassMeshBodyConfig, HourglassMeshPlatesConfig) {
+        get_morphed_shape_config(t, library, &MorphSettings::default())
+    }
+
     #[test]
     fn morph_anchor_t0_is_classic() {
-        let (body, plates) = get_morphed_shape_config(0.0);
-        let (classic_body, classic_plates) = get_main_shape_config(HourglassShape::Classic);
+        let (body, plates) = clock_morph(0.0, &ShapeLibrary::default());
+        let (classic_body, classic_plates) = builtin_main(HourglassShape::CLASSIC);
         assert_abs_diff_eq!(body.total_height, classic_body.total_height, epsilon = 1e-4);
         assert_abs_diff_eq!(plates.width, classic_plates.width, epsilon = 1e-4);
         assert_abs_diff_eq!(plates.height, classic_plates.height, epsilon = 1e-4);
@@ -1145,8 +1321,8 @@ mod tests {
 
     #[test]
     fn morph_anchor_t025_is_modern() {
-        let (body, plates) = get_morphed_shape_config(0.25);
-        let (modern_body, modern_plates) = get_main_shape_config(HourglassShape::Modern);
+        let (body, plates) = clock_morph(0.25, &ShapeLibrary::default());
+        let (modern_body, modern_plates) = builtin_main(HourglassShape::MODERN);
         assert_abs_diff_eq!(body.total_height, modern_body.total_height, epsilon = 1e-4);
         assert_abs_diff_eq!(plates.width, modern_plates.width, epsilon = 1e-4);
         assert_abs_diff_eq!(plates.height, modern_plates.height, epsilon = 1e-4);
@@ -1155,7 +1331,7 @@ mod tests {
     #[test]
     fn morph_halfway_classic_to_modern() {
         // t = 0.125 -> segment 0.5 -> Classic<->Modern at local_t 0.5.
-        let (body, plates) = get_morphed_shape_config(0.125);
+        let (body, plates) = clock_morph(0.125, &ShapeLibrary::default());
         // Classic and Modern share total_height 400.0.
         assert_abs_diff_eq!(body.total_height, 400.0, epsilon = 1e-4);
         // Plates width: lerp(400, 380, 0.5) = 390; height: lerp(10, 12, 0.5) = 11.
@@ -1166,8 +1342,8 @@ mod tests {
     #[test]
     fn morph_wraps_at_t1_back_to_classic() {
         // t = 1.0 -> segment 4.0 -> floor % 4 == 0 -> Classic, local_t 0.
-        let (body, plates) = get_morphed_shape_config(1.0);
-        let (classic_body, classic_plates) = get_main_shape_config(HourglassShape::Classic);
+        let (body, plates) = clock_morph(1.0, &ShapeLibrary::default());
+        let (classic_body, classic_plates) = builtin_main(HourglassShape::CLASSIC);
         assert_abs_diff_eq!(body.total_height, classic_body.total_height, epsilon = 1e-4);
         assert_abs_diff_eq!(plates.width, classic_plates.width, epsilon = 1e-4);
     }
@@ -1176,7 +1352,7 @@ mod tests {
     fn morph_total_height_finite_and_positive_across_sweep() {
         for i in 0..10 {
             let t = i as f32 / 10.0;
-            let (body, _) = get_morphed_shape_config(t);
+            let (body, _) = clock_morph(t, &ShapeLibrary::default());
             assert!(
                 body.total_height.is_finite() && body.total_height > 0.0,
                 "t = {t}: total_height = {}",
@@ -1185,6 +1361,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn morph_cycles_through_loaded_shapes() {
+        let mut tall = ShapeLibrary::default().get(&HourglassShape::SLIM).clone();
+        tall.name = "tall".to_string();
+        tall.main.height = 600.0;
+        let library = ShapeLibrary::with_loaded([&tall]);
+
+        // Five shapes: t = 0.8 lands on the loaded one, then wraps to Classic.
+        let (body, _) = clock_morph(0.8, &library);
+        assert_abs_diff_eq!(body.total_height, 600.0, epsilon = 1e-3);
+        let (body, _) = clock_morph(0.9, &library);
+        assert_abs_diff_eq!(body.total_height, 500.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn morph_follows_the_chosen_sequence_and_easing() {
+        let library = ShapeLibrary::default();
+        let settings = MorphSettings {
+            sequence: vec![
+                HourglassShape::WIDE,
+                HourglassShape::from_name("missing").unwrap(),
+                HourglassShape::SLIM,
+            ],
+            easing: MorphEasing::EaseIn,
+            ..default()
+        };
+        // Wide -> Slim -> Wide: a quarter of the way is halfway to Slim,
+        // eased in to a quarter of the blend.
+        let (body, _) = get_morphed_shape_config(0.0, &library, &settings);
+        assert_abs_diff_eq!(body.total_height, 320.0, epsilon = 1e-3);
+        let (body, _) = get_morphed_shape_config(0.25, &library, &settings);
+        assert_abs_diff_eq!(body.total_height, 360.0, epsilon = 1e-3);
+        let (body, _) = get_morphed_shape_config(0.5, &library, &settings);
+        assert_abs_diff_eq!(body.total_height, 480.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn progress_synced_morph_ends_on_the_last_shape() {
+        let library = ShapeLibrary::default();
+        let settings = MorphSettings {
+            sync: MorphSync::Progress,
+            ..default()
+        };
+        // Classic, Modern, Slim, Wide over three segments.
+        let (body, _) = get_morphed_shape_config(0.0, &library, &settings);
+        assert_abs_diff_eq!(body.total_height, 400.0, epsilon = 1e-3);
+        let (body, _) = get_morphed_shape_config(2.0 / 3.0, &library, &settings);
+        assert_abs_diff_eq!(body.total_height, 480.0, epsilon = 1e-3);
+        let (body, _) = get_morphed_shape_config(1.0, &library, &settings);
+        assert_abs_diff_eq!(body.total_height, 320.0, epsilon = 1e-3);
+
+        let single = MorphSettings {
+            sequence: vec![HourglassShape::SLIM],
+            ..settings
+        };
+        let (body, _) = get_morphed_shape_config(0.5, &library, &single);
+        assert_abs_diff_eq!(body.total_height, 480.0, epsilon = 1e-3);
+    }
+
     // --- within_click_radius ----------------------------------------------
 
     #[test]
@@ -1245,9 +1480,9 @@ mod tests {
     #[test]
     #[cfg(feature = "chrome_extension")]
     fn sidebar_scale_responds_to_width_and_open_sections() {
-        let collapsed = sidebar_hourglass_scale(360.0, 800.0, false, false);
-        let appearance_open = sidebar_hourglass_scale(360.0, 800.0, true, false);
-        let narrow = sidebar_hourglass_scale(260.0, 800.0, false, false);
+        let collapsed = main_hourglass_scale(360.0, 800.0, false, false);
+        let appearance_open = main_hourglass_scale(360.0, 800.0, true, false);
+        let narrow = main_hourglass_scale(260.0, 800.0, false, false);
         assert!(appearance_open <= collapsed);
         assert!(narrow < collapsed);
         assert!((0.35..=1.0).contains(&collapsed));
@@ -1260,6 +1495,25 @@ mod tests {
         assert_abs_diff_eq!(main_hourglass_hit_radius(0.5), 110.0, epsilon = 1e-6);
     }
 
+    #[test]
+    #[cfg(not(feature = "chrome_extension"))]
+    fn window_scale_fits_the_hourglass_between_the_panels() {
+        // The default 1280x720 window keeps the natural size.
+        assert_abs_diff_eq!(main_hourglass_scale(1280.0, 720.0, true, false), 1.0);
+        let panel_open = main_hourglass_scale(1280.0, 720.0, true, true);
+        let narrow = main_hourglass_scale(300.0, 720.0, true, false);
+        assert!(panel_open < 1.0);
+        assert!(narrow < panel_open);
+        assert_abs_diff_eq!(main_hourglass_scale(100.0, 100.0, true, true), 0.35);
+    }
+
+    #[test]
+    #[cfg(not(feature = "chrome_extension"))]
+    fn window_hit_radius_tracks_render_scale() {
+        assert_abs_diff_eq!(main_hourglass_hit_radius(1.0), 400.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(main_hourglass_hit_radius(0.5), 200.0, epsilon = 1e-6);
+    }
+
     // --- exceeds_drag_threshold -------------------------------------------
 
     #[test]
@@ -1325,10 +1579,8 @@ mod tests {
     // --- apply_pending_flip -----------------------------------------------
     //
     // These exercise the flip-on-change orchestration with a headless `App`.
-    // A `MainHourglass` is spawned via `Commands` in `Startup` so it becomes
-    // visible to `apply_pending_flip`'s `Added<MainHourglass>` filter only
-    // after the command buffer flushes — mirroring how the recreation systems
-    // respawn the entity one frame before the flip lands on it.
+    // A `MainHourglass` is spawned via `Commands` in `Startup`, standing in for
+    // the long-lived hourglass that appearance changes update in place.
 
     /// Build a one-tick app that spawns a default `MainHourglass` via commands
     /// and runs `apply_pending_flip` once in `Update`.
@@ -1352,7 +1604,7 @@ mod tests {
     }
 
     #[test]
-    fn pending_flip_flips_the_recreated_hourglass() {
+    fn pending_flip_flips_the_main_hourglass() {
         let mut app = flip_test_app(true);
 
         let hourglass = single_main_hourglass(&mut app);
@@ -1418,8 +1670,8 @@ mod tests {
 
     #[test]
     fn first_start_skips_flip_when_pending() {
-        // A queued color/shape flip owns the animation on the recreated entity,
-        // so handle_timer_start must not flip the current one.
+        // A queued color/shape flip owns the animation via apply_pending_flip,
+        // so handle_timer_start must not flip the hourglass as well.
         let mut app = timer_start_app(
             TimerState {
                 duration: 100.0,
@@ -1511,7 +1763,8 @@ mod tests {
     #[test]
     fn update_hourglass_color_applies_config_color() {
         let mut app = App::new();
-        app.init_resource::<HourglassConfig>();
+        app.init_resource::<HourglassConfig>()
+            .init_resource::<Assets<ColorMaterial>>();
         let new_color = Color::srgb(0.1, 0.2, 0.3);
         app.world_mut().resource_mut::<HourglassConfig>().color = new_color;
         app.add_systems(Startup, |mut commands: Commands| {
@@ -1523,4 +1776,422 @@ mod tests {
         let hourglass = single_main_hourglass(&mut app);
         assert_eq!(hourglass.sand_color, new_color);
     }
+
+    #[test]
+    fn update_hourglass_color_shows_a_preview_without_touching_the_config() {
+        let mut app = App::new();
+        app.init_resource::<HourglassConfig>()
+            .init_resource::<SandColorPreview>()
+            .init_resource::<Assets<ColorMaterial>>();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn((MainHourglass, Hourglass::default()));
+        });
+        app.add_systems(Update, update_hourglass_color);
+        let preview = Color::srgb(0.1, 0.2, 0.3);
+        app.insert_resource(SandColorPreview(Some(preview)));
+        app.update();
+        assert_eq!(single_main_hourglass(&mut app).sand_color, preview);
+
+        app.insert_resource(SandColorPreview(None));
+        app.update();
+        assert_eq!(
+            single_main_hourglass(&mut app).sand_color,
+            HourglassConfig::default().color
+        );
+    }
+
+    // --- in-place appearance updates --------------------------------------
+
+    /// Entities spawned during the last frame.
+    #[derive(Resource, Default)]
+    struct FrameSpawns(usize);
+
+    fn count_spawns(mut spawns: ResMut<FrameSpawns>, added: Query<(), Added<Transform>>) {
+        spawns.0 = added.iter().count();
+    }
+
+    fn hourglass_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            bevy::asset::AssetPlugin::default(),
+            bevy::input::InputPlugin,
+            bevy::render::mesh::MeshPlugin,
+            TransformPlugin,
+            crate::timer::TimerPlugin,
+            HourglassPlugin,
+        ))
+        .init_asset::<ColorMaterial>()
+        .init_resource::<HourglassConfig>()
+        .init_resource::<TimerState>()
+        .init_resource::<FrameSpawns>()
+        .add_systems(Last, count_spawns);
+        // Let the startup spawn and the first shape sync settle.
+        for _ in 0..3 {
+            app.update();
+        }
+        app
+    }
+
+    fn main_hourglass(app: &mut App) -> Entity {
+        let world = app.world_mut();
+        world
+            .query_filtered::<Entity, With<MainHourglass>>()
+            .single(world)
+            .unwrap()
+    }
+
+    fn sand_colors(app: &mut App) -> Vec<Color> {
+        let world = app.world_mut();
+        let handles: Vec<_> = world
+            .query_filtered::<&MeshMaterial2d<ColorMaterial>, With<HourglassMeshSand>>()
+            .iter(world)
+            .map(|material| material.0.clone())
+            .collect();
+        let materials = world.resource::<Assets<ColorMaterial>>();
+        handles
+            .iter()
+            .map(|handle| materials.get(handle).unwrap().color)
+            .collect()
+    }
+
+    #[test]
+    fn colour_changes_recolour_in_place_without_spawning() {
+        let mut app = hourglass_app();
+        let hourglass = main_hourglass(&mut app);
+
+        for (mode, step) in [(ColorMode::Static, 0.1), (ColorMode::Rainbow, 0.05)] {
+            for frame in 1..=5 {
+                let color = Color::srgb(step * frame as f32, 0.4, 0.6);
+                let mut config = app.world_mut().resource_mut::<HourglassConfig>();
+                config.color_mode = mode;
+                config.color = color;
+                app.update();
+
+                assert_eq!(app.world().resource::<FrameSpawns>().0, 0, "{mode:?}");
+                assert_eq!(main_hourglass(&mut app), hourglass);
+                let colors = sand_colors(&mut app);
+                assert!(!colors.is_empty());
+                assert!(colors.iter().all(|sand| *sand == color));
+                let world = app.world();
+                assert_eq!(world.get::<Hourglass>(hourglass).unwrap().sand_color, color);
+                let splash = world.get::<SandSplash>(hourglass).unwrap();
+                assert_eq!(splash.config.particle_color, color);
+            }
+        }
+    }
+
+    fn body_height(app: &mut App) -> f32 {
+        let hourglass = main_hourglass(app);
+        app.world()
+            .get::<HourglassMeshSandState>(hourglass)
+            .unwrap()
+            .body_config
+            .total_height
+    }
+
+    fn main_height(shape: HourglassShape) -> f32 {
+        ShapeLibrary::default()
+            .get(&shape)
+            .main_configs()
+            .0
+            .total_height
+    }
+
+    fn is_transitioning(app: &mut App) -> bool {
+        let hourglass = main_hourglass(app);
+        app.world().get::<ShapeTransition>(hourglass).is_some()
+    }
+
+    #[test]
+    fn shape_changes_tween_in_place_then_build_the_shape_once() {
+        let mut app = hourglass_app();
+        fixed_frames(&mut app, std::time::Duration::from_millis(50));
+        app.update();
+        let hourglass = main_hourglass(&mut app);
+        let parts = |app: &mut App| {
+            let world = app.world_mut();
+            world
+                .query_filtered::<Entity, Or<(With<HourglassMeshBody>, With<HourglassMeshPlate>)>>()
+                .iter(world)
+                .count()
+        };
+        let spawns = |app: &App| app.world().resource::<FrameSpawns>().0;
+        app.world_mut().resource_mut::<TimerState>().is_running = true;
+        app.update();
+
+        let (classic, slim) = (
+            main_height(HourglassShape::CLASSIC),
+            main_height(HourglassShape::SLIM),
+        );
+        app.world_mut().resource_mut::<HourglassConfig>().shape_type = HourglassShape::SLIM;
+        app.update();
+        assert!(is_transitioning(&mut app));
+        // The glass, two plates and two sand meshes of the tween.
+        assert_eq!(spawns(&app), 5);
+        assert_eq!(parts(&mut app), 0);
+
+        let mut heights = Vec::new();
+        let mut glass = glass_positions(&mut app);
+        loop {
+            app.update();
+            if !is_transitioning(&mut app) {
+                break;
+            }
+            heights.push(body_height(&mut app));
+            assert!(heights.len() < 20, "the transition should end");
+            // Each step writes the vertices in place and leaves the running
+            // timer alone.
+            assert_eq!(spawns(&app), 0);
+            assert_eq!(main_hourglass(&mut app), hourglass);
+            let positions = glass_positions(&mut app);
+            assert_ne!(positions, glass);
+            glass = positions;
+            assert!(app.world().get::<Hourglass>(hourglass).unwrap().running);
+        }
+        // About 400 ms at 50 ms a frame, moving steadily from one to the other.
+        assert!((7..=9).contains(&heights.len()), "{heights:?}");
+        assert!(
+            heights
+                .windows(2)
+                .all(|pair| (pair[1] - pair[0]) * (slim - classic) >= 0.0)
+        );
+        assert!(heights.iter().any(|h| (h - classic) * (h - slim) < 0.0));
+
+        // The last step builds the new shape once.
+        assert_eq!(main_hourglass(&mut app), hourglass);
+        assert!(app.world().get::<MorphMeshes>(hourglass).is_none());
+        assert_eq!(parts(&mut app), 3);
+        assert_eq!(body_height(&mut app), slim);
+        app.update();
+        assert_eq!(spawns(&app), 0);
+        let world = app.world_mut();
+        assert_eq!(world.query::<&MorphPart>().iter(world).count(), 0);
+        assert_eq!(world.query::<&HourglassMeshSand>().iter(world).count(), 2);
+    }
+
+    #[test]
+    fn selecting_again_mid_transition_sets_off_from_the_current_glass() {
+        let mut app = hourglass_app();
+        fixed_frames(&mut app, std::time::Duration::from_millis(50));
+        app.update();
+        app.world_mut().resource_mut::<HourglassConfig>().shape_type = HourglassShape::WIDE;
+        for _ in 0..4 {
+            app.update();
+        }
+        let midway = body_height(&mut app);
+
+        app.world_mut().resource_mut::<HourglassConfig>().shape_type = HourglassShape::SLIM;
+        app.update();
+        let hourglass = main_hourglass(&mut app);
+        let transition = app.world().get::<ShapeTransition>(hourglass).unwrap();
+        assert_abs_diff_eq!(transition.from.0.total_height, midway, epsilon = 1e-3);
+        assert_eq!(
+            transition.to.0.total_height,
+            main_height(HourglassShape::SLIM)
+        );
+
+        for _ in 0..12 {
+            app.update();
+        }
+        assert!(!is_transitioning(&mut app));
+        assert_eq!(body_height(&mut app), main_height(HourglassShape::SLIM));
+    }
+
+    // --- morphing meshes ---------------------------------------------------
+
+    fn set_shape_mode(app: &mut App, mode: ShapeMode) {
+        app.world_mut().resource_mut::<HourglassConfig>().shape_mode = mode;
+    }
+
+    /// Advance virtual time by `step` every update, like a steady frame rate.
+    fn fixed_frames(app: &mut App, step: std::time::Duration) {
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(step));
+    }
+
+    fn glass_positions(app: &mut App) -> Vec<[f32; 3]> {
+        let world = app.world_mut();
+        let handle = world
+            .query::<&MorphMeshes>()
+            .single(world)
+            .unwrap()
+            .glass
+            .clone();
+        let mesh = world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(bevy::render::mesh::VertexAttributeValues::Float32x3(positions)) => {
+                positions.clone()
+            }
+            _ => panic!("glass mesh has no positions"),
+        }
+    }
+
+    #[test]
+    fn morphing_blends_vertices_in_place_without_spawning() {
+        let mut app = hourglass_app();
+        fixed_frames(&mut app, std::time::Duration::from_millis(50));
+        let hourglass = main_hourglass(&mut app);
+        set_shape_mode(&mut app, ShapeMode::Morphing);
+        app.update();
+        app.update();
+        let glass = app
+            .world()
+            .get::<MorphMeshes>(hourglass)
+            .unwrap()
+            .glass
+            .clone();
+        let mut previous = glass_positions(&mut app);
+
+        for _ in 0..10 {
+            app.update();
+            assert_eq!(app.world().resource::<FrameSpawns>().0, 0);
+            let parts = app.world().get::<MorphMeshes>(hourglass).unwrap();
+            assert_eq!(parts.glass, glass);
+            let positions = glass_positions(&mut app);
+            assert_eq!(positions.len(), previous.len());
+            assert_ne!(positions, previous, "the glass should keep morphing");
+            previous = positions;
+        }
+
+        // The builder's meshes made way for the morphing ones.
+        let world = app.world_mut();
+        let builder_parts = world
+            .query_filtered::<(), Or<(With<HourglassMeshBody>, With<HourglassMeshSand>)>>()
+            .iter(world)
+            .count();
+        assert_eq!(builder_parts, 0);
+    }
+
+    #[test]
+    fn leaving_morphing_restores_the_static_meshes() {
+        let mut app = hourglass_app();
+        let hourglass = main_hourglass(&mut app);
+        set_shape_mode(&mut app, ShapeMode::Morphing);
+        app.update();
+        app.update();
+        set_shape_mode(&mut app, ShapeMode::Static);
+        app.update();
+        app.update();
+
+        assert_eq!(main_hourglass(&mut app), hourglass);
+        assert!(app.world().get::<MorphMeshes>(hourglass).is_none());
+        let world = app.world_mut();
+        assert_eq!(world.query::<&MorphPart>().iter(world).count(), 0);
+        assert_eq!(world.query::<&HourglassMeshSand>().iter(world).count(), 2);
+        assert_eq!(world.query::<&HourglassMeshBody>().iter(world).count(), 1);
+        assert_eq!(sand_colors(&mut app).len(), 2);
+    }
+
+    /// Morphing mode as it was before the keyframe cache (046d5df): every
+    /// 10 ms the whole hourglass is despawned and built again at the blended
+    /// configs. Only the shape configs and morph phase come from the current
+    /// code, so both paths morph through the same shapes.
+    fn respawn_morph_every_frame(
+        mut commands: Commands,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+        config: Res<HourglassConfig>,
+        timer_state: Res<TimerState>,
+        time: Res<Time>,
+        library: Res<ShapeLibrary>,
+        morph: Res<MorphSettings>,
+        query: Query<(Entity, &Hourglass, &DragState), With<MainHourglass>>,
+        mut last_update_time: Local<f32>,
+    ) {
+        let current_time = time.elapsed_secs();
+        if current_time - *last_update_time < 0.01 {
+            return;
+        }
+        *last_update_time = current_time;
+
+        // Preserve current drag state
+        let (current_flipping, current_drag_state) =
+            if let Ok((_, hourglass, drag_state)) = query.single() {
+                (hourglass.flipping, drag_state.clone())
+            } else {
+                (false, DragState::new())
+            };
+
+        // Don't interrupt the hourglass if it's currently flipping
+        if current_flipping {
+            return;
+        }
+
+        let t = morph.phase(current_time, &timer_state);
+        let (body_config, plates_config) = get_morphed_shape_config(t, &library, &morph);
+
+        // Despawn the old hourglass
+        for (entity, _, _) in query.iter() {
+            commands.entity(entity).despawn();
+        }
+
+        // fill_percent 1.0 = top chamber full, 0.0 = bottom chamber full
+        let fill_percent = if timer_state.duration > 0.0 {
+            timer_state.remaining / timer_state.duration
+        } else {
+            1.0
+        };
+
+        // Spawn a new hourglass with the morphed shape and correct sand level
+        let entity = HourglassMeshBuilder::new(Transform::from_xyz(0.0, 0.0, 0.0))
+            .with_body(body_config)
+            .with_plates(plates_config)
+            .with_sand(HourglassMeshSandConfig {
+                color: config.color,
+                fill_percent,
+                wall_offset: 4.0,
+            })
+            .with_sand_splash(SandSplashConfig {
+                particle_color: config.color,
+                splash_radius: 20.0,
+                particle_size: 2.0,
+                ..Default::default()
+            })
+            .with_timing(timer_state.duration)
+            .build(&mut commands, &mut meshes, &mut materials);
+
+        commands.entity(entity).insert((
+            MainHourglass,
+            current_drag_state,
+            Name::new("Main Hourglass"),
+        ));
+    }
+
+    /// Frame time of a running, morphing hourglass on a headless app, with
+    /// the keyframe cache and with the old respawn-every-frame path. Run with
+    /// `cargo test --release --lib morph_benchmark -- --ignored`; a failure
+    /// reports both frame times.
+    #[test]
+    #[ignore = "benchmark"]
+    fn morph_benchmark() {
+        const FRAMES: u32 = 600;
+        let run = |respawn: bool| {
+            let mut app = hourglass_app();
+            fixed_frames(&mut app, std::time::Duration::from_millis(16));
+            if respawn {
+                app.add_systems(
+                    Update,
+                    respawn_morph_every_frame.in_set(TimerSystems::Observe),
+                );
+            } else {
+                set_shape_mode(&mut app, ShapeMode::Morphing);
+            }
+            app.world_mut().send_event(TimerCommand::Start);
+            for _ in 0..30 {
+                app.update();
+            }
+            let start = std::time::Instant::now();
+            for _ in 0..FRAMES {
+                app.update();
+            }
+            start.elapsed() / FRAMES
+        };
+
+        let respawned = run(true);
+        let cached = run(false);
+        assert!(
+            cached < respawned,
+            "morph frame: respawn {respawned:?}, keyframe cache {cached:?}"
+        );
+    }
 }
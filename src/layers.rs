@@ -0,0 +1,326 @@
+//! Layered sand. With `SandLayers` set, bands in each layer's colour are drawn
+//! over the main hourglass's sand: the first layer to drain lies on top of the
+//! upper bulb and lands at the bottom of the lower one, so the stripes left
+//! show how many segments remain.
+
+use crate::hourglass::{HourglassPlacement, MainHourglass, ShapeTransition};
+use crate::morph_mesh::{ShapeProfile, sand_band_mesh};
+use crate::resources::{HourglassConfig, HourglassShape, SandLayers, ShapeMode, ShapePreview};
+use crate::shapes::ShapeLibrary;
+use crate::timer::TimerSystems;
+use bevy::prelude::*;
+use bevy::render::view::NoFrustumCulling;
+use bevy_hourglass::{HourglassMeshPlatesConfig, HourglassMeshSandState};
+
+/// In front of the sand, behind the graduation marks.
+const BAND_Z: f32 = 0.15;
+
+pub struct SandLayersPlugin;
+
+impl Plugin for SandLayersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SandLayers>()
+            .register_type::<SandLayers>()
+            .add_systems(Update, draw_sand_layers.after(TimerSystems::Observe));
+    }
+}
+
+/// One layer's sand in the upper (`top`) or lower bulb.
+#[derive(Component)]
+struct SandLayerBand {
+    layer: usize,
+    top: bool,
+}
+
+/// Heights of the band layer `start..end` (as fractions drained) fills in
+/// the upper or lower bulb while the upper one holds `fill`.
+fn band_heights(profile: &ShapeProfile, start: f32, end: f32, fill: f32, top: bool) -> (f32, f32) {
+    if top {
+        // What is left of the layer, measured as the fill it spans.
+        let low = 1.0 - end;
+        let high = (1.0 - start).min(fill).max(low);
+        (profile.top_fill_line(low), profile.top_fill_line(high))
+    } else {
+        // What has run through of it, piled on the layers before.
+        let high = end.min(1.0 - fill).max(start);
+        (
+            profile.bottom_fill_line(1.0 - start),
+            profile.bottom_fill_line(1.0 - high),
+        )
+    }
+}
+
+/// Keep one band per layer and bulb, shaped to the glass and the sand level
+/// and in the layer's colour. The profile is sampled again when the shape
+/// changes and every frame while morphing or changing shape.
+fn draw_sand_layers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    layers: Res<SandLayers>,
+    config: Res<HourglassConfig>,
+    preview: Res<ShapePreview>,
+    library: Res<ShapeLibrary>,
+    placement: Res<HourglassPlacement>,
+    hourglass: Query<
+        (Entity, Ref<HourglassMeshSandState>, Has<ShapeTransition>),
+        With<MainHourglass>,
+    >,
+    bands: Query<(
+        Entity,
+        &SandLayerBand,
+        &Mesh2d,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+    mut profile: Local<Option<(HourglassShape, ShapeProfile)>>,
+) {
+    if layers.0.is_empty() {
+        for (band, ..) in &bands {
+            commands.entity(band).despawn();
+        }
+        return;
+    }
+    let Ok((entity, sand_state, transitioning)) = hourglass.single() else {
+        return;
+    };
+
+    let (shape, shape_mode) = preview.shape(&config);
+    let resample = shape_mode == ShapeMode::Morphing
+        || transitioning
+        || library.is_changed()
+        || profile.as_ref().is_none_or(|(last, _)| last != shape);
+    if resample {
+        *profile = Some((
+            shape.clone(),
+            ShapeProfile::new(
+                &sand_state.body_config,
+                &HourglassMeshPlatesConfig::default(),
+            ),
+        ));
+    }
+    let Some((_, profile)) = profile.as_ref() else {
+        return;
+    };
+    let fill = sand_state.fill_percent;
+    let spans: Vec<_> = layers.spans().collect();
+
+    if bands.iter().count() != spans.len() * 2 {
+        for (band, ..) in &bands {
+            commands.entity(band).despawn();
+        }
+        for (layer, (start, end, color)) in spans.into_iter().enumerate() {
+            for top in [true, false] {
+                let (low, high) = band_heights(profile, start, end, fill, top);
+                let mut mesh = sand_band_mesh();
+                profile.write_sand_band(&mut mesh, low, high);
+                commands.spawn((
+                    SandLayerBand { layer, top },
+                    Mesh2d(meshes.add(mesh)),
+                    MeshMaterial2d(materials.add(color)),
+                    Transform::from_xyz(0.0, 0.0, BAND_Z),
+                    // Vertices move with the sand, so bounds would go stale.
+                    NoFrustumCulling,
+                    placement.render_layers.clone(),
+                    ChildOf(entity),
+                ));
+            }
+        }
+        return;
+    }
+
+    if !resample && !sand_state.is_changed() && !layers.is_changed() {
+        return;
+    }
+    for (_, band, mesh, material) in &bands {
+        let Some(&(start, end, color)) = spans.get(band.layer) else {
+            continue;
+        };
+        if let Some(mesh) = meshes.get_mut(&mesh.0) {
+            let (low, high) = band_heights(profile, start, end, fill, band.top);
+            profile.write_sand_band(mesh, low, high);
+        }
+        if materials.get(&material.0).is_some_and(|m| m.color != color)
+            && let Some(material) = materials.get_mut(&material.0)
+        {
+            material.color = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HourglassTimerPlugin;
+    use crate::resources::{SandLayer, TimerState};
+    use approx::assert_abs_diff_eq;
+    use bevy::asset::AssetPlugin;
+    use bevy::input::InputPlugin;
+    use bevy::render::mesh::{MeshPlugin, VertexAttributeValues};
+    use bevy_hourglass::{Hourglass, SandSplash};
+
+    const WORK: Color = Color::srgb(0.8, 0.2, 0.2);
+    const REST: Color = Color::srgb(0.2, 0.6, 0.9);
+
+    fn work_and_rest() -> SandLayers {
+        SandLayers(vec![
+            SandLayer {
+                share: 3.0,
+                color: WORK,
+            },
+            SandLayer {
+                share: 1.0,
+                color: REST,
+            },
+        ])
+    }
+
+    fn classic() -> ShapeProfile {
+        let (body, plates) = ShapeLibrary::default()
+            .get(&HourglassShape::CLASSIC)
+            .main_configs();
+        ShapeProfile::new(&body, &plates)
+    }
+
+    #[test]
+    fn layers_drain_from_the_top_and_pile_from_the_bottom() {
+        let profile = classic();
+        let (work, rest) = ((0.0, 0.75), (0.75, 1.0));
+
+        // Full: the first layer sits on top, and the lower bulb is empty.
+        let (low, high) = band_heights(&profile, work.0, work.1, 1.0, true);
+        assert_abs_diff_eq!(low, profile.top_fill_line(0.25));
+        assert_abs_diff_eq!(high, profile.top_fill_line(1.0));
+        let (low, high) = band_heights(&profile, rest.0, rest.1, 1.0, true);
+        assert_abs_diff_eq!(low, profile.top_fill_line(0.0));
+        assert_abs_diff_eq!(high, profile.top_fill_line(0.25));
+        let (low, high) = band_heights(&profile, work.0, work.1, 1.0, false);
+        assert_eq!(low, high);
+
+        // Half drained: two thirds of the first layer are at the bottom.
+        let (low, high) = band_heights(&profile, work.0, work.1, 0.5, true);
+        assert_abs_diff_eq!(high, profile.top_fill_line(0.5));
+        assert!(high > low);
+        let (low, high) = band_heights(&profile, work.0, work.1, 0.5, false);
+        assert_abs_diff_eq!(low, profile.bottom_fill_line(1.0));
+        assert_abs_diff_eq!(high, profile.bottom_fill_line(0.5));
+        let (low, high) = band_heights(&profile, rest.0, rest.1, 0.5, false);
+        assert_eq!(low, high);
+
+        // Into the last layer: the first is gone from the top.
+        let (low, high) = band_heights(&profile, work.0, work.1, 0.1, true);
+        assert_eq!(low, high);
+        let (low, high) = band_heights(&profile, rest.0, rest.1, 0.1, false);
+        assert_abs_diff_eq!(low, profile.bottom_fill_line(0.25));
+        assert_abs_diff_eq!(high, profile.bottom_fill_line(0.1));
+    }
+
+    fn layered_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            MeshPlugin,
+            TransformPlugin,
+        ))
+        .init_asset::<ColorMaterial>()
+        .insert_resource(TimerState {
+            duration: 400.0,
+            remaining: 400.0,
+            is_running: false,
+        })
+        .insert_resource(work_and_rest())
+        .add_plugins(HourglassTimerPlugin {
+            ui: false,
+            ..default()
+        });
+        app.update();
+        app.update();
+        app
+    }
+
+    fn band_colors(app: &mut App) -> Vec<(usize, bool, Color)> {
+        let world = app.world_mut();
+        let mut bands: Vec<_> = world
+            .query::<(&SandLayerBand, &MeshMaterial2d<ColorMaterial>)>()
+            .iter(world)
+            .map(|(band, material)| (band.layer, band.top, material.0.clone()))
+            .collect();
+        bands.sort_by_key(|(layer, top, _)| (*layer, !*top));
+        let materials = world.resource::<Assets<ColorMaterial>>();
+        bands
+            .into_iter()
+            .map(|(layer, top, material)| (layer, top, materials.get(&material).unwrap().color))
+            .collect()
+    }
+
+    fn highest_top_band_vertex(app: &mut App, layer: usize) -> f32 {
+        let world = app.world_mut();
+        let mesh = world
+            .query::<(&SandLayerBand, &Mesh2d)>()
+            .iter(world)
+            .find(|(band, _)| band.layer == layer && band.top)
+            .map(|(_, mesh)| mesh.0.clone())
+            .unwrap();
+        match world
+            .resource::<Assets<Mesh>>()
+            .get(&mesh)
+            .unwrap()
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+        {
+            Some(VertexAttributeValues::Float32x3(positions)) => {
+                positions.iter().map(|p| p[1]).fold(f32::MIN, f32::max)
+            }
+            _ => panic!("band has no positions"),
+        }
+    }
+
+    #[test]
+    fn bands_follow_the_layers_and_the_sand() {
+        let mut app = layered_app();
+        assert_eq!(
+            band_colors(&mut app),
+            [
+                (0, true, WORK),
+                (0, false, WORK),
+                (1, true, REST),
+                (1, false, REST)
+            ]
+        );
+        let full = highest_top_band_vertex(&mut app, 0);
+
+        app.world_mut().resource_mut::<TimerState>().remaining = 200.0;
+        app.update();
+        app.update();
+        assert!(highest_top_band_vertex(&mut app, 0) < full);
+
+        app.insert_resource(SandLayers::default());
+        app.update();
+        assert!(band_colors(&mut app).is_empty());
+    }
+
+    #[test]
+    fn stream_and_splash_take_the_draining_layer_colour() {
+        let mut app = layered_app();
+        let colors = |app: &mut App| {
+            let world = app.world_mut();
+            world
+                .query_filtered::<(&Hourglass, &SandSplash), With<MainHourglass>>()
+                .iter(world)
+                .map(|(hourglass, splash)| (hourglass.sand_color, splash.config.particle_color))
+                .next()
+                .unwrap()
+        };
+        assert_eq!(colors(&mut app), (WORK, WORK));
+
+        app.world_mut().resource_mut::<TimerState>().remaining = 50.0;
+        app.update();
+        app.update();
+        assert_eq!(colors(&mut app), (REST, REST));
+
+        app.insert_resource(SandLayers::default());
+        app.update();
+        let sand = app.world().resource::<HourglassConfig>().color;
+        assert_eq!(colors(&mut app), (sand, sand));
+    }
+}
@@ -0,0 +1,190 @@
+//! A visual hourglass countdown timer for Bevy.
+//!
+//! The `hourglass-timer` binary is a thin wrapper around this crate. To embed
+//! the timer in another app, add [`HourglassTimerPlugin`] after
+//! `DefaultPlugins` and tag the camera that renders it with
+//! [`HourglassCamera`]. The timer is driven through [`TimerCommand`] events
+//! and reports every change with [`TimerStateChanged`]; systems that read or
+//! steer it should be ordered against [`TimerSystems`].
+
+// Support configuring Bevy lints within code.
+#![cfg_attr(bevy_lint, feature(register_tool), register_tool(bevy))]
+
+mod cascade;
+mod celebration;
+#[cfg(feature = "chrome_extension")]
+pub mod chrome_extension;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
+#[cfg(feature = "config_file")]
+pub mod config_file;
+mod glass;
+mod graduations;
+pub mod hourglass;
+mod layers;
+mod morph_mesh;
+#[cfg(all(feature = "persistence", not(target_arch = "wasm32")))]
+pub mod persistence;
+pub mod power;
+pub mod presets;
+pub mod resources;
+pub mod shapes;
+#[cfg(feature = "serde")]
+mod snapshot;
+pub mod timer;
+pub mod ui;
+mod urgency;
+mod view_3d;
+
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+
+pub use hourglass::{HourglassCamera, MainHourglass};
+pub use resources::{
+    AppearanceStateChanged, ColorMode, HourglassConfig, HourglassShape, ShapeMode, TimerState,
+    ViewMode,
+};
+pub use timer::{TimerCommand, TimerStateChanged, TimerSystems};
+
+/// The hourglass, its timer and (optionally) its controls, without a window
+/// or camera of its own.
+///
+/// `HourglassConfig` and `TimerState` are only initialised if the host has
+/// not inserted them already, so they can be seeded before the plugin is
+/// added.
+#[derive(Debug, Clone)]
+pub struct HourglassTimerPlugin {
+    /// Spawn the control panels, command palette, keyboard shortcuts and
+    /// toasts. Without them the timer is driven purely by `TimerCommand`s.
+    pub ui: bool,
+    /// Parent the main hourglass under this entity instead of the world root,
+    /// so the host can position and scale it inside its own scene.
+    pub root: Option<Entity>,
+    /// Render layers given to the hourglass meshes and sand particles. The
+    /// host's `HourglassCamera` must render these layers.
+    pub render_layers: RenderLayers,
+}
+
+impl Default for HourglassTimerPlugin {
+    fn default() -> Self {
+        Self {
+            ui: true,
+            root: None,
+            render_layers: RenderLayers::default(),
+        }
+    }
+}
+
+impl Plugin for HourglassTimerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(hourglass::HourglassPlacement {
+            root: self.root,
+            render_layers: self.render_layers.clone(),
+        })
+        .init_resource::<HourglassConfig>()
+        .init_resource::<TimerState>()
+        .register_type::<HourglassConfig>()
+        .register_type::<TimerState>()
+        .add_event::<AppearanceStateChanged>()
+        .add_plugins((
+            hourglass::HourglassPlugin,
+            celebration::CelebrationPlugin,
+            urgency::UrgencyPlugin,
+            graduations::GraduationsPlugin,
+            cascade::CascadePlugin,
+            layers::SandLayersPlugin,
+            glass::GlassPlugin,
+            view_3d::View3dPlugin,
+            timer::TimerPlugin,
+            presets::PresetsPlugin,
+            shapes::ShapesPlugin,
+        ));
+
+        if self.ui {
+            app.add_plugins(ui::UIPlugin);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::asset::AssetPlugin;
+    use bevy::input::InputPlugin;
+    use bevy::render::mesh::MeshPlugin;
+    use bevy_hourglass::HourglassMesh;
+
+    fn base_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            MeshPlugin,
+            TransformPlugin,
+        ))
+        .init_asset::<ColorMaterial>();
+        app
+    }
+
+    #[test]
+    fn headless_plugin_drives_the_timer_through_commands() {
+        let mut app = base_app();
+        app.add_plugins(HourglassTimerPlugin {
+            ui: false,
+            ..default()
+        });
+        app.update();
+        app.world_mut().send_event(TimerCommand::SetDuration(90.0));
+        app.world_mut().send_event(TimerCommand::Start);
+        app.update();
+
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!(timer.duration, 90.0);
+        assert!(timer.is_running);
+        let changes: Vec<_> = app
+            .world()
+            .resource::<Events<TimerStateChanged>>()
+            .iter_current_update_events()
+            .map(|changed| changed.0)
+            .collect();
+        assert_eq!(
+            changes,
+            [TimerCommand::SetDuration(90.0), TimerCommand::Start]
+        );
+    }
+
+    #[test]
+    fn hourglass_spawns_under_the_root_on_the_configured_layers() {
+        let mut app = base_app();
+        let root = app.world_mut().spawn(Transform::default()).id();
+        app.add_plugins(HourglassTimerPlugin {
+            ui: false,
+            root: Some(root),
+            render_layers: RenderLayers::layer(3),
+        });
+        app.update();
+
+        let world = app.world_mut();
+        let (hourglass, parent) = world
+            .query_filtered::<(Entity, &ChildOf), With<MainHourglass>>()
+            .single(world)
+            .unwrap();
+        assert_eq!(parent.parent(), root);
+
+        let children: Vec<Entity> = world
+            .query::<&Children>()
+            .get(world, hourglass)
+            .unwrap()
+            .iter()
+            .collect();
+        assert!(!children.is_empty());
+        for entity in std::iter::once(hourglass).chain(children) {
+            assert_eq!(
+                world.get::<RenderLayers>(entity),
+                Some(&RenderLayers::layer(3))
+            );
+        }
+        assert_eq!(world.query::<&HourglassMesh>().iter(world).count(), 1);
+    }
+}
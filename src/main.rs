@@ -1,17 +1,18 @@
 // Support configuring Bevy lints within code.
 #![cfg_attr(bevy_lint, feature(register_tool), register_tool(bevy))]
 
-#[cfg(feature = "chrome_extension")]
-mod chrome_extension;
-mod hourglass;
-pub mod resources;
-mod timer;
-mod ui;
-
 use bevy::prelude::*;
-use resources::{HourglassConfig, TimerState};
+#[cfg(all(feature = "chrome_extension", target_arch = "wasm32"))]
+use hourglass_timer::chrome_extension;
+#[cfg(not(target_arch = "wasm32"))]
+use hourglass_timer::cli;
+use hourglass_timer::{HourglassCamera, HourglassTimerPlugin};
 
 fn main() -> AppExit {
+    // Exits with a usage error before any window opens if a flag is invalid.
+    #[cfg(not(target_arch = "wasm32"))]
+    let cli = <cli::Cli as clap::Parser>::parse();
+
     #[cfg(all(feature = "chrome_extension", target_arch = "wasm32"))]
     chrome_extension::report_startup_stage("Rust module started…");
 
@@ -20,6 +21,9 @@ fn main() -> AppExit {
     #[cfg(all(feature = "chrome_extension", target_arch = "wasm32"))]
     chrome_extension::report_startup_stage("Configuring Bevy…");
 
+    #[cfg(not(target_arch = "wasm32"))]
+    cli.insert_into(&mut app);
+
     app.add_plugins(AppPlugin);
 
     #[cfg(all(feature = "chrome_extension", target_arch = "wasm32"))]
@@ -28,34 +32,38 @@ fn main() -> AppExit {
     app.run()
 }
 
+/// The standalone app: owns the window and camera, and adds the app-level
+/// features (persistence, config file, extension bridge, power saving) around
+/// `HourglassTimerPlugin`.
 pub struct AppPlugin;
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
+        #[allow(unused_mut)]
+        let mut primary_window = Window {
+            title: "Hourglass Timer".to_string(),
+            #[cfg(feature = "chrome_extension")]
+            canvas: Some("#hourglass-canvas".to_string()),
+            fit_canvas_to_parent: true,
+            ..default()
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(options) = app.world().get_resource::<cli::WindowOptions>() {
+            options.apply(&mut primary_window);
+        }
+
         // Add Bevy plugins.
-        app.add_plugins(
-            DefaultPlugins.set(WindowPlugin {
-                primary_window: Window {
-                    title: "Hourglass Timer".to_string(),
-                    #[cfg(feature = "chrome_extension")]
-                    canvas: Some("#hourglass-canvas".to_string()),
-                    fit_canvas_to_parent: true,
-                    ..default()
-                }
-                .into(),
-                ..default()
-            }),
-        );
+        app.add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(primary_window),
+            ..default()
+        }));
 
         #[cfg(all(feature = "chrome_extension", target_arch = "wasm32"))]
         chrome_extension::report_startup_stage("Bevy platform configured…");
 
-        // Initialize resources
-        app.init_resource::<HourglassConfig>()
-            .init_resource::<TimerState>();
-
-        // Add our custom plugins
-        app.add_plugins((hourglass::HourglassPlugin, timer::TimerPlugin, ui::UIPlugin));
+        // Add the timer itself (its resources are kept if the command line
+        // already provided them)
+        app.add_plugins(HourglassTimerPlugin::default());
 
         #[cfg(all(feature = "chrome_extension", target_arch = "wasm32"))]
         chrome_extension::report_startup_stage("Hourglass systems configured…");
@@ -63,11 +71,20 @@ impl Plugin for AppPlugin {
         #[cfg(all(feature = "chrome_extension", target_arch = "wasm32"))]
         app.add_plugins(chrome_extension::ChromeExtensionPlugin);
 
+        #[cfg(all(feature = "persistence", not(target_arch = "wasm32")))]
+        app.add_plugins(hourglass_timer::persistence::PersistencePlugin);
+
+        #[cfg(feature = "config_file")]
+        app.add_plugins(hourglass_timer::config_file::ConfigFilePlugin);
+
+        // Sleep between visible changes instead of redrawing every frame.
+        app.add_plugins(hourglass_timer::power::PowerSavingPlugin);
+
         // Spawn the main camera.
         app.add_systems(Startup, spawn_camera);
     }
 }
 
 fn spawn_camera(mut commands: Commands) {
-    commands.spawn((Name::new("Camera"), Camera2d));
+    commands.spawn((Name::new("Camera"), Camera2d, HourglassCamera));
 }
@@ -0,0 +1,578 @@
+//! Meshes for morphing mode. Every keyframe shape is sampled once into the
+//! same rows of half-widths, so the glass and sand meshes keep one vertex
+//! layout whatever the shape and a morph frame only blends vertex positions
+//! into the existing `Mesh` assets.
+
+use bevy::math::FloatExt;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::view::NoFrustumCulling;
+use bevy::sprite::AlphaMode2d;
+use bevy_hourglass::{HourglassMeshBodyConfig, HourglassMeshPlatesConfig, HourglassShapeBuilder};
+use std::f32::consts::TAU;
+
+/// Rows the glass outline is sampled at, from the top plate to the bottom one.
+const GLASS_ROWS: usize = 64;
+/// Rows across the sand in each bulb.
+const SAND_ROWS: usize = 24;
+/// Gap between the glass and the sand, as on the main hourglass.
+pub(crate) const SAND_WALL_OFFSET: f32 = 4.0;
+/// Narrowest the sand gets at the neck, as in `bevy_hourglass`.
+const MIN_SAND_HALF_WIDTH: f32 = 0.5;
+/// Steps around the axis of the turned meshes of the 3D view.
+const LATHE_SEGMENTS: usize = 48;
+
+/// One shape sampled into the layout shared by every morph keyframe.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ShapeProfile {
+    pub total_height: f32,
+    pub neck_height: f32,
+    /// Half-width of the glass at each row, top to bottom.
+    glass: Vec<f32>,
+    /// Half-width of the space the sand fills at each row.
+    cavity: Vec<f32>,
+    pub plate_size: Vec2,
+    pub glass_color: Color,
+    pub plate_color: Color,
+}
+
+impl ShapeProfile {
+    pub(crate) fn new(body: &HourglassMeshBodyConfig, plates: &HourglassMeshPlatesConfig) -> Self {
+        let shape = HourglassShapeBuilder {
+            total_height: body.total_height,
+            bulb_style: body.bulb_style.clone(),
+            neck_style: body.neck_style.clone(),
+        };
+        let rows = |outline: &[[f32; 2]], inset: f32, minimum: f32| {
+            (0..=GLASS_ROWS)
+                .map(|row| {
+                    let y = row_y(body.total_height, row);
+                    (half_width(outline, y) - inset).max(minimum)
+                })
+                .collect()
+        };
+        let cavity_outline = shape.generate_outline_with_wall_offset(SAND_WALL_OFFSET);
+        Self {
+            total_height: body.total_height,
+            neck_height: body.neck_style.height(),
+            glass: rows(&shape.generate_outline(), 0.0, 0.0),
+            cavity: rows(&cavity_outline, SAND_WALL_OFFSET, MIN_SAND_HALF_WIDTH),
+            plate_size: Vec2::new(plates.width, plates.height),
+            glass_color: body.color,
+            plate_color: plates.color,
+        }
+    }
+
+    /// Blend `from` towards `to`, reusing this profile's buffers.
+    pub(crate) fn blend(&mut self, from: &Self, to: &Self, t: f32) {
+        let lerp_rows = |rows: &mut Vec<f32>, a: &[f32], b: &[f32]| {
+            rows.clear();
+            rows.extend(a.iter().zip(b).map(|(a, b)| a.lerp(*b, t)));
+        };
+        self.total_height = from.total_height.lerp(to.total_height, t);
+        self.neck_height = from.neck_height.lerp(to.neck_height, t);
+        lerp_rows(&mut self.glass, &from.glass, &to.glass);
+        lerp_rows(&mut self.cavity, &from.cavity, &to.cavity);
+        self.plate_size = from.plate_size.lerp(to.plate_size, t);
+        self.glass_color = from.glass_color.mix(&to.glass_color, t);
+        self.plate_color = from.plate_color.mix(&to.plate_color, t);
+    }
+
+    /// Half-width of the sand space at height `y`, between sampled rows.
+    pub(crate) fn cavity_at(&self, y: f32) -> f32 {
+        self.row_at(&self.cavity, y)
+    }
+
+    /// Half-width of the glass at height `y`, between sampled rows.
+    pub(crate) fn glass_at(&self, y: f32) -> f32 {
+        self.row_at(&self.glass, y)
+    }
+
+    fn row_at(&self, rows: &[f32], y: f32) -> f32 {
+        if rows.is_empty() || self.total_height <= 0.0 {
+            return 0.0;
+        }
+        let row = ((0.5 - y / self.total_height) * GLASS_ROWS as f32).clamp(0.0, GLASS_ROWS as f32);
+        let below = (row.floor() as usize).min(GLASS_ROWS - 1);
+        rows[below].lerp(rows[below + 1], row - below as f32)
+    }
+
+    pub(crate) fn write_glass(&self, mesh: &mut Mesh) {
+        let rows = self.glass.iter().enumerate();
+        write_strip(
+            mesh,
+            rows.map(|(row, width)| (row_y(self.total_height, row), *width)),
+        );
+    }
+
+    /// Sand left in the top bulb at `fill` (1 full, 0 empty), with the stream
+    /// falling through the neck while any is left.
+    pub(crate) fn write_top_sand(&self, mesh: &mut Mesh, fill: f32) {
+        let fill_line = self.top_fill_line(fill);
+        let rows = (0..=SAND_ROWS).map(|row| fill_line * row as f32 / SAND_ROWS as f32);
+        let stream = if fill > 0.0 { self.cavity_at(0.0) } else { 0.0 };
+        let bottom = -self.total_height / 2.0;
+        write_strip(
+            mesh,
+            rows.map(|y| (y, self.cavity_at(y)))
+                .chain([(0.0, stream), (bottom, stream)]),
+        );
+    }
+
+    /// Sand piled in the bottom bulb when the top one holds `fill`.
+    pub(crate) fn write_bottom_sand(&self, mesh: &mut Mesh, fill: f32) {
+        self.write_sand_band(mesh, -self.total_height / 2.0, self.bottom_fill_line(fill));
+    }
+
+    /// Sand filling the glass between heights `low` and `high`, into a mesh
+    /// from `sand_band_mesh`.
+    pub(crate) fn write_sand_band(&self, mesh: &mut Mesh, low: f32, high: f32) {
+        let rows = (0..=SAND_ROWS).map(|row| low + (high - low) * row as f32 / SAND_ROWS as f32);
+        write_strip(mesh, rows.map(|y| (y, self.cavity_at(y))));
+    }
+
+    /// The glass turned about its axis, into a mesh from `glass_lathe_mesh`.
+    pub(crate) fn write_glass_lathe(&self, mesh: &mut Mesh) {
+        let rows = self.glass.iter().enumerate();
+        write_lathe(
+            mesh,
+            rows.map(|(row, width)| (row_y(self.total_height, row), *width)),
+        );
+    }
+
+    /// Sand filling the turned glass between heights `low` and `high`, closed
+    /// at both ends, into a mesh from `sand_volume_mesh`.
+    pub(crate) fn write_sand_volume(&self, mesh: &mut Mesh, low: f32, high: f32) {
+        let rows = (0..=SAND_ROWS).map(|row| high + (low - high) * row as f32 / SAND_ROWS as f32);
+        write_lathe(
+            mesh,
+            std::iter::once((high, 0.0))
+                .chain(rows.map(|y| (y, self.cavity_at(y))))
+                .chain([(low, 0.0)]),
+        );
+    }
+
+    /// Height of the sand surface in the top bulb at `fill`. The sand level
+    /// is linear in `fill`, as in `bevy_hourglass`, rising from the middle
+    /// to the top of the glass.
+    pub(crate) fn top_fill_line(&self, fill: f32) -> f32 {
+        fill.clamp(0.0, 1.0) * self.total_height / 2.0
+    }
+
+    /// Height of the pile in the bottom bulb when the top one holds `fill`,
+    /// rising from the bottom of the glass to just below the neck.
+    pub(crate) fn bottom_fill_line(&self, fill: f32) -> f32 {
+        let bottom = -self.total_height / 2.0;
+        bottom + (1.0 - fill.clamp(0.0, 1.0)) * (-self.neck_height / 2.0 - bottom)
+    }
+
+    /// Where the unit-square plate mesh sits, above (`side` 1) or below
+    /// (`side` -1) the glass.
+    pub(crate) fn plate_transform(&self, side: f32) -> Transform {
+        let y = side * (self.total_height + self.plate_size.y) / 2.0;
+        Transform::from_xyz(0.0, y, 0.0).with_scale(self.plate_size.extend(1.0))
+    }
+}
+
+/// Height of glass row `row` on a hourglass `total_height` tall.
+fn row_y(total_height: f32, row: usize) -> f32 {
+    total_height * (0.5 - row as f32 / GLASS_ROWS as f32)
+}
+
+/// Widest point of `outline` along the horizontal line at `y`.
+fn half_width(outline: &[[f32; 2]], y: f32) -> f32 {
+    let mut widest = 0.0_f32;
+    for (index, start) in outline.iter().enumerate() {
+        let end = outline[(index + 1) % outline.len()];
+        let (low, high) = if start[1] <= end[1] {
+            (*start, end)
+        } else {
+            (end, *start)
+        };
+        if y < low[1] || y > high[1] {
+            continue;
+        }
+        let x = if high[1] - low[1] > f32::EPSILON {
+            low[0]
+                .lerp(high[0], (y - low[1]) / (high[1] - low[1]))
+                .abs()
+        } else {
+            low[0].abs().max(high[0].abs())
+        };
+        widest = widest.max(x);
+    }
+    widest
+}
+
+/// Overwrite a strip mesh's positions with a left/right pair per
+/// `(y, half_width)` row.
+fn write_strip(mesh: &mut Mesh, rows: impl Iterator<Item = (f32, f32)>) {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return;
+    };
+    for (pair, (y, width)) in positions.chunks_exact_mut(2).zip(rows) {
+        pair[0] = [-width, y, 0.0];
+        pair[1] = [width, y, 0.0];
+    }
+}
+
+/// Overwrite a lathe mesh's positions and normals with a ring per
+/// `(y, radius)` row, listed from the top down.
+fn write_lathe(mesh: &mut Mesh, rows: impl Iterator<Item = (f32, f32)>) {
+    let rows: Vec<Vec2> = rows.map(|(y, radius)| Vec2::new(radius, y)).collect();
+    // Outward normals of the profile, from the rows either side of each one.
+    let profile_normals: Vec<Vec2> = (0..rows.len())
+        .map(|row| {
+            let along = rows[(row + 1).min(rows.len() - 1)] - rows[row.saturating_sub(1)];
+            Vec2::new(-along.y, along.x)
+                .try_normalize()
+                .unwrap_or(Vec2::X)
+        })
+        .collect();
+    let turn = |profile: &[Vec2], out: &mut [[f32; 3]]| {
+        for (ring, point) in out.chunks_exact_mut(LATHE_SEGMENTS + 1).zip(profile) {
+            for (segment, vertex) in ring.iter_mut().enumerate() {
+                let (sin, cos) = (segment as f32 * TAU / LATHE_SEGMENTS as f32).sin_cos();
+                *vertex = [point.x * sin, point.y, point.x * cos];
+            }
+        }
+    };
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        turn(&rows, positions);
+    }
+    if let Some(VertexAttributeValues::Float32x3(normals)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+    {
+        turn(&profile_normals, normals);
+    }
+}
+
+/// A mesh of `rows` left/right vertex pairs with a quad between each pair of
+/// consecutive rows. Rows listed in `breaks` start a new strip instead of
+/// joining the row before.
+fn strip_mesh(rows: usize, breaks: &[usize]) -> Mesh {
+    let vertices = rows * 2;
+    let indices = (1..rows)
+        .filter(|row| !breaks.contains(row))
+        .flat_map(|row| {
+            let (a, b, c, d) = (2 * row - 2, 2 * row - 1, 2 * row, 2 * row + 1);
+            [a, b, d, a, d, c].map(|index| index as u32)
+        })
+        .collect();
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]; vertices])
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; vertices])
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; vertices])
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// The glass strip, with `u` running across each row from 0 at the left wall
+/// to 1 at the right and `v` from 0 at the top to 1 at the bottom.
+pub(crate) fn glass_mesh() -> Mesh {
+    let uvs: Vec<[f32; 2]> = (0..=GLASS_ROWS)
+        .flat_map(|row| {
+            let v = row as f32 / GLASS_ROWS as f32;
+            [[0.0, v], [1.0, v]]
+        })
+        .collect();
+    strip_mesh(GLASS_ROWS + 1, &[]).with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+}
+
+/// A surface of `rings` rings turned about the vertical axis, with `u`
+/// running around it and `v` down the profile. The first and last column
+/// share positions so the texture coordinates can wrap.
+fn lathe_mesh(rings: usize) -> Mesh {
+    let columns = LATHE_SEGMENTS + 1;
+    let indices = (1..rings)
+        .flat_map(|ring| {
+            (0..LATHE_SEGMENTS).flat_map(move |segment| {
+                let (a, c) = ((ring - 1) * columns + segment, ring * columns + segment);
+                [a, c, a + 1, a + 1, c, c + 1].map(|index| index as u32)
+            })
+        })
+        .collect();
+    let uvs: Vec<[f32; 2]> = (0..rings)
+        .flat_map(|ring| {
+            let v = ring as f32 / (rings - 1).max(1) as f32;
+            (0..columns).map(move |column| [column as f32 / LATHE_SEGMENTS as f32, v])
+        })
+        .collect();
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]; rings * columns])
+    .with_inserted_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        vec![[0.0, 0.0, 1.0]; rings * columns],
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+pub(crate) fn glass_lathe_mesh() -> Mesh {
+    lathe_mesh(GLASS_ROWS + 1)
+}
+
+pub(crate) fn sand_volume_mesh() -> Mesh {
+    // A ring at the axis closes each end.
+    lathe_mesh(SAND_ROWS + 3)
+}
+
+fn top_sand_mesh() -> Mesh {
+    // The stream is a separate two-row strip after the pile.
+    strip_mesh(SAND_ROWS + 3, &[SAND_ROWS + 1])
+}
+
+fn bottom_sand_mesh() -> Mesh {
+    sand_band_mesh()
+}
+
+pub(crate) fn sand_band_mesh() -> Mesh {
+    strip_mesh(SAND_ROWS + 1, &[])
+}
+
+/// The morph keyframes, sampled once per sequence, and the blended frame.
+#[derive(Debug, Default)]
+pub(crate) struct MorphKeyframes {
+    shapes: Vec<ShapeProfile>,
+    blended: ShapeProfile,
+}
+
+impl MorphKeyframes {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub(crate) fn set(&mut self, shapes: impl IntoIterator<Item = ShapeProfile>) {
+        self.shapes = shapes.into_iter().collect();
+    }
+
+    /// Blend keyframe `from` towards keyframe `to`.
+    pub(crate) fn blend(&mut self, from: usize, to: usize, t: f32) -> &ShapeProfile {
+        self.blended.blend(&self.shapes[from], &self.shapes[to], t);
+        &self.blended
+    }
+}
+
+/// Marks the glass, plates and sand spawned for morphing mode.
+#[derive(Component)]
+pub(crate) struct MorphPart;
+
+/// The parts of a morphing hourglass, whose vertices, transforms and colours
+/// are rewritten in place every morph frame.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct MorphMeshes {
+    pub glass: Handle<Mesh>,
+    pub top_sand: Handle<Mesh>,
+    pub bottom_sand: Handle<Mesh>,
+    pub glass_material: Handle<ColorMaterial>,
+    pub plate_material: Handle<ColorMaterial>,
+    pub sand_material: Handle<ColorMaterial>,
+    /// Top and bottom plates.
+    pub plates: [Entity; 2],
+}
+
+impl MorphMeshes {
+    /// Spawn the morphing parts under `hourglass`, drawn at `profile` with the
+    /// top bulb holding `fill`.
+    pub(crate) fn spawn(
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<ColorMaterial>,
+        hourglass: Entity,
+        profile: &ShapeProfile,
+        fill: f32,
+        sand_color: Color,
+    ) -> Self {
+        let mut glass = glass_mesh();
+        profile.write_glass(&mut glass);
+        let mut top_sand = top_sand_mesh();
+        profile.write_top_sand(&mut top_sand, fill);
+        let mut bottom_sand = bottom_sand_mesh();
+        profile.write_bottom_sand(&mut bottom_sand, fill);
+
+        let glass = meshes.add(glass);
+        let top_sand = meshes.add(top_sand);
+        let bottom_sand = meshes.add(bottom_sand);
+        let plate = meshes.add(Rectangle::new(1.0, 1.0));
+        let glass_material = materials.add(ColorMaterial {
+            color: profile.glass_color,
+            alpha_mode: AlphaMode2d::Blend,
+            ..default()
+        });
+        let plate_material = materials.add(profile.plate_color);
+        let sand_material = materials.add(sand_color);
+
+        // Vertices move every frame, so bounds computed once would go stale.
+        let part = |mesh: &Handle<Mesh>, material: &Handle<ColorMaterial>, transform| {
+            (
+                MorphPart,
+                Mesh2d(mesh.clone()),
+                MeshMaterial2d(material.clone()),
+                transform,
+                NoFrustumCulling,
+            )
+        };
+        let sand_transform = Transform::from_xyz(0.0, 0.0, 0.1);
+        let mut plates = [Entity::PLACEHOLDER; 2];
+        commands.entity(hourglass).with_children(|parent| {
+            parent.spawn(part(&glass, &glass_material, Transform::default()));
+            for (entity, side) in plates.iter_mut().zip([1.0, -1.0]) {
+                let transform = profile.plate_transform(side);
+                *entity = parent.spawn(part(&plate, &plate_material, transform)).id();
+            }
+            parent.spawn(part(&top_sand, &sand_material, sand_transform));
+            parent.spawn(part(&bottom_sand, &sand_material, sand_transform));
+        });
+
+        Self {
+            glass,
+            top_sand,
+            bottom_sand,
+            glass_material,
+            plate_material,
+            sand_material,
+            plates,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::HourglassShape;
+    use crate::shapes::ShapeLibrary;
+    use approx::assert_abs_diff_eq;
+
+    fn profile(shape: &HourglassShape) -> ShapeProfile {
+        let (body, plates) = ShapeLibrary::default().get(shape).main_configs();
+        ShapeProfile::new(&body, &plates)
+    }
+
+    fn positions(mesh: &Mesh) -> &[[f32; 3]] {
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            _ => panic!("mesh has no positions"),
+        }
+    }
+
+    #[test]
+    fn every_shape_samples_to_the_same_layout() {
+        let profiles: Vec<_> = HourglassShape::BUILTIN.iter().map(profile).collect();
+        for profile in &profiles {
+            assert_eq!(profile.glass.len(), GLASS_ROWS + 1);
+            assert_eq!(profile.cavity.len(), GLASS_ROWS + 1);
+            // Widest at the bulbs, narrowest at the neck.
+            let neck = profile.glass[GLASS_ROWS / 2];
+            assert!(profile.glass[0] > neck && profile.glass[GLASS_ROWS] > neck);
+            assert!(
+                profile
+                    .cavity
+                    .iter()
+                    .zip(&profile.glass)
+                    .all(|(c, g)| c <= g)
+            );
+        }
+        assert_ne!(profiles[0], profiles[2]);
+    }
+
+    #[test]
+    fn half_width_follows_the_outline() {
+        let diamond = [[0.0, 10.0], [4.0, 0.0], [0.0, -10.0], [-4.0, 0.0]];
+        assert_abs_diff_eq!(half_width(&diamond, 0.0), 4.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(half_width(&diamond, 5.0), 2.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(half_width(&diamond, 11.0), 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn blending_reaches_each_keyframe_at_the_ends() {
+        let (classic, wide) = (
+            profile(&HourglassShape::CLASSIC),
+            profile(&HourglassShape::WIDE),
+        );
+        let mut blended = ShapeProfile::default();
+        blended.blend(&classic, &wide, 0.0);
+        assert_eq!(blended, classic);
+        blended.blend(&classic, &wide, 1.0);
+        assert_eq!(blended.glass, wide.glass);
+        assert_abs_diff_eq!(blended.total_height, wide.total_height, epsilon = 1e-4);
+        blended.blend(&classic, &wide, 0.5);
+        let middle = (classic.glass[0] + wide.glass[0]) / 2.0;
+        assert_abs_diff_eq!(blended.glass[0], middle, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn sand_meshes_track_the_fill_level() {
+        let profile = profile(&HourglassShape::CLASSIC);
+        let half = profile.total_height / 2.0;
+        let mut top = top_sand_mesh();
+        let mut bottom = bottom_sand_mesh();
+
+        profile.write_top_sand(&mut top, 1.0);
+        profile.write_bottom_sand(&mut bottom, 1.0);
+        let highest = positions(&top)
+            .iter()
+            .map(|p| p[1])
+            .fold(f32::MIN, f32::max);
+        assert_abs_diff_eq!(highest, half, epsilon = 1e-4);
+        // A full top bulb leaves a flat, empty pile at the bottom.
+        assert!(positions(&bottom).iter().all(|p| p[1] == -half));
+
+        profile.write_top_sand(&mut top, 0.0);
+        profile.write_bottom_sand(&mut bottom, 0.0);
+        assert!(positions(&top).iter().all(|p| p[1] == 0.0 || p[0] == 0.0));
+        let pile = positions(&bottom)
+            .iter()
+            .map(|p| p[1])
+            .fold(f32::MIN, f32::max);
+        assert_abs_diff_eq!(pile, -profile.neck_height / 2.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn turned_sand_is_round_and_closed_at_both_ends() {
+        let profile = profile(&HourglassShape::CLASSIC);
+        let mut sand = sand_volume_mesh();
+        let (low, high) = (-profile.total_height / 2.0, -50.0);
+        profile.write_sand_volume(&mut sand, low, high);
+
+        let columns = LATHE_SEGMENTS + 1;
+        let rings: Vec<_> = positions(&sand).chunks_exact(columns).collect();
+        assert_eq!(rings.len(), SAND_ROWS + 3);
+        // The end rings sit on the axis, and every other one is a circle as
+        // wide as the space inside the glass.
+        for point in rings[0].iter().chain(rings[rings.len() - 1]) {
+            assert_abs_diff_eq!(point[0], 0.0, epsilon = 1e-4);
+            assert_abs_diff_eq!(point[2], 0.0, epsilon = 1e-4);
+        }
+        let surface = rings[1][0][1];
+        assert_abs_diff_eq!(surface, high, epsilon = 1e-4);
+        for point in rings[1] {
+            let radius = Vec2::new(point[0], point[2]).length();
+            assert_abs_diff_eq!(radius, profile.cavity_at(surface), epsilon = 1e-3);
+        }
+
+        // The ends face up and down, the sides away from the axis.
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            sand.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("mesh has no normals");
+        };
+        assert_abs_diff_eq!(normals[0][1], 1.0, epsilon = 1e-4);
+        assert_abs_diff_eq!(normals[normals.len() - 1][1], -1.0, epsilon = 1e-4);
+        let side = normals[columns * (SAND_ROWS / 2 + 1)];
+        assert!(side[2] > 0.0 && side[2] > side[1].abs());
+    }
+}
@@ -0,0 +1,571 @@
+//! Native persistence: keeps the timer and appearance across restarts by
+//! writing the shared `SnapshotV1` format to the user's state directory, and
+//! keeps the saved presets, the edited palette and the shapes saved from the
+//! shape editor in files next to it.
+
+use crate::presets::TimerPresets;
+use crate::presets::stored::{presets_from_json, presets_to_json};
+use crate::resources::stored_colors::{colors_from_json, colors_to_json};
+use crate::resources::{
+    AppearanceStateChanged, ColorPalette, HourglassConfig, LaunchOverrides, RecentColors,
+    TimerState,
+};
+use crate::shapes::stored_shapes::{shapes_from_json, shapes_to_json};
+use crate::shapes::{ShapeDefinition, ShapeLibrary};
+use crate::snapshot::{SnapshotV1, apply_snapshot, snapshot_from_resources};
+use crate::timer::{TimerStateChanged, TimerSystems, now_ms};
+use bevy::prelude::*;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE_NAME: &str = "state-v1.json";
+const PRESETS_FILE_NAME: &str = "presets-v1.json";
+const COLORS_FILE_NAME: &str = "colors-v1.json";
+const SHAPES_FILE_NAME: &str = "shapes-v1.json";
+
+pub struct PersistencePlugin;
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<StateFilePath>() {
+            app.insert_resource(StateFilePath(
+                default_state_dir().map(|dir| dir.join(STATE_FILE_NAME)),
+            ));
+        }
+        app.init_resource::<TimerPresets>()
+            .init_resource::<ColorPalette>()
+            .init_resource::<RecentColors>()
+            .init_resource::<ShapeLibrary>()
+            .register_type::<StateFilePath>()
+            .add_systems(
+                Update,
+                (
+                    restore_persisted_state.run_if(run_once),
+                    restore_presets.run_if(run_once),
+                    restore_colors.run_if(run_once),
+                    restore_shapes.run_if(run_once),
+                )
+                    .in_set(TimerSystems::Restore),
+            )
+            .add_systems(
+                Update,
+                (
+                    persist_state,
+                    persist_presets,
+                    persist_colors,
+                    persist_shapes,
+                )
+                    .in_set(TimerSystems::Observe),
+            );
+    }
+}
+
+/// Where the snapshot is read from and written to; presets, colours and
+/// shapes are stored in the same directory. `None` disables persistence, e.g. when no home directory
+/// can be found.
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct StateFilePath(pub Option<PathBuf>);
+
+/// `$XDG_STATE_HOME/hourglass-timer`, falling back to the platform's usual
+/// per-user application data directory.
+fn default_state_dir() -> Option<PathBuf> {
+    let from_env = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+
+    let base = if let Some(state_home) = from_env("XDG_STATE_HOME") {
+        PathBuf::from(state_home)
+    } else if cfg!(target_os = "windows") {
+        PathBuf::from(from_env("LOCALAPPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(from_env("HOME")?).join("Library/Application Support")
+    } else {
+        PathBuf::from(from_env("HOME")?).join(".local/state")
+    };
+    Some(base.join("hourglass-timer"))
+}
+
+/// Restore the last session, then put back the flags given on the command
+/// line so they win over the saved fields they name.
+fn restore_persisted_state(
+    path: Res<StateFilePath>,
+    launch_overrides: Option<Res<LaunchOverrides>>,
+    mut timer_state: ResMut<TimerState>,
+    mut config: ResMut<HourglassConfig>,
+) {
+    let Some(path) = path.0.as_deref() else {
+        return;
+    };
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!("Could not read saved state {}: {error}", path.display());
+            return;
+        }
+    };
+    match serde_json::from_str::<SnapshotV1>(&json) {
+        Ok(snapshot) => {
+            // The native countdown takes a fresh deadline from whatever
+            // remains, so only the resolved remaining time matters here.
+            let mut deadline = None;
+            if !apply_snapshot(
+                snapshot,
+                now_ms(),
+                &mut timer_state,
+                &mut deadline,
+                &mut config,
+            ) {
+                warn!("Ignoring saved state with unsupported version");
+            }
+            if let Some(overrides) = launch_overrides {
+                overrides.apply_to_timer(&mut timer_state);
+                overrides.apply_to_config(&mut config);
+            }
+        }
+        Err(error) => warn!("Ignoring invalid saved state: {error}"),
+    }
+}
+
+fn persist_state(
+    mut timer_changes: EventReader<TimerStateChanged>,
+    mut appearance_changes: EventReader<AppearanceStateChanged>,
+    path: Res<StateFilePath>,
+    timer_state: Res<TimerState>,
+    config: Res<HourglassConfig>,
+) {
+    let timer_dirty = timer_changes.read().count() > 0;
+    let appearance_dirty = appearance_changes.read().count() > 0;
+    if !timer_dirty && !appearance_dirty {
+        return;
+    }
+    let Some(path) = path.0.as_deref() else {
+        return;
+    };
+
+    // Store an absolute deadline for running timers so time spent closed is
+    // subtracted on the next launch.
+    let deadline_ms = (timer_state.is_running && timer_state.remaining > 0.0)
+        .then(|| now_ms() + f64::from(timer_state.remaining) * 1000.0);
+    let snapshot = snapshot_from_resources(&timer_state, deadline_ms, &config);
+    if let Err(error) = write_snapshot(path, &snapshot) {
+        warn!("Could not save state to {}: {error}", path.display());
+    }
+}
+
+fn presets_path(state_path: &Path) -> PathBuf {
+    state_path.with_file_name(PRESETS_FILE_NAME)
+}
+
+fn restore_presets(path: Res<StateFilePath>, mut presets: ResMut<TimerPresets>) {
+    let Some(path) = path.0.as_deref().map(presets_path) else {
+        return;
+    };
+    let json = match fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!("Could not read saved presets {}: {error}", path.display());
+            return;
+        }
+    };
+    match presets_from_json(&json) {
+        Ok(restored) => *presets = restored,
+        Err(error) => warn!("Ignoring invalid saved presets: {error}"),
+    }
+}
+
+/// Write the presets whenever they differ from what was last loaded or saved.
+fn persist_presets(
+    path: Res<StateFilePath>,
+    presets: Res<TimerPresets>,
+    mut saved: Local<Option<TimerPresets>>,
+) {
+    let Some(saved) = saved.as_mut() else {
+        *saved = Some(presets.clone());
+        return;
+    };
+    if !presets.is_changed() || *presets == *saved {
+        return;
+    }
+    *saved = presets.clone();
+    let Some(path) = path.0.as_deref().map(presets_path) else {
+        return;
+    };
+    let result = presets_to_json(&presets)
+        .map_err(io::Error::other)
+        .and_then(|json| write_atomically(&path, &json));
+    if let Err(error) = result {
+        warn!("Could not save presets to {}: {error}", path.display());
+    }
+}
+
+pub(crate) fn colors_path(state_path: &Path) -> PathBuf {
+    state_path.with_file_name(COLORS_FILE_NAME)
+}
+
+pub(crate) fn restore_colors(
+    path: Res<StateFilePath>,
+    mut palette: ResMut<ColorPalette>,
+    mut recent: ResMut<RecentColors>,
+) {
+    let Some(path) = path.0.as_deref().map(colors_path) else {
+        return;
+    };
+    let json = match fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!("Could not read saved colours {}: {error}", path.display());
+            return;
+        }
+    };
+    match colors_from_json(&json) {
+        Ok((restored_palette, restored_recent)) => {
+            *palette = restored_palette;
+            *recent = restored_recent;
+        }
+        Err(error) => warn!("Ignoring invalid saved colours: {error}"),
+    }
+}
+
+/// Write the palette and recent colours whenever either differs from what
+/// was last loaded or saved.
+fn persist_colors(
+    path: Res<StateFilePath>,
+    palette: Res<ColorPalette>,
+    recent: Res<RecentColors>,
+    mut saved: Local<Option<(ColorPalette, RecentColors)>>,
+) {
+    let Some(saved) = saved.as_mut() else {
+        *saved = Some((palette.clone(), recent.clone()));
+        return;
+    };
+    if !palette.is_changed() && !recent.is_changed() {
+        return;
+    }
+    if saved.0 == *palette && saved.1 == *recent {
+        return;
+    }
+    *saved = (palette.clone(), recent.clone());
+    let Some(path) = path.0.as_deref().map(colors_path) else {
+        return;
+    };
+    let result = colors_to_json(&palette, &recent)
+        .map_err(io::Error::other)
+        .and_then(|json| write_atomically(&path, &json));
+    if let Err(error) = result {
+        warn!("Could not save colours to {}: {error}", path.display());
+    }
+}
+
+fn shapes_path(state_path: &Path) -> PathBuf {
+    state_path.with_file_name(SHAPES_FILE_NAME)
+}
+
+fn restore_shapes(path: Res<StateFilePath>, mut library: ResMut<ShapeLibrary>) {
+    let Some(path) = path.0.as_deref().map(shapes_path) else {
+        return;
+    };
+    let json = match fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+        Err(error) => {
+            warn!("Could not read saved shapes {}: {error}", path.display());
+            return;
+        }
+    };
+    match shapes_from_json(&json) {
+        Ok(shapes) => library.set_saved(shapes),
+        Err(error) => warn!("Ignoring invalid saved shapes: {error}"),
+    }
+}
+
+/// Write the saved shapes whenever they differ from what was last loaded or
+/// saved. Dragging the editor's sliders only changes the draft, so it never
+/// writes.
+fn persist_shapes(
+    path: Res<StateFilePath>,
+    library: Res<ShapeLibrary>,
+    mut saved: Local<Option<Vec<ShapeDefinition>>>,
+) {
+    let Some(saved) = saved.as_mut() else {
+        *saved = Some(library.saved().to_vec());
+        return;
+    };
+    if !library.is_changed() || saved.as_slice() == library.saved() {
+        return;
+    }
+    *saved = library.saved().to_vec();
+    let Some(path) = path.0.as_deref().map(shapes_path) else {
+        return;
+    };
+    let result = shapes_to_json(library.saved())
+        .map_err(io::Error::other)
+        .and_then(|json| write_atomically(&path, &json));
+    if let Err(error) = result {
+        warn!("Could not save shapes to {}: {error}", path.display());
+    }
+}
+
+fn write_snapshot(path: &Path, snapshot: &SnapshotV1) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(snapshot).map_err(io::Error::other)?;
+    write_atomically(path, &json)
+}
+
+/// Write via a temporary sibling and rename, so a crash mid-write never
+/// leaves a truncated file behind.
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::TimerPreset;
+    use crate::resources::{HourglassShape, ShapeMode};
+    use crate::timer::TimerCommand;
+
+    fn temp_state_path(test_name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "hourglass-timer-test-{}-{test_name}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join(STATE_FILE_NAME)
+    }
+
+    fn persistence_app(path: &Path) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, crate::timer::TimerPlugin))
+            .add_event::<AppearanceStateChanged>()
+            .init_resource::<TimerState>()
+            .init_resource::<HourglassConfig>()
+            .insert_resource(StateFilePath(Some(path.to_path_buf())))
+            .add_plugins(PersistencePlugin);
+        app
+    }
+
+    #[test]
+    fn state_survives_a_restart() {
+        let path = temp_state_path("restart");
+
+        let mut first = persistence_app(&path);
+        first.update();
+        first
+            .world_mut()
+            .resource_mut::<HourglassConfig>()
+            .shape_type = HourglassShape::SLIM;
+        first
+            .world_mut()
+            .resource_mut::<HourglassConfig>()
+            .shape_mode = ShapeMode::Morphing;
+        first
+            .world_mut()
+            .send_event(TimerCommand::SetDuration(1500.0));
+        first.update();
+        assert!(path.exists());
+
+        let mut second = persistence_app(&path);
+        second.update();
+        let timer = second.world().resource::<TimerState>();
+        assert_eq!(timer.duration, 1500.0);
+        assert_eq!(timer.remaining, 1500.0);
+        let config = second.world().resource::<HourglassConfig>();
+        assert_eq!(config.shape_type, HourglassShape::SLIM);
+        assert_eq!(config.shape_mode, ShapeMode::Morphing);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn presets_survive_a_restart() {
+        let path = temp_state_path("presets");
+
+        let mut first = persistence_app(&path);
+        first.update();
+        assert!(!presets_path(&path).exists());
+        first
+            .world_mut()
+            .resource_mut::<TimerPresets>()
+            .save(TimerPreset {
+                name: "Pasta".to_string(),
+                duration: 540.0,
+                appearance: HourglassConfig {
+                    shape_type: HourglassShape::WIDE,
+                    ..default()
+                },
+            });
+        first.update();
+
+        let mut second = persistence_app(&path);
+        second.update();
+        let presets = second.world().resource::<TimerPresets>();
+        assert_eq!(presets, first.world().resource::<TimerPresets>());
+        assert_eq!(
+            presets.get("pasta").unwrap().appearance.shape_type,
+            HourglassShape::WIDE
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn edited_palette_and_recent_colors_survive_a_restart() {
+        let path = temp_state_path("colors");
+
+        let mut first = persistence_app(&path);
+        first.update();
+        assert!(!colors_path(&path).exists());
+        let teal = Color::srgb(0.0, 0.5, 0.5);
+        let mut palette = first.world_mut().resource_mut::<ColorPalette>();
+        palette.add(teal);
+        let added = palette.0.len() - 1;
+        palette.move_swatch(added, 0);
+        first
+            .world_mut()
+            .resource_mut::<RecentColors>()
+            .record(Color::srgb(0.9, 0.1, 0.5));
+        first.update();
+
+        let mut second = persistence_app(&path);
+        second.update();
+        assert_eq!(second.world().resource::<ColorPalette>().0[0].1, teal);
+        assert_eq!(
+            second.world().resource::<RecentColors>(),
+            first.world().resource::<RecentColors>()
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn saved_shapes_survive_a_restart_but_drafts_do_not() {
+        let path = temp_state_path("shapes");
+
+        let mut first = persistence_app(&path);
+        first.update();
+        let mut vase = ShapeDefinition::builtin()[0].clone();
+        vase.name = "vase".to_string();
+        let mut draft = vase.clone();
+        draft.name = "draft".to_string();
+        let mut library = first.world_mut().resource_mut::<ShapeLibrary>();
+        library.set_draft(Some(draft));
+        first.update();
+        assert!(!shapes_path(&path).exists());
+        first
+            .world_mut()
+            .resource_mut::<ShapeLibrary>()
+            .save(vase.clone());
+        first.update();
+
+        let mut second = persistence_app(&path);
+        second.update();
+        let library = second.world().resource::<ShapeLibrary>();
+        assert_eq!(library.saved(), [vase]);
+        assert!(
+            library
+                .find(&HourglassShape::from_name("vase").unwrap())
+                .is_some()
+        );
+        assert!(
+            library
+                .find(&HourglassShape::from_name("draft").unwrap())
+                .is_none()
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn running_timer_resumes_against_its_deadline() {
+        let path = temp_state_path("deadline");
+        let timer = TimerState {
+            duration: 600.0,
+            remaining: 600.0,
+            is_running: true,
+        };
+        // Saved 100s before the deadline, as if the app closed 500s into a run.
+        let deadline_ms = now_ms() + 100_000.0;
+        write_snapshot(
+            &path,
+            &snapshot_from_resources(&timer, Some(deadline_ms), &HourglassConfig::default()),
+        )
+        .unwrap();
+
+        let mut app = persistence_app(&path);
+        app.update();
+        let restored = app.world().resource::<TimerState>();
+        assert!(restored.is_running);
+        assert!(restored.remaining <= 100.0 && restored.remaining > 95.0);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn missing_or_corrupt_state_keeps_defaults() {
+        let path = temp_state_path("corrupt");
+        let mut app = persistence_app(&path);
+        app.update();
+        assert_eq!(*app.world().resource::<TimerState>(), TimerState::default());
+
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+        let mut app = persistence_app(&path);
+        app.update();
+        assert_eq!(*app.world().resource::<TimerState>(), TimerState::default());
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn command_line_flags_apply_on_top_of_the_restored_state() {
+        let path = temp_state_path("overrides");
+        let saved = TimerState {
+            duration: 60.0,
+            remaining: 45.0,
+            is_running: false,
+        };
+        let appearance = HourglassConfig {
+            shape_type: HourglassShape::SLIM,
+            ..default()
+        };
+        write_snapshot(&path, &snapshot_from_resources(&saved, None, &appearance)).unwrap();
+
+        let mut app = persistence_app(&path);
+        app.insert_resource(LaunchOverrides {
+            start: true,
+            ..default()
+        });
+        app.update();
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!(timer.duration, 60.0);
+        assert!(timer.remaining <= 45.0 && timer.remaining > 44.0);
+        assert!(timer.is_running);
+        assert_eq!(
+            app.world().resource::<HourglassConfig>().shape_type,
+            HourglassShape::SLIM
+        );
+
+        let mut app = persistence_app(&path);
+        app.insert_resource(LaunchOverrides {
+            duration: Some(1500.0),
+            shape: Some(HourglassShape::WIDE),
+            ..default()
+        });
+        app.update();
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!((timer.duration, timer.remaining), (1500.0, 1500.0));
+        assert_eq!(
+            app.world().resource::<HourglassConfig>().shape_type,
+            HourglassShape::WIDE
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
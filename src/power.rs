@@ -0,0 +1,264 @@
+//! Power saving for apps that own their window: while nothing on screen
+//! moves, winit stops updating until input arrives or the next visible change
+//! (a sand step, the countdown text, the deadline) is due.
+
+use crate::celebration::FinishCelebration;
+use crate::hourglass::{
+    HourglassCamera, MainHourglass, ShapeTransition, main_hourglass_hit_radius,
+};
+use crate::resources::{
+    CascadeSettings, ColorMode, HourglassConfig, PendingFlip, ShapeMode, ShapePreview, TimerState,
+};
+use crate::urgency::Urgency;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy::winit::{UpdateMode, WinitSettings};
+use bevy_hourglass::{Hourglass, HourglassMeshSandState};
+use std::time::Duration;
+
+/// How long an idle app sleeps when no input arrives.
+const IDLE_WAIT: Duration = Duration::from_secs(30);
+/// The longest gap between updates while the timer runs. The countdown text
+/// changes once a second, so slow sand never waits longer than that.
+const MAX_RUNNING_WAIT: f32 = 1.0;
+/// Fast sand is paced at most at 60 updates a second.
+const MIN_RUNNING_WAIT: f32 = 1.0 / 60.0;
+
+/// Switches `WinitSettings` between continuous and reactive updates from what
+/// is animating. The standalone app adds it; hosts that embed
+/// `HourglassTimerPlugin` own their window and can opt in.
+pub struct PowerSavingPlugin;
+
+impl Plugin for PowerSavingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WinitSettings>()
+            .add_systems(Last, update_winit_settings);
+
+        // Updates can now be a whole idle wait apart, so let virtual time
+        // cover that gap in one step; otherwise toasts and splash particles
+        // would outlive their wall-clock lifetimes.
+        if let Some(mut time) = app.world_mut().get_resource_mut::<Time<Virtual>>() {
+            time.set_max_delta(IDLE_WAIT);
+        }
+    }
+}
+
+fn update_winit_settings(
+    config: Res<HourglassConfig>,
+    timer_state: Res<TimerState>,
+    pending_flip: Res<PendingFlip>,
+    celebration: Option<Res<FinishCelebration>>,
+    urgency: Option<Res<Urgency>>,
+    shape_preview: Option<Res<ShapePreview>>,
+    cascade: Option<Res<CascadeSettings>>,
+    hourglasses: Query<&Hourglass>,
+    shape_transitions: Query<(), With<ShapeTransition>>,
+    main_hourglass: Query<(&GlobalTransform, &HourglassMeshSandState), With<MainHourglass>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<HourglassCamera>>,
+    interactions: Query<&Interaction>,
+    mut settings: ResMut<WinitSettings>,
+) {
+    let pulse = urgency.as_ref().map_or(1.0, |urgency| urgency.pulse);
+    let hovering = interactions
+        .iter()
+        .any(|interaction| *interaction == Interaction::Hovered)
+        || over_main_hourglass(&window, &camera, &main_hourglass, pulse);
+    let animating = pending_flip.0
+        || celebration.is_some_and(|celebration| celebration.is_playing())
+        || urgency.is_some_and(|urgency| urgency.is_pulsing())
+        || config.color_mode == ColorMode::Rainbow
+        || shape_preview.map_or(config.shape_mode, |preview| preview.shape(&config).1)
+            == ShapeMode::Morphing
+        || !shape_transitions.is_empty()
+        || hourglasses.iter().any(|hourglass| hourglass.flipping)
+        || hovering;
+    let sand_rows = main_hourglass
+        .iter()
+        .map(|(transform, sand)| sand_rows(transform, sand))
+        .fold(0.0, f32::max);
+
+    // A cascading countdown runs one segment at a time through the glass.
+    let glass_duration = cascade
+        .and_then(|cascade| cascade.segment(&timer_state))
+        .map_or(timer_state.duration, |segment| segment.duration);
+
+    let mode = update_mode(animating, &timer_state, glass_duration, sand_rows);
+    if settings.focused_mode != mode || settings.unfocused_mode != mode {
+        settings.focused_mode = mode;
+        settings.unfocused_mode = mode;
+    }
+}
+
+/// Whether the cursor is within the main hourglass's click target, measured at
+/// its resting size like the click itself. A cursor parked over empty canvas
+/// does not count, so a small always-visible timer can still sleep.
+fn over_main_hourglass(
+    window: &Query<&Window, With<PrimaryWindow>>,
+    camera: &Query<(&Camera, &GlobalTransform), With<HourglassCamera>>,
+    main_hourglass: &Query<(&GlobalTransform, &HourglassMeshSandState), With<MainHourglass>>,
+    pulse: f32,
+) -> bool {
+    let (Ok(window), Ok((camera, camera_transform)), Ok((transform, _))) =
+        (window.single(), camera.single(), main_hourglass.single())
+    else {
+        return false;
+    };
+    window
+        .cursor_position()
+        .and_then(|position| camera.viewport_to_world_2d(camera_transform, position).ok())
+        .is_some_and(|position| {
+            position.distance(transform.translation().truncate())
+                < main_hourglass_hit_radius(transform.scale().x / pulse)
+        })
+}
+
+/// Roughly how many pixel rows one bulb's sand spans on screen. World units
+/// are logical pixels under the default 2D camera.
+fn sand_rows(transform: &GlobalTransform, sand: &HourglassMeshSandState) -> f32 {
+    sand.body_config.total_height / 2.0 * transform.scale().y.abs()
+}
+
+/// Continuous while something animates; otherwise wake for the next visible
+/// sand step of a `glass_duration` glass while the timer runs, and only for
+/// input when it does not. A running timer also wakes at least once a second
+/// and exactly at the deadline.
+fn update_mode(
+    animating: bool,
+    timer_state: &TimerState,
+    glass_duration: f32,
+    sand_rows: f32,
+) -> UpdateMode {
+    if animating {
+        return UpdateMode::Continuous;
+    }
+    if !timer_state.is_running || timer_state.remaining <= 0.0 {
+        return UpdateMode::reactive_low_power(IDLE_WAIT);
+    }
+
+    let per_row = glass_duration / sand_rows.max(1.0);
+    let wait = per_row
+        .clamp(MIN_RUNNING_WAIT, MAX_RUNNING_WAIT)
+        .min(timer_state.remaining);
+    UpdateMode::reactive_low_power(Duration::from_secs_f32(wait))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn running(duration: f32, remaining: f32) -> TimerState {
+        TimerState {
+            duration,
+            remaining,
+            is_running: true,
+        }
+    }
+
+    fn wait(mode: UpdateMode) -> f32 {
+        match mode {
+            UpdateMode::Reactive { wait, .. } => wait.as_secs_f32(),
+            UpdateMode::Continuous => panic!("expected a reactive update mode"),
+        }
+    }
+
+    #[test]
+    fn idle_timer_sleeps_until_input() {
+        let mode = update_mode(false, &TimerState::default(), 0.0, 100.0);
+        assert_eq!(mode, UpdateMode::reactive_low_power(IDLE_WAIT));
+    }
+
+    #[test]
+    fn animation_keeps_updating_continuously() {
+        assert_eq!(
+            update_mode(true, &TimerState::default(), 0.0, 100.0),
+            UpdateMode::Continuous
+        );
+    }
+
+    #[test]
+    fn running_timer_ticks_for_each_visible_sand_step() {
+        // 25 minutes over 100 rows moves a row every 15 s; the text still
+        // needs a tick every second.
+        assert_relative_eq!(
+            wait(update_mode(false, &running(1500.0, 900.0), 1500.0, 100.0)),
+            1.0
+        );
+        // 10 s over 100 rows moves a row every 0.1 s.
+        assert_relative_eq!(
+            wait(update_mode(false, &running(10.0, 8.0), 10.0, 100.0)),
+            0.1,
+            epsilon = 1e-6
+        );
+        // A one second run over 200 rows is capped at 60 updates a second.
+        assert_relative_eq!(
+            wait(update_mode(false, &running(1.0, 1.0), 1.0, 200.0)),
+            MIN_RUNNING_WAIT,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn running_timer_wakes_at_the_deadline() {
+        assert_relative_eq!(
+            wait(update_mode(false, &running(1500.0, 0.25), 1500.0, 100.0)),
+            0.25,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn rainbow_and_morphing_keep_the_app_awake() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<HourglassConfig>()
+            .init_resource::<TimerState>()
+            .init_resource::<PendingFlip>()
+            .add_plugins(PowerSavingPlugin);
+        let mode = |app: &App| app.world().resource::<WinitSettings>().focused_mode;
+
+        app.update();
+        assert_eq!(mode(&app), UpdateMode::reactive_low_power(IDLE_WAIT));
+
+        app.world_mut().resource_mut::<HourglassConfig>().color_mode = ColorMode::Rainbow;
+        app.update();
+        assert_eq!(mode(&app), UpdateMode::Continuous);
+
+        let mut config = app.world_mut().resource_mut::<HourglassConfig>();
+        config.color_mode = ColorMode::Static;
+        config.shape_mode = ShapeMode::Morphing;
+        app.update();
+        assert_eq!(mode(&app), UpdateMode::Continuous);
+
+        app.world_mut().resource_mut::<HourglassConfig>().shape_mode = ShapeMode::Static;
+        app.update();
+        assert_eq!(mode(&app), UpdateMode::reactive_low_power(IDLE_WAIT));
+    }
+
+    #[test]
+    fn a_cursor_parked_over_empty_canvas_lets_the_app_sleep() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<HourglassConfig>()
+            .init_resource::<TimerState>()
+            .init_resource::<PendingFlip>()
+            .add_plugins(PowerSavingPlugin);
+        let mut window = Window::default();
+        window.set_physical_cursor_position(Some(bevy::math::DVec2::new(10.0, 10.0)));
+        app.world_mut().spawn((window, PrimaryWindow));
+        let mode = |app: &App| app.world().resource::<WinitSettings>().focused_mode;
+
+        app.update();
+        assert_eq!(mode(&app), UpdateMode::reactive_low_power(IDLE_WAIT));
+
+        // Hovering a control keeps its highlight responsive.
+        let button = app.world_mut().spawn(Interaction::Hovered).id();
+        app.update();
+        assert_eq!(mode(&app), UpdateMode::Continuous);
+
+        app.world_mut().despawn(button);
+        app.update();
+        assert_eq!(mode(&app), UpdateMode::reactive_low_power(IDLE_WAIT));
+    }
+}
@@ -0,0 +1,286 @@
+//! Named presets that bundle a countdown length with an appearance, e.g.
+//! "Tea" for a four-minute slim green hourglass.
+
+use crate::resources::{
+    AppearanceStateChanged, ColorMode, HourglassConfig, HourglassShape, PendingFlip, TimerState,
+};
+use crate::timer::{TimerCommand, TimerSystems};
+use crate::ui::extension_appearance_change_command;
+use bevy::prelude::*;
+
+pub struct PresetsPlugin;
+
+impl Plugin for PresetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimerPresets>()
+            .register_type::<TimerPresets>()
+            .add_event::<PresetCommand>()
+            .add_systems(Update, apply_preset_commands.in_set(TimerSystems::Input));
+    }
+}
+
+/// A saved countdown length and look.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TimerPreset {
+    pub name: String,
+    /// Countdown length in seconds.
+    pub duration: f32,
+    pub appearance: HourglassConfig,
+}
+
+/// Every saved preset, in quick-pick order.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct TimerPresets(pub Vec<TimerPreset>);
+
+impl Default for TimerPresets {
+    fn default() -> Self {
+        let preset = |name: &str, minutes: f32, appearance| TimerPreset {
+            name: name.to_string(),
+            duration: minutes * 60.0,
+            appearance,
+        };
+        Self(vec![
+            preset(
+                "Tea",
+                4.0,
+                HourglassConfig {
+                    color: Color::srgb(0.1, 0.5, 0.1),
+                    shape_type: HourglassShape::SLIM,
+                    ..default()
+                },
+            ),
+            preset("Standup", 15.0, HourglassConfig::default()),
+            preset(
+                "Focus",
+                50.0,
+                HourglassConfig {
+                    color_mode: ColorMode::Rainbow,
+                    ..default()
+                },
+            ),
+        ])
+    }
+}
+
+impl TimerPresets {
+    /// Look up a preset by name (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&TimerPreset> {
+        self.0
+            .iter()
+            .find(|preset| preset.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Add `preset`, replacing any existing preset with the same name.
+    pub fn save(&mut self, preset: TimerPreset) {
+        match self
+            .0
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(&preset.name))
+        {
+            Some(existing) => *existing = preset,
+            None => self.0.push(preset),
+        }
+    }
+
+    /// The first of `Preset 1`, `Preset 2`, ... that is not taken yet.
+    pub fn unused_name(&self) -> String {
+        (1..)
+            .map(|index| format!("Preset {index}"))
+            .find(|name| self.get(name).is_none())
+            .unwrap_or_default()
+    }
+}
+
+/// Requests from the quick-pick row and the command palette.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum PresetCommand {
+    /// Load the named preset's duration and appearance.
+    Apply(String),
+    /// Save the current duration and appearance, under a fresh name if none
+    /// is given.
+    SaveCurrent(Option<String>),
+}
+
+/// Presets change the timer through `TimerCommand`s and announce appearance
+/// edits like the colour and shape panels do, so persistence and the
+/// extension bridge see them as ordinary changes.
+fn apply_preset_commands(
+    mut requests: EventReader<PresetCommand>,
+    mut presets: ResMut<TimerPresets>,
+    mut config: ResMut<HourglassConfig>,
+    timer_state: Res<TimerState>,
+    mut pending_flip: ResMut<PendingFlip>,
+    mut timer_commands: EventWriter<TimerCommand>,
+    mut appearance_changed: EventWriter<AppearanceStateChanged>,
+) {
+    for request in requests.read() {
+        match request {
+            PresetCommand::Apply(name) => {
+                let Some(preset) = presets.get(name) else {
+                    warn!("No preset named `{name}`");
+                    continue;
+                };
+                timer_commands.write(TimerCommand::SetDuration(preset.duration));
+                if *config != preset.appearance {
+                    *config = preset.appearance.clone();
+                    if let Some(command) = extension_appearance_change_command(&mut pending_flip) {
+                        timer_commands.write(command);
+                    }
+                    appearance_changed.write_default();
+                }
+            }
+            PresetCommand::SaveCurrent(name) => {
+                let name = name
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| presets.unused_name());
+                presets.save(TimerPreset {
+                    name,
+                    duration: timer_state.duration,
+                    appearance: config.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// The stored form shared by the native presets file and the extension's
+/// local storage.
+#[cfg(any(
+    all(feature = "persistence", not(target_arch = "wasm32")),
+    all(feature = "chrome_extension", target_arch = "wasm32")
+))]
+pub(crate) mod stored {
+    use super::{TimerPreset, TimerPresets};
+    use crate::resources::MAX_DURATION_SECS;
+    use bevy::prelude::*;
+
+    /// Version of the stored preset list, bumped on incompatible changes.
+    const PRESETS_VERSION: u8 = 1;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct PresetsV1 {
+        version: u8,
+        presets: Vec<TimerPreset>,
+    }
+
+    pub(crate) fn presets_to_json(presets: &TimerPresets) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&PresetsV1 {
+            version: PRESETS_VERSION,
+            presets: presets.0.clone(),
+        })
+    }
+
+    /// Parse stored presets, dropping entries that cannot be used.
+    pub(crate) fn presets_from_json(json: &str) -> Result<TimerPresets, String> {
+        let stored: PresetsV1 = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        if stored.version != PRESETS_VERSION {
+            return Err(format!("unsupported presets version {}", stored.version));
+        }
+        Ok(TimerPresets(
+            stored.presets.into_iter().filter_map(sanitized).collect(),
+        ))
+    }
+
+    /// Clamp values read from storage into the ranges the timer accepts.
+    fn sanitized(mut preset: TimerPreset) -> Option<TimerPreset> {
+        preset.name = preset.name.trim().to_string();
+        if preset.name.is_empty() || !preset.duration.is_finite() || preset.duration <= 0.0 {
+            return None;
+        }
+        preset.duration = preset.duration.min(MAX_DURATION_SECS);
+        let [red, green, blue, alpha] = preset.appearance.color.to_srgba().to_f32_array();
+        preset.appearance.color = Color::srgba(
+            red.clamp(0.0, 1.0),
+            green.clamp(0.0, 1.0),
+            blue.clamp(0.0, 1.0),
+            alpha.clamp(0.0, 1.0),
+        );
+        Some(preset)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn stored_presets_round_trip_and_drop_unusable_entries() {
+            let presets = TimerPresets::default();
+            let json = presets_to_json(&presets).unwrap();
+            assert_eq!(presets_from_json(&json).unwrap(), presets);
+
+            let json = json
+                .replacen("\"duration\": 240.0", "\"duration\": -1.0", 1)
+                .replacen("\"duration\": 900.0", "\"duration\": 1e9", 1);
+            let restored = presets_from_json(&json).unwrap();
+            assert_eq!(restored.0.len(), 2);
+            assert_eq!(restored.get("standup").unwrap().duration, MAX_DURATION_SECS);
+            assert!(presets_from_json("{\"version\": 7, \"presets\": []}").is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ShapeMode;
+
+    fn presets_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, crate::timer::TimerPlugin, PresetsPlugin))
+            .add_event::<AppearanceStateChanged>()
+            .init_resource::<TimerState>()
+            .init_resource::<HourglassConfig>()
+            .init_resource::<PendingFlip>();
+        app
+    }
+
+    #[test]
+    fn applying_a_preset_sets_duration_and_appearance() {
+        let mut app = presets_app();
+        app.world_mut()
+            .send_event(PresetCommand::Apply("tea".to_string()));
+        app.update();
+
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!(timer.duration, 240.0);
+        assert_eq!(timer.remaining, 240.0);
+        let config = app.world().resource::<HourglassConfig>();
+        assert_eq!(config.shape_type, HourglassShape::SLIM);
+        assert_eq!(config.color, Color::srgb(0.1, 0.5, 0.1));
+        assert_eq!(
+            app.world()
+                .resource::<Events<AppearanceStateChanged>>()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn saving_the_current_setup_adds_or_replaces_a_preset() {
+        let mut app = presets_app();
+        app.world_mut().resource_mut::<HourglassConfig>().shape_mode = ShapeMode::Morphing;
+        app.world_mut().send_event(TimerCommand::SetDuration(600.0));
+        app.update();
+        app.world_mut().send_event(PresetCommand::SaveCurrent(None));
+        app.world_mut()
+            .send_event(PresetCommand::SaveCurrent(Some(" standup ".to_string())));
+        app.update();
+
+        let presets = app.world().resource::<TimerPresets>();
+        assert_eq!(presets.0.len(), 4);
+        let saved = presets.get("Preset 1").unwrap();
+        assert_eq!(saved.duration, 600.0);
+        assert_eq!(saved.appearance.shape_mode, ShapeMode::Morphing);
+        assert_eq!(presets.get("Standup").unwrap().duration, 600.0);
+        assert_eq!(presets.unused_name(), "Preset 2");
+    }
+}
@@ -1,51 +1,677 @@
 use bevy::prelude::*;
+use std::borrow::Cow;
 
 pub const SAND_COLOR: Color = Color::srgb(0.8, 0.6, 0.2);
 
+/// Longest countdown the timer accepts, in seconds (24 hours).
+pub const MAX_DURATION_SECS: f32 = 3600.0 * 24.0;
+
 /// Resource to track the current hourglass configuration
-#[derive(Resource, Debug, Clone)]
+///
+/// Its serde form is the `appearance` object of the persisted snapshot, so the
+/// field names match what the extension's JavaScript expects.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
 pub struct HourglassConfig {
+    #[cfg_attr(feature = "serde", serde(rename = "colorRgba", with = "color_rgba"))]
     pub color: Color,
+    #[cfg_attr(feature = "serde", serde(rename = "shape"))]
     pub shape_type: HourglassShape,
     pub color_mode: ColorMode,
     pub shape_mode: ShapeMode,
+    /// Sand colours for `ColorMode::Progress`.
+    pub gradient: ColorGradient,
+    pub view: ViewMode,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub enum ColorMode {
     Static,
     Random,
     Rainbow,
+    /// Follow `HourglassConfig::gradient` as the countdown runs.
+    Progress,
+}
+
+impl ColorMode {
+    pub const ALL: [ColorMode; 4] = [
+        ColorMode::Static,
+        ColorMode::Random,
+        ColorMode::Rainbow,
+        ColorMode::Progress,
+    ];
+
+    /// Lowercase name used by text-driven controls.
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorMode::Static => "static",
+            ColorMode::Random => "random",
+            ColorMode::Rainbow => "rainbow",
+            ColorMode::Progress => "progress",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name.trim()))
+    }
 }
 
 impl Default for HourglassConfig {
     fn default() -> Self {
         Self {
             color: SAND_COLOR,
-            shape_type: HourglassShape::Classic,
+            shape_type: HourglassShape::CLASSIC,
             color_mode: ColorMode::Static,
             shape_mode: ShapeMode::Static,
+            gradient: ColorGradient::default(),
+            view: ViewMode::TwoD,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum HourglassShape {
-    Classic,
-    Modern,
-    Slim,
-    Wide,
-    // Add more shapes as needed
+/// Most stops a gradient keeps; extra stops from a snapshot are dropped.
+pub const MAX_GRADIENT_STOPS: usize = 16;
+
+/// Sand colours keyed to how much of the countdown is left. Colours between
+/// two stops are blended in Oklab, so the midpoints stay as bright as the
+/// stops instead of turning muddy like a raw sRGB blend.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ColorGradient {
+    pub key: GradientKey,
+    pub stops: Vec<GradientStop>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct GradientStop {
+    /// Remaining fraction (1 at the start, 0 at the deadline) or remaining
+    /// seconds, depending on the gradient's key.
+    pub at: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "colorRgba", with = "color_rgba"))]
+    pub color: Color,
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum GradientKey {
+    /// Stops are fractions of the duration left.
+    Fraction,
+    /// Stops are seconds left, whatever the duration.
+    Seconds,
+}
+
+impl Default for ColorGradient {
+    /// Green while there is plenty of time, amber at half way, red at the end.
+    fn default() -> Self {
+        let stop = |at, color| GradientStop { at, color };
+        Self {
+            key: GradientKey::Fraction,
+            stops: vec![
+                stop(1.0, Color::srgb(0.2, 0.7, 0.25)),
+                stop(0.5, Color::srgb(1.0, 0.65, 0.0)),
+                stop(0.0, Color::srgb(0.85, 0.15, 0.15)),
+            ],
+        }
+    }
+}
+
+impl ColorGradient {
+    /// Where `timer_state` sits along the gradient's key.
+    pub fn position(&self, timer_state: &TimerState) -> f32 {
+        match self.key {
+            GradientKey::Fraction if timer_state.duration > 0.0 => {
+                timer_state.remaining / timer_state.duration
+            }
+            GradientKey::Fraction => 0.0,
+            GradientKey::Seconds => timer_state.remaining,
+        }
+    }
+
+    pub fn color_at(&self, timer_state: &TimerState) -> Color {
+        self.sample(self.position(timer_state))
+    }
+
+    /// The colour at `position`, holding the end stops' colours beyond them.
+    pub fn sample(&self, position: f32) -> Color {
+        let below = self
+            .stops
+            .iter()
+            .filter(|stop| stop.at <= position)
+            .max_by(|a, b| a.at.total_cmp(&b.at));
+        let above = self
+            .stops
+            .iter()
+            .filter(|stop| stop.at >= position)
+            .min_by(|a, b| a.at.total_cmp(&b.at));
+        let (low, high) = match (below, above) {
+            (Some(low), Some(high)) => (low, high),
+            (Some(stop), None) | (None, Some(stop)) => (stop, stop),
+            (None, None) => return SAND_COLOR,
+        };
+        let t = if high.at > low.at {
+            (position - low.at) / (high.at - low.at)
+        } else {
+            0.0
+        };
+        let mixed = if t <= 0.0 {
+            low.color.to_srgba()
+        } else {
+            Srgba::from(Oklaba::from(low.color).mix(&Oklaba::from(high.color), t))
+        };
+        Color::srgba(
+            mixed.red.clamp(0.0, 1.0),
+            mixed.green.clamp(0.0, 1.0),
+            mixed.blue.clamp(0.0, 1.0),
+            mixed.alpha.clamp(0.0, 1.0),
+        )
+    }
+
+    /// Drop stops that cannot be placed, clamp the rest and keep at most
+    /// `MAX_GRADIENT_STOPS`, sorted from the most time left to the least. An
+    /// empty result falls back to the default gradient.
+    pub fn sanitized(self) -> Self {
+        let limit = match self.key {
+            GradientKey::Fraction => 1.0,
+            GradientKey::Seconds => MAX_DURATION_SECS,
+        };
+        let mut stops: Vec<GradientStop> = self
+            .stops
+            .into_iter()
+            .filter(|stop| stop.at.is_finite())
+            .take(MAX_GRADIENT_STOPS)
+            .map(|stop| {
+                let [red, green, blue, alpha] = stop.color.to_srgba().to_f32_array();
+                GradientStop {
+                    at: stop.at.clamp(0.0, limit),
+                    color: Color::srgba(
+                        red.clamp(0.0, 1.0),
+                        green.clamp(0.0, 1.0),
+                        blue.clamp(0.0, 1.0),
+                        alpha.clamp(0.0, 1.0),
+                    ),
+                }
+            })
+            .collect();
+        if stops.is_empty() {
+            return Self::default();
+        }
+        stops.sort_by(|a, b| b.at.total_cmp(&a.at));
+        Self {
+            key: self.key,
+            stops,
+        }
+    }
+}
+
+/// Name of an entry in the `ShapeLibrary`. The built-in shapes have
+/// constants; shapes loaded from `assets/shapes` go by the name in their file.
+#[derive(Reflect, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct HourglassShape(Cow<'static, str>);
+
+impl HourglassShape {
+    pub const CLASSIC: HourglassShape = HourglassShape(Cow::Borrowed("classic"));
+    pub const MODERN: HourglassShape = HourglassShape(Cow::Borrowed("modern"));
+    pub const SLIM: HourglassShape = HourglassShape(Cow::Borrowed("slim"));
+    pub const WIDE: HourglassShape = HourglassShape(Cow::Borrowed("wide"));
+
+    /// The shapes that exist even when no shape files are loaded.
+    pub const BUILTIN: [HourglassShape; 4] = [
+        HourglassShape::CLASSIC,
+        HourglassShape::MODERN,
+        HourglassShape::SLIM,
+        HourglassShape::WIDE,
+    ];
+
+    /// Lowercase name used by text-driven controls and shape files.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// Normalise a typed or loaded name. Whether a shape by that name exists
+    /// is up to the `ShapeLibrary`; blank names are rejected here.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            return None;
+        }
+        Some(
+            Self::BUILTIN
+                .into_iter()
+                .find(|shape| shape.name() == name)
+                .unwrap_or(Self(Cow::Owned(name))),
+        )
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub enum ShapeMode {
     Static,
     Morphing,
 }
 
+/// How the main hourglass is drawn. Timing, flipping and clicks behave the
+/// same in either view.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum ViewMode {
+    /// Flat meshes drawn by the `HourglassCamera`.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "2d"))]
+    TwoD,
+    /// A lit glass turned from the same outline, between wooden end caps,
+    /// seen through a camera of its own that can be orbited.
+    #[cfg_attr(feature = "serde", serde(rename = "3d"))]
+    ThreeD,
+}
+
+impl ViewMode {
+    pub const ALL: [ViewMode; 2] = [ViewMode::TwoD, ViewMode::ThreeD];
+
+    /// Lowercase name used by text-driven controls.
+    pub fn name(self) -> &'static str {
+        match self {
+            ViewMode::TwoD => "2d",
+            ViewMode::ThreeD => "3d",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// How morphing mode moves between shapes.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct MorphSettings {
+    /// Shapes to morph through, in order. Empty, or naming no loaded shape,
+    /// means every shape in the library.
+    pub sequence: Vec<HourglassShape>,
+    /// Seconds for one pass through the sequence when following the clock.
+    pub period_secs: f32,
+    pub easing: MorphEasing,
+    pub sync: MorphSync,
+}
+
+impl Default for MorphSettings {
+    fn default() -> Self {
+        Self {
+            sequence: Vec::new(),
+            period_secs: 8.0,
+            easing: MorphEasing::Linear,
+            sync: MorphSync::Clock,
+        }
+    }
+}
+
+impl MorphSettings {
+    /// Where the morph is, from 0 to 1: the share of the period elapsed, or
+    /// of the countdown used up when synced to the timer.
+    pub fn phase(&self, elapsed_secs: f32, timer_state: &TimerState) -> f32 {
+        match self.sync {
+            MorphSync::Clock if self.period_secs > 0.0 => {
+                (elapsed_secs % self.period_secs) / self.period_secs
+            }
+            MorphSync::Clock => 0.0,
+            MorphSync::Progress if timer_state.duration > 0.0 => {
+                (1.0 - timer_state.remaining / timer_state.duration).clamp(0.0, 1.0)
+            }
+            MorphSync::Progress => 0.0,
+        }
+    }
+}
+
+/// Easing applied to each blend between two neighbouring shapes.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MorphEasing {
+    Linear,
+    /// Slow out of one shape and into the next.
+    Smooth,
+    EaseIn,
+    EaseOut,
+}
+
+impl MorphEasing {
+    pub const ALL: [MorphEasing; 4] = [
+        MorphEasing::Linear,
+        MorphEasing::Smooth,
+        MorphEasing::EaseIn,
+        MorphEasing::EaseOut,
+    ];
+
+    /// Lowercase name used by text-driven controls.
+    pub fn name(self) -> &'static str {
+        match self {
+            MorphEasing::Linear => "linear",
+            MorphEasing::Smooth => "smooth",
+            MorphEasing::EaseIn => "ease-in",
+            MorphEasing::EaseOut => "ease-out",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|easing| easing.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Map a blend fraction in 0..=1 onto the curve.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            MorphEasing::Linear => t,
+            MorphEasing::Smooth => t * t * (3.0 - 2.0 * t),
+            MorphEasing::EaseIn => t * t,
+            MorphEasing::EaseOut => t * (2.0 - t),
+        }
+    }
+}
+
+/// What drives the morph.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MorphSync {
+    /// Loop through the sequence every `period_secs`.
+    Clock,
+    /// Go through the sequence once over the countdown, reaching the last
+    /// shape at the deadline.
+    Progress,
+}
+
+impl MorphSync {
+    pub const ALL: [MorphSync; 2] = [MorphSync::Clock, MorphSync::Progress];
+
+    /// Lowercase name used by text-driven controls.
+    pub fn name(self) -> &'static str {
+        match self {
+            MorphSync::Clock => "clock",
+            MorphSync::Progress => "progress",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|sync| sync.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Length of one cascade segment: the main hourglass runs through an hour at
+/// a time.
+pub const CASCADE_SEGMENT_SECS: f32 = 3600.0;
+
+/// Splits long countdowns across several hourglasses, so the sand still
+/// visibly moves on a six hour timer.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct CascadeSettings {
+    /// Durations longer than this cascade; 0 never does.
+    pub threshold_secs: f32,
+}
+
+impl Default for CascadeSettings {
+    fn default() -> Self {
+        Self {
+            threshold_secs: 7200.0,
+        }
+    }
+}
+
+impl CascadeSettings {
+    /// The part of `timer_state` the main hourglass shows when it cascades.
+    /// Any odd part of an hour runs first, so the hours queued behind it are
+    /// always whole.
+    pub fn segment(&self, timer_state: &TimerState) -> Option<CascadeSegment> {
+        if self.threshold_secs <= 0.0 || timer_state.duration <= self.threshold_secs {
+            return None;
+        }
+        let queued = ((timer_state.remaining / CASCADE_SEGMENT_SECS).ceil() - 1.0).max(0.0);
+        let elapsed_hours = queued * CASCADE_SEGMENT_SECS;
+        Some(CascadeSegment {
+            duration: (timer_state.duration - elapsed_hours).min(CASCADE_SEGMENT_SECS),
+            remaining: timer_state.remaining - elapsed_hours,
+            queued: queued as usize,
+            hours: ((timer_state.duration / CASCADE_SEGMENT_SECS).ceil() as usize).max(1) - 1,
+        })
+    }
+}
+
+/// What a cascading countdown shows right now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CascadeSegment {
+    /// Length and time left of the segment in the main hourglass.
+    pub duration: f32,
+    pub remaining: f32,
+    /// Whole hours still waiting in the small hourglasses.
+    pub queued: usize,
+    /// Small hourglasses in the row, full or not.
+    pub hours: usize,
+}
+
+/// Most layers the sand can be split into.
+pub const MAX_SAND_LAYERS: usize = 8;
+
+/// Stripes the sand for countdowns made of segments, such as an agenda or
+/// work and rest phases. Layers are listed in the order they drain; with none
+/// the sand is a single colour.
+#[derive(Resource, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct SandLayers(pub Vec<SandLayer>);
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct SandLayer {
+    /// Size of the layer relative to the others; together they fill the glass.
+    pub share: f32,
+    pub color: Color,
+}
+
+impl SandLayers {
+    /// Each layer's part of the glass as the fractions drained when it starts
+    /// and when it has run through, with its colour.
+    pub fn spans(&self) -> impl Iterator<Item = (f32, f32, Color)> + '_ {
+        let total: f32 = self.0.iter().map(|layer| layer.share.max(0.0)).sum();
+        let mut start = 0.0;
+        self.0.iter().map(move |layer| {
+            let end = if total > 0.0 {
+                (start + layer.share.max(0.0) / total).min(1.0)
+            } else {
+                1.0
+            };
+            let span = (start, end, layer.color);
+            start = end;
+            span
+        })
+    }
+
+    /// Colour of the layer running through the neck once `drained` of the
+    /// glass has emptied, or `None` without layers.
+    pub fn draining(&self, drained: f32) -> Option<Color> {
+        self.spans()
+            .find(|(_, end, _)| drained < *end)
+            .or_else(|| self.spans().last())
+            .map(|(_, _, color)| color)
+    }
+}
+
+/// Tick marks along the glass that show how much time is left when the sand
+/// reaches them.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct GraduationSettings {
+    pub enabled: bool,
+    /// Time between marks. Left unset, a round step that gives a handful of
+    /// marks for the current duration is picked.
+    pub interval_secs: Option<f32>,
+    /// Print the time left beside each mark.
+    pub labels: bool,
+}
+
+impl Default for GraduationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: None,
+            labels: true,
+        }
+    }
+}
+
+/// Shading of the glass: a rim highlight, a specular streak and a tinted
+/// darkening of the sand seen through the curved walls.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct GlassSettings {
+    /// Shade the glass. Off, or where the shader is unsupported, the glass is
+    /// a flat tint.
+    pub shaded: bool,
+    /// Colour the walls cast over the sand behind them.
+    pub tint: Color,
+    /// Strength of the rim highlight and specular streak, from 0 to 1.
+    pub highlight: f32,
+    /// How much the walls darken the sand behind them, from 0 to 1.
+    pub refraction: f32,
+}
+
+impl Default for GlassSettings {
+    fn default() -> Self {
+        Self {
+            shaded: true,
+            tint: Color::srgb_u8(0x33, 0x59, 0x73),
+            highlight: 0.8,
+            refraction: 0.5,
+        }
+    }
+}
+
+/// How the hourglass calls for attention in the final seconds of a run.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct UrgencySettings {
+    /// Seconds before the deadline the effects start; 0 turns them off.
+    pub threshold_secs: f32,
+    /// Strength of the effects at the deadline, from 0 to 1.
+    pub intensity: f32,
+    /// Keep only the colour shift: no pulse and no faster splash.
+    pub reduced_motion: bool,
+}
+
+impl Default for UrgencySettings {
+    fn default() -> Self {
+        Self {
+            threshold_secs: 10.0,
+            intensity: 1.0,
+            reduced_motion: false,
+        }
+    }
+}
+
+/// What plays when a countdown reaches zero. The celebration keeps going
+/// until a click or key press acknowledges it.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct FinishSettings {
+    /// Pulse a glow in the sand colour behind the glass.
+    pub glow: bool,
+    /// Throw bursts of sand up from the bottom pile.
+    pub particles: bool,
+    /// Flash the whole window.
+    pub flash: bool,
+    pub acknowledge: FinishAcknowledge,
+}
+
+impl Default for FinishSettings {
+    fn default() -> Self {
+        Self {
+            glow: true,
+            particles: true,
+            flash: false,
+            acknowledge: FinishAcknowledge::Reset,
+        }
+    }
+}
+
+impl FinishSettings {
+    /// Whether finishing plays anything at all.
+    pub fn celebrates(&self) -> bool {
+        self.glow || self.particles || self.flash
+    }
+}
+
+/// What acknowledging the finish celebration does to the timer.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinishAcknowledge {
+    /// Refill the hourglass for another run.
+    Reset,
+    /// Only stop the celebration, leaving the empty hourglass.
+    Dismiss,
+}
+
+impl FinishAcknowledge {
+    pub const ALL: [FinishAcknowledge; 2] = [FinishAcknowledge::Reset, FinishAcknowledge::Dismiss];
+
+    /// Lowercase name used by text-driven controls.
+    pub fn name(self) -> &'static str {
+        match self {
+            FinishAcknowledge::Reset => "reset",
+            FinishAcknowledge::Dismiss => "dismiss",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|acknowledge| acknowledge.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
 /// Resource to manage the countdown timer
-#[derive(Resource, Debug, Clone, PartialEq)]
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TimerState {
     pub duration: f32,  // Total duration in seconds
     pub remaining: f32, // Remaining time in seconds
@@ -72,10 +698,16 @@ impl TimerState {
         self.duration += seconds;
         self.remaining += seconds;
         // Clamp to reasonable values
-        self.duration = self.duration.clamp(0.0, 3600.0 * 24.0); // Max 24 hours
+        self.duration = self.duration.clamp(0.0, MAX_DURATION_SECS);
         self.remaining = self.remaining.max(0.0).min(self.duration);
     }
 
+    /// Replace the duration and rewind to it, stopped.
+    pub fn set_duration(&mut self, seconds: f32) {
+        self.duration = seconds.clamp(0.0, MAX_DURATION_SECS);
+        self.reset();
+    }
+
     pub fn format_time(&self) -> String {
         let total_seconds = self.remaining as i32;
         let hours = total_seconds / 3600;
@@ -85,11 +717,86 @@ impl TimerState {
     }
 }
 
-/// Set by color/shape UI handlers to request a flip animation on the next
-/// (re)spawned main hourglass. Consumed by `apply_pending_flip`.
-#[derive(Resource, Default)]
+/// The timer and appearance flags given on the command line. They are
+/// applied on top of the state restored from the previous session, so a lone
+/// `--start` keeps the saved duration and look.
+#[derive(Resource, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct LaunchOverrides {
+    pub duration: Option<f32>,
+    pub start: bool,
+    pub shape: Option<HourglassShape>,
+    pub color: Option<Color>,
+    pub color_mode: Option<ColorMode>,
+    pub morphing: bool,
+    pub view: Option<ViewMode>,
+}
+
+impl LaunchOverrides {
+    pub fn apply_to_timer(&self, timer: &mut TimerState) {
+        if let Some(seconds) = self.duration {
+            timer.set_duration(seconds);
+        }
+        if self.start {
+            timer.is_running = timer.remaining > 0.0;
+        }
+    }
+
+    pub fn apply_to_config(&self, config: &mut HourglassConfig) {
+        if let Some(color) = self.color {
+            config.color = color;
+        }
+        if let Some(shape) = &self.shape {
+            config.shape_type = shape.clone();
+        }
+        if let Some(color_mode) = self.color_mode {
+            config.color_mode = color_mode;
+        }
+        if self.morphing {
+            config.shape_mode = ShapeMode::Morphing;
+        }
+        if let Some(view) = self.view {
+            config.view = view;
+        }
+    }
+}
+
+/// Set by color/shape UI handlers to request a flip animation on the main
+/// hourglass. Consumed by `apply_pending_flip`.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource, Default)]
 pub struct PendingFlip(pub bool);
 
+/// A sand colour shown in place of `HourglassConfig::color` without changing
+/// it, e.g. while the colour picker is open, so a colour that is only being
+/// tried out is never saved or sent to the extension.
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct SandColorPreview(pub Option<Color>);
+
+impl SandColorPreview {
+    pub fn sand_color(&self, config: &HourglassConfig) -> Color {
+        self.0.unwrap_or(config.color)
+    }
+}
+
+/// A shape drawn on the hourglass in place of `HourglassConfig::shape_type`
+/// without changing it, e.g. the shape editor's draft, so a shape that is only
+/// being edited is never saved or sent to the extension. A preview is always
+/// drawn static.
+#[derive(Resource, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct ShapePreview(pub Option<HourglassShape>);
+
+impl ShapePreview {
+    pub fn shape<'a>(&'a self, config: &'a HourglassConfig) -> (&'a HourglassShape, ShapeMode) {
+        match &self.0 {
+            Some(shape) => (shape, ShapeMode::Static),
+            None => (&config.shape_type, config.shape_mode),
+        }
+    }
+}
+
 /// Emitted for user-selected appearance changes. Continuous rainbow and
 /// morphing animation frames intentionally do not emit it.
 #[derive(Event, Debug, Clone, Copy, Default)]
@@ -108,9 +815,231 @@ pub const COLOR_PALETTE: &[Color] = &[
     Color::srgb(0.8, 0.4, 0.0), // Orange
 ];
 
+/// Names for `COLOR_PALETTE` entries, in the same order.
+pub const COLOR_PALETTE_NAMES: &[&str] = &[
+    "sand", "black", "white", "blue", "red", "purple", "green", "yellow", "orange",
+];
+
+/// Look up a palette colour by its name (case-insensitive).
+pub fn palette_color_by_name(name: &str) -> Option<Color> {
+    COLOR_PALETTE_NAMES
+        .iter()
+        .position(|candidate| candidate.eq_ignore_ascii_case(name.trim()))
+        .map(|index| COLOR_PALETTE[index])
+}
+
+/// Colours serialise as `[red, green, blue, alpha]` sRGB channels, the
+/// `colorRgba` shape used by the persisted snapshot.
+#[cfg(feature = "serde")]
+pub(crate) mod color_rgba {
+    use bevy::prelude::*;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        color.to_srgba().to_f32_array().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let [red, green, blue, alpha] = <[f32; 4]>::deserialize(deserializer)?;
+        Ok(Color::srgba(red, green, blue, alpha))
+    }
+}
+
+/// Parse a `#rrggbb`/`#rgb` hex colour or a built-in palette name.
+pub fn parse_color(text: &str) -> Option<Color> {
+    palette_color_by_name(text).or_else(|| Srgba::hex(text.trim()).ok().map(Color::from))
+}
+
+/// The named swatches offered by the colour row and the command palette.
+/// Starts as the built-in `COLOR_PALETTE` and can be replaced by the config
+/// file.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct ColorPalette(pub Vec<(String, Color)>);
+
+impl Default for ColorPalette {
+    fn default() -> Self {
+        Self(
+            COLOR_PALETTE_NAMES
+                .iter()
+                .zip(COLOR_PALETTE)
+                .map(|(name, &color)| (name.to_string(), color))
+                .collect(),
+        )
+    }
+}
+
+impl ColorPalette {
+    pub fn color_by_name(&self, name: &str) -> Option<Color> {
+        self.0
+            .iter()
+            .find(|(candidate, _)| candidate.eq_ignore_ascii_case(name.trim()))
+            .map(|&(_, color)| color)
+    }
+
+    pub fn contains(&self, color: Color) -> bool {
+        self.0.iter().any(|&(_, swatch)| swatch == color)
+    }
+
+    /// Append `color`, named after its hex code. Returns false if the palette
+    /// already has it.
+    pub fn add(&mut self, color: Color) -> bool {
+        if self.contains(color) {
+            return false;
+        }
+        self.0
+            .push((color.to_srgba().to_hex().to_lowercase(), color));
+        true
+    }
+
+    /// Remove the swatch at `index`, unless it is the last one left.
+    pub fn remove(&mut self, index: usize) -> bool {
+        if self.0.len() <= 1 || index >= self.0.len() {
+            return false;
+        }
+        self.0.remove(index);
+        true
+    }
+
+    /// Move the swatch at `from` so it ends up at `to`.
+    pub fn move_swatch(&mut self, from: usize, to: usize) {
+        if from < self.0.len() {
+            let swatch = self.0.remove(from);
+            self.0.insert(to.min(self.0.len()), swatch);
+        }
+    }
+}
+
+/// How many recently used colours the colour row remembers.
+pub const RECENT_COLOR_LIMIT: usize = 5;
+
+/// Static colours applied lately that are not in the palette, newest first.
+#[derive(Resource, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct RecentColors(pub Vec<Color>);
+
+impl RecentColors {
+    /// Move `color` to the front, forgetting the oldest beyond the limit.
+    pub fn record(&mut self, color: Color) {
+        self.0.retain(|&recent| recent != color);
+        self.0.insert(0, color);
+        self.0.truncate(RECENT_COLOR_LIMIT);
+    }
+}
+
+/// The stored form of the edited palette and the recent colours, shared by
+/// the native colours file and the extension's local storage.
+#[cfg(any(
+    all(feature = "persistence", not(target_arch = "wasm32")),
+    all(feature = "chrome_extension", target_arch = "wasm32")
+))]
+pub(crate) mod stored_colors {
+    use super::{ColorPalette, RECENT_COLOR_LIMIT, RecentColors};
+    use bevy::prelude::*;
+
+    /// Version of the stored colours, bumped on incompatible changes.
+    const COLORS_VERSION: u8 = 1;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct StoredSwatch {
+        name: String,
+        #[serde(with = "super::color_rgba")]
+        color_rgba: Color,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct StoredColor(#[serde(with = "super::color_rgba")] Color);
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct ColorsV1 {
+        version: u8,
+        palette: Vec<StoredSwatch>,
+        recent: Vec<StoredColor>,
+    }
+
+    pub(crate) fn colors_to_json(
+        palette: &ColorPalette,
+        recent: &RecentColors,
+    ) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&ColorsV1 {
+            version: COLORS_VERSION,
+            palette: palette
+                .0
+                .iter()
+                .map(|(name, color)| StoredSwatch {
+                    name: name.clone(),
+                    color_rgba: *color,
+                })
+                .collect(),
+            recent: recent.0.iter().copied().map(StoredColor).collect(),
+        })
+    }
+
+    /// Parse stored colours, clamping channels and dropping unnamed swatches.
+    /// An empty palette falls back to the built-in one.
+    pub(crate) fn colors_from_json(json: &str) -> Result<(ColorPalette, RecentColors), String> {
+        let stored: ColorsV1 = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        if stored.version != COLORS_VERSION {
+            return Err(format!("unsupported colours version {}", stored.version));
+        }
+        let swatches: Vec<(String, Color)> = stored
+            .palette
+            .into_iter()
+            .map(|swatch| (swatch.name.trim().to_string(), clamped(swatch.color_rgba)))
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+        let palette = if swatches.is_empty() {
+            ColorPalette::default()
+        } else {
+            ColorPalette(swatches)
+        };
+        let mut recent: Vec<Color> = stored
+            .recent
+            .into_iter()
+            .map(|StoredColor(color)| clamped(color))
+            .collect();
+        recent.truncate(RECENT_COLOR_LIMIT);
+        Ok((palette, RecentColors(recent)))
+    }
+
+    fn clamped(color: Color) -> Color {
+        let [red, green, blue, alpha] = color.to_srgba().to_f32_array();
+        Color::srgba(
+            red.clamp(0.0, 1.0),
+            green.clamp(0.0, 1.0),
+            blue.clamp(0.0, 1.0),
+            alpha.clamp(0.0, 1.0),
+        )
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn stored_colors_round_trip_and_clamp() {
+            let mut palette = ColorPalette::default();
+            palette.add(Color::srgb(0.0, 0.5, 0.5));
+            let recent = RecentColors(vec![Color::srgba(0.2, 0.4, 0.6, 0.8)]);
+            let json = colors_to_json(&palette, &recent).unwrap();
+            assert_eq!(colors_from_json(&json).unwrap(), (palette, recent));
+
+            let (palette, recent) = colors_from_json(
+                r#"{"version": 1, "palette": [], "recent": [[2.0, -1.0, 0.5, 1.0]]}"#,
+            )
+            .unwrap();
+            assert_eq!(palette, ColorPalette::default());
+            assert_eq!(recent.0, [Color::srgba(1.0, 0.0, 0.5, 1.0)]);
+            assert!(colors_from_json(r#"{"version": 2, "palette": [], "recent": []}"#).is_err());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_abs_diff_eq;
 
     fn state(duration: f32, remaining: f32, is_running: bool) -> TimerState {
         TimerState {
@@ -126,6 +1055,173 @@ mod tests {
         assert!(COLOR_PALETTE.contains(&SAND_COLOR));
     }
 
+    #[test]
+    fn palette_names_cover_every_palette_color() {
+        assert_eq!(COLOR_PALETTE_NAMES.len(), COLOR_PALETTE.len());
+        assert_eq!(palette_color_by_name("Sand"), Some(SAND_COLOR));
+        assert_eq!(palette_color_by_name("red"), Some(COLOR_PALETTE[4]));
+        assert_eq!(palette_color_by_name("mauve"), None);
+    }
+
+    #[test]
+    fn parse_color_accepts_hex_and_names() {
+        assert_eq!(parse_color("sand"), Some(SAND_COLOR));
+        assert_eq!(
+            parse_color("#ff8800"),
+            Some(Color::from(Srgba::rgb_u8(0xff, 0x88, 0x00)))
+        );
+        assert_eq!(parse_color("#ggg"), None);
+        assert_eq!(
+            ColorPalette::default().color_by_name("BLUE"),
+            palette_color_by_name("blue")
+        );
+    }
+
+    #[test]
+    fn palette_edits_keep_at_least_one_swatch() {
+        let teal = Color::srgb(0.0, 0.5, 0.5);
+        let mut palette = ColorPalette(vec![("sand".to_string(), SAND_COLOR)]);
+        assert!(palette.add(teal));
+        assert!(!palette.add(teal));
+        assert_eq!(palette.color_by_name("#008080"), Some(teal));
+
+        palette.move_swatch(1, 0);
+        assert_eq!(palette.0[0].1, teal);
+        assert!(palette.remove(0));
+        assert!(!palette.remove(0));
+        assert_eq!(palette.0, [("sand".to_string(), SAND_COLOR)]);
+    }
+
+    #[test]
+    fn recent_colors_are_newest_first_and_limited() {
+        let mut recent = RecentColors::default();
+        for step in 0..=RECENT_COLOR_LIMIT {
+            recent.record(Color::srgb(step as f32 / 10.0, 0.0, 0.0));
+        }
+        recent.record(Color::srgb(0.2, 0.0, 0.0));
+        assert_eq!(recent.0.len(), RECENT_COLOR_LIMIT);
+        assert_eq!(recent.0[0], Color::srgb(0.2, 0.0, 0.0));
+        assert_eq!(recent.0[1], Color::srgb(0.5, 0.0, 0.0));
+        assert!(!recent.0.contains(&Color::srgb(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn gradient_blends_between_stops_in_oklab() {
+        let gradient = ColorGradient::default();
+        let [green, amber, red] = [0, 1, 2].map(|index| gradient.stops[index].color);
+        assert_eq!(gradient.sample(1.0), green.to_srgba().into());
+        assert_eq!(gradient.sample(2.0), gradient.sample(1.0));
+        assert_eq!(gradient.sample(-1.0), red.to_srgba().into());
+
+        let midpoint = gradient.sample(0.75).to_srgba();
+        let oklab = Srgba::from(Oklaba::from(green).mix(&Oklaba::from(amber), 0.5));
+        assert!((midpoint.red - oklab.red).abs() < 1e-4);
+        assert!((midpoint.green - oklab.green).abs() < 1e-4);
+        // A raw sRGB blend of the same stops comes out noticeably different.
+        let srgb = green.to_srgba().mix(&amber.to_srgba(), 0.5);
+        assert!((midpoint.red - srgb.red).abs() > 0.01);
+
+        let timer = TimerState {
+            duration: 600.0,
+            remaining: 300.0,
+            is_running: true,
+        };
+        assert_eq!(gradient.color_at(&timer), gradient.sample(0.5));
+        let seconds = ColorGradient {
+            key: GradientKey::Seconds,
+            ..gradient
+        };
+        assert_eq!(seconds.color_at(&timer), seconds.sample(300.0));
+        assert_eq!(seconds.sample(300.0), green.to_srgba().into());
+    }
+
+    #[test]
+    fn gradient_sanitizing_sorts_clamps_and_falls_back() {
+        let stop = |at, color| GradientStop { at, color };
+        let gradient = ColorGradient {
+            key: GradientKey::Fraction,
+            stops: vec![
+                stop(-0.5, Color::srgba(2.0, 0.0, 0.0, 1.0)),
+                stop(f32::NAN, Color::WHITE),
+                stop(0.8, Color::BLACK),
+            ],
+        }
+        .sanitized();
+        assert_eq!(
+            gradient.stops,
+            [
+                stop(0.8, Color::srgba(0.0, 0.0, 0.0, 1.0)),
+                stop(0.0, Color::srgba(1.0, 0.0, 0.0, 1.0)),
+            ]
+        );
+
+        let empty = ColorGradient {
+            key: GradientKey::Seconds,
+            stops: Vec::new(),
+        };
+        assert_eq!(empty.sanitized(), ColorGradient::default());
+    }
+
+    #[test]
+    fn morph_phase_follows_the_clock_or_the_countdown() {
+        let idle = state(600.0, 600.0, false);
+        let clock = MorphSettings {
+            period_secs: 4.0,
+            ..default()
+        };
+        assert_eq!(clock.phase(5.0, &idle), 0.25);
+        assert_eq!(clock.phase(8.0, &idle), 0.0);
+
+        let progress = MorphSettings {
+            sync: MorphSync::Progress,
+            ..default()
+        };
+        assert_eq!(progress.phase(5.0, &idle), 0.0);
+        assert_eq!(progress.phase(5.0, &state(600.0, 150.0, true)), 0.75);
+        assert_eq!(progress.phase(5.0, &state(0.0, 0.0, false)), 0.0);
+    }
+
+    #[test]
+    fn morph_easings_keep_their_ends_and_names() {
+        for easing in MorphEasing::ALL {
+            assert_eq!(MorphEasing::from_name(easing.name()), Some(easing));
+            assert_eq!(easing.ease(0.0), 0.0);
+            assert_eq!(easing.ease(1.0), 1.0);
+        }
+        assert_eq!(MorphEasing::Smooth.ease(0.5), 0.5);
+        assert!(MorphEasing::EaseIn.ease(0.5) < 0.5);
+        assert!(MorphEasing::EaseOut.ease(0.5) > 0.5);
+        assert_eq!(
+            MorphSync::from_name(" Progress "),
+            Some(MorphSync::Progress)
+        );
+    }
+
+    #[test]
+    fn shape_names_round_trip() {
+        for shape in HourglassShape::BUILTIN {
+            assert_eq!(HourglassShape::from_name(shape.name()), Some(shape));
+        }
+        assert_eq!(
+            HourglassShape::from_name(" SLIM "),
+            Some(HourglassShape::SLIM)
+        );
+        assert_eq!(
+            HourglassShape::from_name("Tall Vase").unwrap().name(),
+            "tall vase"
+        );
+        assert_eq!(HourglassShape::from_name("  "), None);
+    }
+
+    #[test]
+    fn set_duration_clamps_and_rewinds() {
+        let mut s = state(180.0, 12.0, true);
+        s.set_duration(100_000.0);
+        assert_eq!(s.duration, MAX_DURATION_SECS);
+        assert_eq!(s.remaining, MAX_DURATION_SECS);
+        assert!(!s.is_running);
+    }
+
     #[test]
     fn reset_restores_remaining_and_stops() {
         let mut s = state(120.0, 3.0, true);
@@ -176,6 +1272,75 @@ mod tests {
         assert_eq!(s.remaining, 70.0);
     }
 
+    #[test]
+    fn sand_layers_split_the_glass_by_share() {
+        let (work, rest) = (Color::srgb(0.8, 0.2, 0.2), Color::srgb(0.2, 0.8, 0.2));
+        let layers = SandLayers(vec![
+            SandLayer {
+                share: 1500.0,
+                color: work,
+            },
+            SandLayer {
+                share: 300.0,
+                color: rest,
+            },
+            SandLayer {
+                share: 1500.0,
+                color: work,
+            },
+        ]);
+        let ends: Vec<f32> = layers.spans().map(|(_, end, _)| end).collect();
+        assert_abs_diff_eq!(ends[0], 1500.0 / 3300.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(ends[1], 1800.0 / 3300.0, epsilon = 1e-6);
+        assert_eq!(ends[2], 1.0);
+
+        assert_eq!(layers.draining(0.0), Some(work));
+        assert_eq!(layers.draining(0.5), Some(rest));
+        assert_eq!(layers.draining(0.9), Some(work));
+        assert_eq!(layers.draining(1.0), Some(work));
+        assert_eq!(SandLayers::default().draining(0.5), None);
+    }
+
+    #[test]
+    fn long_countdowns_cascade_an_hour_at_a_time() {
+        let cascade = CascadeSettings::default();
+        assert_eq!(cascade.segment(&state(7200.0, 7200.0, false)), None);
+
+        let hour = CASCADE_SEGMENT_SECS;
+        assert_eq!(
+            cascade.segment(&state(6.0 * hour, 6.0 * hour, false)),
+            Some(CascadeSegment {
+                duration: hour,
+                remaining: hour,
+                queued: 5,
+                hours: 5,
+            })
+        );
+        // The odd half hour runs first.
+        assert_eq!(
+            cascade.segment(&state(5.5 * hour, 5.25 * hour, true)),
+            Some(CascadeSegment {
+                duration: 0.5 * hour,
+                remaining: 0.25 * hour,
+                queued: 5,
+                hours: 5,
+            })
+        );
+        // Then one whole hour after another.
+        let segment = cascade
+            .segment(&state(5.5 * hour, 2.25 * hour, true))
+            .unwrap();
+        assert_eq!((segment.duration, segment.remaining), (hour, 0.25 * hour));
+        assert_eq!(segment.queued, 2);
+        let finished = cascade.segment(&state(5.5 * hour, 0.0, false)).unwrap();
+        assert_eq!((finished.remaining, finished.queued), (0.0, 0));
+
+        let never = CascadeSettings {
+            threshold_secs: 0.0,
+        };
+        assert_eq!(never.segment(&state(6.0 * hour, hour, true)), None);
+    }
+
     #[test]
     fn format_time_boundaries() {
         assert_eq!(state(0.0, 0.0, false).format_time(), "00:00:00");
@@ -200,4 +1365,67 @@ mod tests {
         // the UI never displays this — but format_time itself does not guard.
         assert_eq!(state(0.0, -5.0, false).format_time(), "00:00:-5");
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn config_serializes_with_snapshot_wire_names() {
+        let config = HourglassConfig {
+            color: Color::srgba(0.25, 0.5, 1.0, 1.0),
+            shape_type: HourglassShape::SLIM,
+            color_mode: ColorMode::Progress,
+            shape_mode: ShapeMode::Morphing,
+            gradient: ColorGradient {
+                key: GradientKey::Seconds,
+                stops: vec![GradientStop {
+                    at: 60.0,
+                    color: Color::srgba(1.0, 0.5, 0.0, 1.0),
+                }],
+            },
+            view: ViewMode::ThreeD,
+        };
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "colorRgba": [0.25, 0.5, 1.0, 1.0],
+                "shape": "slim",
+                "colorMode": "progress",
+                "shapeMode": "morphing",
+                "gradient": {
+                    "key": "seconds",
+                    "stops": [{"at": 60.0, "colorRgba": [1.0, 0.5, 0.0, 1.0]}],
+                },
+                "view": "3d",
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<HourglassConfig>(json).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn core_resources_are_reflectable() {
+        let mut app = App::new();
+        app.register_type::<HourglassConfig>()
+            .register_type::<TimerState>()
+            .register_type::<ColorPalette>();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        for type_path in [
+            HourglassConfig::type_path(),
+            TimerState::type_path(),
+            ColorPalette::type_path(),
+        ] {
+            let registration = registry.get_with_type_path(type_path).unwrap();
+            assert!(
+                registration.data::<ReflectResource>().is_some(),
+                "{type_path}"
+            );
+        }
+        assert!(
+            registry
+                .get(std::any::TypeId::of::<HourglassShape>())
+                .is_some()
+        );
+    }
 }
@@ -0,0 +1,856 @@
+//! Hourglass shapes described as data. The built-in shapes ship as
+//! `assets/shapes/*.shape.ron`; any other `.shape.ron` file in that folder
+//! joins the shape row, and builds with `file_watcher` (such as `dev_native`)
+//! pick up edits while the app is running.
+
+use crate::resources::HourglassShape;
+use bevy::prelude::*;
+use bevy_hourglass::{BulbStyle, HourglassMeshBodyConfig, HourglassMeshPlatesConfig, NeckStyle};
+
+/// Height of the main hourglass before a shape's own scaling.
+pub const MAIN_BASE_HEIGHT: f32 = 400.0;
+/// Height of the shape-row minis before a shape's own scaling.
+pub const MINI_BASE_HEIGHT: f32 = 25.0;
+
+/// Glass tint shared by the built-in shapes.
+pub const GLASS_COLOR: Color = Color::srgba(0.85, 0.95, 1.0, 0.2);
+
+pub struct ShapesPlugin;
+
+impl Plugin for ShapesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShapeLibrary>();
+
+        #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+        app.add_plugins(assets::ShapeAssetsPlugin);
+    }
+}
+
+/// Mirror of `bevy_hourglass::BulbStyle` that can be read from a file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShapeBulb {
+    Circular {
+        curvature: f32,
+        width_factor: f32,
+        curve_resolution: usize,
+    },
+    Straight {
+        width_factor: f32,
+    },
+}
+
+/// Mirror of `bevy_hourglass::NeckStyle` that can be read from a file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShapeNeck {
+    Straight {
+        width: f32,
+        height: f32,
+    },
+    Curved {
+        curvature: f32,
+        width: f32,
+        height: f32,
+        curve_resolution: usize,
+    },
+}
+
+/// Outline of one hourglass at one size.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct ShapeGeometry {
+    /// Total height of the glass body.
+    pub height: f32,
+    pub bulb: ShapeBulb,
+    pub neck: ShapeNeck,
+    pub plate_width: f32,
+    pub plate_height: f32,
+}
+
+impl ShapeGeometry {
+    /// The same outline at `factor` times the size. Curve resolutions are
+    /// halved for anything much smaller, as the minis do not need them.
+    fn scaled(&self, factor: f32) -> Self {
+        let resolution = |resolution: usize| {
+            if factor < 0.5 {
+                (resolution / 2).max(4)
+            } else {
+                resolution
+            }
+        };
+        Self {
+            height: self.height * factor,
+            bulb: match self.bulb {
+                ShapeBulb::Circular {
+                    curvature,
+                    width_factor,
+                    curve_resolution,
+                } => ShapeBulb::Circular {
+                    curvature,
+                    width_factor,
+                    curve_resolution: resolution(curve_resolution),
+                },
+                ShapeBulb::Straight { width_factor } => ShapeBulb::Straight { width_factor },
+            },
+            neck: match self.neck {
+                ShapeNeck::Straight { width, height } => ShapeNeck::Straight {
+                    width: width * factor,
+                    height: height * factor,
+                },
+                ShapeNeck::Curved {
+                    curvature,
+                    width,
+                    height,
+                    curve_resolution,
+                } => ShapeNeck::Curved {
+                    curvature,
+                    width: width * factor,
+                    height: height * factor,
+                    curve_resolution: resolution(curve_resolution),
+                },
+            },
+            plate_width: self.plate_width * factor,
+            plate_height: self.plate_height * factor,
+        }
+    }
+
+    fn mesh_configs(
+        &self,
+        glass_color: Color,
+    ) -> (HourglassMeshBodyConfig, HourglassMeshPlatesConfig) {
+        (
+            HourglassMeshBodyConfig {
+                total_height: self.height,
+                bulb_style: match self.bulb {
+                    ShapeBulb::Circular {
+                        curvature,
+                        width_factor,
+                        curve_resolution,
+                    } => BulbStyle::Circular {
+                        curvature,
+                        width_factor,
+                        curve_resolution,
+                    },
+                    ShapeBulb::Straight { width_factor } => BulbStyle::Straight { width_factor },
+                },
+                neck_style: match self.neck {
+                    ShapeNeck::Straight { width, height } => NeckStyle::Straight { width, height },
+                    ShapeNeck::Curved {
+                        curvature,
+                        width,
+                        height,
+                        curve_resolution,
+                    } => NeckStyle::Curved {
+                        curvature,
+                        width,
+                        height,
+                        curve_resolution,
+                    },
+                },
+                color: glass_color,
+            },
+            HourglassMeshPlatesConfig {
+                width: self.plate_width,
+                height: self.plate_height,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+/// One `*.shape.ron` file.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct ShapeDefinition {
+    /// Name shown in the shape row and accepted by `--shape`, the config file
+    /// and the command palette. A file named like a built-in replaces it.
+    pub name: String,
+    /// `(r, g, b, a)` in sRGB.
+    #[cfg_attr(feature = "serde", serde(with = "crate::resources::color_rgba"))]
+    pub glass_color: Color,
+    /// The main hourglass, drawn around `MAIN_BASE_HEIGHT` tall.
+    pub main: ShapeGeometry,
+    /// The shape-row button, drawn around `MINI_BASE_HEIGHT` tall. Scaled
+    /// down from `main` when left out.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub mini: Option<ShapeGeometry>,
+}
+
+impl ShapeDefinition {
+    pub fn shape(&self) -> HourglassShape {
+        HourglassShape::from_name(&self.name).unwrap_or(HourglassShape::CLASSIC)
+    }
+
+    pub fn main_configs(&self) -> (HourglassMeshBodyConfig, HourglassMeshPlatesConfig) {
+        self.main.mesh_configs(self.glass_color)
+    }
+
+    pub fn mini_configs(&self) -> (HourglassMeshBodyConfig, HourglassMeshPlatesConfig) {
+        match &self.mini {
+            Some(mini) => mini.mesh_configs(self.glass_color),
+            None => self
+                .main
+                .scaled(MINI_BASE_HEIGHT / MAIN_BASE_HEIGHT)
+                .mesh_configs(self.glass_color),
+        }
+    }
+
+    /// The shapes compiled into the binary, matching the bundled files.
+    pub fn builtin() -> [ShapeDefinition; 4] {
+        let shape = |name: &str, main, mini| ShapeDefinition {
+            name: name.to_string(),
+            glass_color: GLASS_COLOR,
+            main,
+            mini: Some(mini),
+        };
+        let geometry = |height, bulb, neck, plate_width, plate_height| ShapeGeometry {
+            height,
+            bulb,
+            neck,
+            plate_width,
+            plate_height,
+        };
+        let circular = |curvature, width_factor, curve_resolution| ShapeBulb::Circular {
+            curvature,
+            width_factor,
+            curve_resolution,
+        };
+        let curved = |curvature, width, height, curve_resolution| ShapeNeck::Curved {
+            curvature,
+            width,
+            height,
+            curve_resolution,
+        };
+        [
+            shape(
+                "classic",
+                geometry(
+                    400.0,
+                    circular(1.0, 1.0, 20),
+                    curved(1.0, 14.0, 20.0, 10),
+                    400.0,
+                    10.0,
+                ),
+                geometry(
+                    25.0,
+                    circular(1.0, 1.0, 10),
+                    curved(1.0, 3.0, 4.0, 5),
+                    25.0,
+                    2.0,
+                ),
+            ),
+            shape(
+                "modern",
+                geometry(
+                    400.0,
+                    circular(0.0, 1.0, 10),
+                    ShapeNeck::Straight {
+                        width: 12.0,
+                        height: 32.0,
+                    },
+                    380.0,
+                    12.0,
+                ),
+                geometry(
+                    25.0,
+                    circular(0.0, 1.0, 5),
+                    ShapeNeck::Straight {
+                        width: 2.5,
+                        height: 6.0,
+                    },
+                    22.0,
+                    2.5,
+                ),
+            ),
+            // Taller and narrower, with a thinner neck.
+            shape(
+                "slim",
+                geometry(
+                    480.0,
+                    circular(1.5, 0.7, 18),
+                    curved(1.5, 12.0, 24.0, 8),
+                    340.0,
+                    8.0,
+                ),
+                geometry(
+                    30.0,
+                    circular(1.5, 0.7, 8),
+                    curved(1.5, 2.0, 5.0, 4),
+                    20.0,
+                    1.5,
+                ),
+            ),
+            // Shorter and wider, with a thicker neck.
+            shape(
+                "wide",
+                geometry(
+                    320.0,
+                    circular(1.0, 1.2, 24),
+                    curved(0.7, 20.0, 16.0, 12),
+                    390.0,
+                    14.0,
+                ),
+                geometry(
+                    20.0,
+                    circular(1.0, 1.2, 10),
+                    curved(0.7, 4.0, 3.0, 6),
+                    28.0,
+                    3.0,
+                ),
+            ),
+        ]
+    }
+}
+
+/// Every shape the app can draw, in shape-row order: the built-ins first,
+/// then the others by name. Shapes come from three layers, each replacing
+/// same-named shapes of the one before: the shape files, shapes saved from
+/// the editor, and the editor's unsaved draft.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct ShapeLibrary {
+    definitions: Vec<ShapeDefinition>,
+    loaded: Vec<ShapeDefinition>,
+    saved: Vec<ShapeDefinition>,
+    draft: Option<ShapeDefinition>,
+    /// Shape files are still being read, so a name that is not in the
+    /// library yet may still turn up.
+    loading: bool,
+}
+
+impl Default for ShapeLibrary {
+    fn default() -> Self {
+        Self {
+            definitions: ShapeDefinition::builtin().into(),
+            loaded: Vec::new(),
+            saved: Vec::new(),
+            draft: None,
+            loading: false,
+        }
+    }
+}
+
+impl ShapeLibrary {
+    /// The built-ins with `loaded` definitions layered on top.
+    pub fn with_loaded<'a>(loaded: impl IntoIterator<Item = &'a ShapeDefinition>) -> Self {
+        let mut library = Self::default();
+        library.set_loaded(loaded.into_iter().cloned().collect());
+        library
+    }
+
+    fn refresh(&mut self) {
+        let mut definitions: Vec<ShapeDefinition> = ShapeDefinition::builtin().into();
+        let builtin_count = definitions.len();
+        for definition in self.loaded.iter().chain(&self.saved).chain(&self.draft) {
+            let shape = definition.shape();
+            match definitions
+                .iter_mut()
+                .find(|existing| existing.shape() == shape)
+            {
+                Some(existing) => *existing = definition.clone(),
+                None => definitions.push(definition.clone()),
+            }
+        }
+        definitions[builtin_count..].sort_by_key(ShapeDefinition::shape);
+        self.definitions = definitions;
+    }
+
+    /// Replace the shapes read from files.
+    pub fn set_loaded(&mut self, loaded: Vec<ShapeDefinition>) {
+        self.loaded = loaded;
+        self.refresh();
+    }
+
+    /// Shapes saved from the editor, in the order they were first saved.
+    pub fn saved(&self) -> &[ShapeDefinition] {
+        &self.saved
+    }
+
+    pub fn set_saved(&mut self, saved: Vec<ShapeDefinition>) {
+        self.saved = saved;
+        self.refresh();
+    }
+
+    /// Keep `definition`, replacing a saved shape with the same name.
+    pub fn save(&mut self, definition: ShapeDefinition) {
+        let shape = definition.shape();
+        match self
+            .saved
+            .iter_mut()
+            .find(|existing| existing.shape() == shape)
+        {
+            Some(existing) => *existing = definition,
+            None => self.saved.push(definition),
+        }
+        self.refresh();
+    }
+
+    /// Show an unsaved shape, or stop showing it with `None`.
+    pub fn set_draft(&mut self, draft: Option<ShapeDefinition>) {
+        self.draft = draft;
+        self.refresh();
+    }
+
+    pub fn definitions(&self) -> &[ShapeDefinition] {
+        &self.definitions
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = HourglassShape> + '_ {
+        self.definitions.iter().map(ShapeDefinition::shape)
+    }
+
+    pub fn find(&self, shape: &HourglassShape) -> Option<&ShapeDefinition> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.shape() == *shape)
+    }
+
+    /// The definition for `shape`, or Classic if no such shape is loaded.
+    pub fn get(&self, shape: &HourglassShape) -> &ShapeDefinition {
+        self.find(shape).unwrap_or(&self.definitions[0])
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Whether `shape` can be chosen: it is loaded, or might still be.
+    pub fn accepts(&self, shape: &HourglassShape) -> bool {
+        self.loading || self.find(shape).is_some()
+    }
+
+    /// Comma-separated names for error messages.
+    pub fn names(&self) -> String {
+        self.shapes()
+            .map(|shape| shape.name().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The first of `custom 1`, `custom 2`, ... that is not taken yet.
+    pub fn unused_name(&self) -> String {
+        (1..)
+            .map(|index| format!("custom {index}"))
+            .find(|name| {
+                HourglassShape::from_name(name).is_some_and(|shape| self.find(&shape).is_none())
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The stored form of the shapes saved from the editor, shared by the
+/// native shapes file and the extension's local storage.
+#[cfg(any(
+    all(feature = "persistence", not(target_arch = "wasm32")),
+    all(feature = "chrome_extension", target_arch = "wasm32")
+))]
+pub(crate) mod stored_shapes {
+    use super::{ShapeBulb, ShapeDefinition, ShapeGeometry, ShapeNeck};
+
+    /// Version of the stored shapes, bumped on incompatible changes.
+    const SHAPES_VERSION: u8 = 1;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct ShapesV1 {
+        version: u8,
+        shapes: Vec<ShapeDefinition>,
+    }
+
+    pub(crate) fn shapes_to_json(shapes: &[ShapeDefinition]) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&ShapesV1 {
+            version: SHAPES_VERSION,
+            shapes: shapes.to_vec(),
+        })
+    }
+
+    /// Parse stored shapes, dropping unnamed ones and any whose sizes are not
+    /// positive numbers.
+    pub(crate) fn shapes_from_json(json: &str) -> Result<Vec<ShapeDefinition>, String> {
+        let stored: ShapesV1 = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        if stored.version != SHAPES_VERSION {
+            return Err(format!("unsupported shapes version {}", stored.version));
+        }
+        Ok(stored
+            .shapes
+            .into_iter()
+            .filter(|shape| {
+                !shape.name.trim().is_empty()
+                    && drawable(&shape.main)
+                    && shape.mini.as_ref().is_none_or(drawable)
+            })
+            .collect())
+    }
+
+    fn drawable(geometry: &ShapeGeometry) -> bool {
+        let positive = |value: f32| value.is_finite() && value > 0.0;
+        let bulb_width = match geometry.bulb {
+            ShapeBulb::Circular { width_factor, .. } | ShapeBulb::Straight { width_factor } => {
+                width_factor
+            }
+        };
+        let neck_width = match geometry.neck {
+            ShapeNeck::Straight { width, .. } | ShapeNeck::Curved { width, .. } => width,
+        };
+        positive(geometry.height)
+            && positive(bulb_width)
+            && positive(neck_width)
+            && positive(geometry.plate_width)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn stored_shapes_round_trip_and_drop_broken_ones() {
+            let mut vase = ShapeDefinition::builtin()[1].clone();
+            vase.name = "vase".to_string();
+            vase.mini = None;
+            let json = shapes_to_json(std::slice::from_ref(&vase)).unwrap();
+            assert_eq!(shapes_from_json(&json).unwrap(), [vase.clone()]);
+
+            let mut flat = vase.clone();
+            flat.main.height = 0.0;
+            let mut unnamed = vase.clone();
+            unnamed.name = " ".to_string();
+            let json = shapes_to_json(&[flat, unnamed, vase.clone()]).unwrap();
+            assert_eq!(shapes_from_json(&json).unwrap(), [vase]);
+            assert!(shapes_from_json(r#"{"version": 2, "shapes": []}"#).is_err());
+        }
+    }
+}
+
+#[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+pub(crate) use assets::export_shape;
+
+#[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+mod assets {
+    use super::{ShapeDefinition, ShapeLibrary};
+    use bevy::asset::io::Reader;
+    use bevy::asset::io::file::FileAssetReader;
+    use bevy::asset::{AssetLoader, LoadContext, LoadedFolder, RecursiveDependencyLoadState, ron};
+    use bevy::prelude::*;
+    use std::fmt;
+    use std::path::{Path, PathBuf};
+
+    /// Folder inside the asset directory that holds the shape files.
+    const SHAPES_FOLDER: &str = "shapes";
+
+    pub(super) struct ShapeAssetsPlugin;
+
+    impl Plugin for ShapeAssetsPlugin {
+        fn build(&self, app: &mut App) {
+            app.init_asset::<ShapeDefinition>()
+                .init_asset_loader::<ShapeDefinitionLoader>()
+                .add_systems(Startup, load_shape_folder)
+                .add_systems(PreUpdate, sync_shape_library);
+        }
+    }
+
+    #[derive(Default)]
+    struct ShapeDefinitionLoader;
+
+    #[derive(Debug)]
+    enum ShapeLoadError {
+        Io(std::io::Error),
+        Ron(ron::error::SpannedError),
+        Name,
+    }
+
+    impl fmt::Display for ShapeLoadError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Io(error) => write!(f, "could not read shape: {error}"),
+                Self::Ron(error) => write!(f, "{error}"),
+                Self::Name => write!(f, "shape needs a non-blank name"),
+            }
+        }
+    }
+
+    impl std::error::Error for ShapeLoadError {}
+
+    impl AssetLoader for ShapeDefinitionLoader {
+        type Asset = ShapeDefinition;
+        type Settings = ();
+        type Error = ShapeLoadError;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            _settings: &(),
+            _load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(ShapeLoadError::Io)?;
+            let definition = parse_shape_file(&bytes).map_err(ShapeLoadError::Ron)?;
+            if definition.name.trim().is_empty() {
+                return Err(ShapeLoadError::Name);
+            }
+            Ok(definition)
+        }
+
+        // Longer than the config file's `ron`, so it wins for these files.
+        fn extensions(&self) -> &[&str] {
+            &["shape.ron"]
+        }
+    }
+
+    /// `mini` may be written bare rather than as `Some(...)`.
+    pub(super) fn parse_shape_file(
+        bytes: &[u8],
+    ) -> Result<ShapeDefinition, ron::error::SpannedError> {
+        ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_bytes(bytes)
+    }
+
+    /// Write `definition` into the shape folder, where builds with
+    /// `file_watcher` pick it up straight away and others on the next launch.
+    pub(crate) fn export_shape(definition: &ShapeDefinition) -> std::io::Result<PathBuf> {
+        let folder = FileAssetReader::get_base_path()
+            .join("assets")
+            .join(SHAPES_FOLDER);
+        write_shape_file(&folder, definition)
+    }
+
+    pub(super) fn write_shape_file(
+        folder: &Path,
+        definition: &ShapeDefinition,
+    ) -> std::io::Result<PathBuf> {
+        let slug: String = definition
+            .name
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let ron = ron::ser::to_string_pretty(definition, ron::ser::PrettyConfig::default())
+            .map_err(std::io::Error::other)?;
+        std::fs::create_dir_all(folder)?;
+        let path = folder.join(format!("{slug}.shape.ron"));
+        std::fs::write(&path, ron + "\n")?;
+        Ok(path)
+    }
+
+    /// Keeps the folder, and with it every shape, loaded.
+    #[derive(Resource)]
+    struct ShapeFolderHandle(Handle<LoadedFolder>);
+
+    fn load_shape_folder(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        mut library: ResMut<ShapeLibrary>,
+    ) {
+        commands.insert_resource(ShapeFolderHandle(asset_server.load_folder(SHAPES_FOLDER)));
+        library.loading = true;
+    }
+
+    /// Rebuild the library whenever a shape file is added, edited or removed,
+    /// and once the whole folder has been read.
+    fn sync_shape_library(
+        mut events: EventReader<AssetEvent<ShapeDefinition>>,
+        folder: Option<Res<ShapeFolderHandle>>,
+        asset_server: Res<AssetServer>,
+        definitions: Res<Assets<ShapeDefinition>>,
+        mut library: ResMut<ShapeLibrary>,
+    ) {
+        let changed = events.read().count() > 0;
+        let loading = folder.is_some_and(|folder| {
+            !matches!(
+                asset_server.get_recursive_dependency_load_state(&folder.0),
+                Some(
+                    RecursiveDependencyLoadState::Loaded | RecursiveDependencyLoadState::Failed(_)
+                ) | None
+            )
+        });
+        if !changed && loading == library.loading {
+            return;
+        }
+        let mut loaded: Vec<_> = definitions
+            .iter()
+            .map(|(_, definition)| definition.clone())
+            .collect();
+        loaded.sort_by(|a, b| a.name.cmp(&b.name));
+        if library.loaded != loaded {
+            library.set_loaded(loaded);
+        }
+        if library.loading != loading {
+            library.loading = loading;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circular(bulb: &BulbStyle) -> (f32, f32, usize) {
+        match *bulb {
+            BulbStyle::Circular {
+                curvature,
+                width_factor,
+                curve_resolution,
+            } => (curvature, width_factor, curve_resolution),
+            _ => panic!("expected a circular bulb"),
+        }
+    }
+
+    #[test]
+    fn builtin_library_covers_the_builtin_names_in_order() {
+        let library = ShapeLibrary::default();
+        assert_eq!(
+            library.shapes().collect::<Vec<_>>(),
+            HourglassShape::BUILTIN
+        );
+        let (body, plates) = library.get(&HourglassShape::SLIM).main_configs();
+        assert_eq!(body.total_height, 480.0);
+        assert_eq!(circular(&body.bulb_style), (1.5, 0.7, 18));
+        assert_eq!((plates.width, plates.height), (340.0, 8.0));
+        assert_eq!(body.color, GLASS_COLOR);
+        let (mini, _) = library.get(&HourglassShape::WIDE).mini_configs();
+        assert_eq!(mini.total_height, 20.0);
+    }
+
+    #[test]
+    fn unknown_shapes_fall_back_to_classic() {
+        let library = ShapeLibrary::default();
+        let round = HourglassShape::from_name("round").unwrap();
+        assert!(library.find(&round).is_none());
+        assert_eq!(library.get(&round).shape(), HourglassShape::CLASSIC);
+    }
+
+    #[test]
+    fn loaded_shapes_replace_builtins_and_follow_them_by_name() {
+        let mut tall = ShapeDefinition::builtin()[2].clone();
+        tall.name = "Tall".to_string();
+        tall.mini = None;
+        let mut vase = tall.clone();
+        vase.name = "vase".to_string();
+        let mut classic = ShapeDefinition::builtin()[0].clone();
+        classic.glass_color = Color::WHITE;
+
+        let library = ShapeLibrary::with_loaded([&vase, &classic, &tall]);
+        let names: Vec<_> = library
+            .shapes()
+            .map(|shape| shape.name().to_string())
+            .collect();
+        assert_eq!(names, ["classic", "modern", "slim", "wide", "tall", "vase"]);
+        assert_eq!(
+            library.get(&HourglassShape::CLASSIC).glass_color,
+            Color::WHITE
+        );
+
+        // Without a `mini` outline the shape row scales the main one down.
+        let (mini, plates) = library
+            .get(&HourglassShape::from_name("tall").unwrap())
+            .mini_configs();
+        assert_eq!(mini.total_height, 30.0);
+        assert_eq!(circular(&mini.bulb_style), (1.5, 0.7, 9));
+        assert_eq!(plates.width, 340.0 / 16.0);
+    }
+
+    #[test]
+    fn saved_shapes_and_the_draft_layer_over_loaded_ones() {
+        let mut vase = ShapeDefinition::builtin()[1].clone();
+        vase.name = "vase".to_string();
+        let mut library = ShapeLibrary::with_loaded([&vase]);
+        assert_eq!(library.unused_name(), "custom 1");
+
+        let mut saved_vase = vase.clone();
+        saved_vase.glass_color = Color::WHITE;
+        library.save(saved_vase.clone());
+        let mut custom = vase.clone();
+        custom.name = "Custom 1".to_string();
+        library.save(custom);
+        assert_eq!(library.saved().len(), 2);
+        assert_eq!(library.unused_name(), "custom 2");
+
+        let mut draft = saved_vase.clone();
+        draft.main.height = 300.0;
+        library.set_draft(Some(draft.clone()));
+        let vase_shape = HourglassShape::from_name("vase").unwrap();
+        assert_eq!(library.get(&vase_shape), &draft);
+        library.set_draft(None);
+        assert_eq!(library.get(&vase_shape), &saved_vase);
+
+        // New files keep the saved shapes on top.
+        library.set_loaded(Vec::new());
+        assert_eq!(library.get(&vase_shape), &saved_vase);
+        assert_eq!(library.definitions().len(), 6);
+    }
+
+    #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+    #[test]
+    fn exported_shapes_read_back_as_shape_files() {
+        let folder = std::env::temp_dir().join(format!(
+            "hourglass-timer-test-export-{}",
+            std::process::id()
+        ));
+        let mut vase = ShapeDefinition::builtin()[0].clone();
+        vase.name = "Tall Vase".to_string();
+        vase.mini = None;
+
+        let path = assets::write_shape_file(&folder, &vase).unwrap();
+        assert_eq!(path.file_name().unwrap(), "tall-vase.shape.ron");
+        let file = std::fs::read(&path).unwrap();
+        assert_eq!(assets::parse_shape_file(&file).unwrap(), vase);
+
+        let _ = std::fs::remove_dir_all(folder);
+    }
+
+    #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+    #[test]
+    fn bundled_shape_files_match_the_builtins() {
+        let files = [
+            include_str!("../assets/shapes/classic.shape.ron"),
+            include_str!("../assets/shapes/modern.shape.ron"),
+            include_str!("../assets/shapes/slim.shape.ron"),
+            include_str!("../assets/shapes/wide.shape.ron"),
+        ];
+        for (file, builtin) in files.into_iter().zip(ShapeDefinition::builtin()) {
+            assert_eq!(assets::parse_shape_file(file.as_bytes()).unwrap(), builtin);
+        }
+    }
+
+    #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+    #[test]
+    fn shape_folder_loads_into_the_library() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            bevy::asset::AssetPlugin::default(),
+            ShapesPlugin,
+        ));
+        // A warm disk can load the whole folder within the first update, so
+        // only the end state is checked.
+        for _ in 0..200 {
+            app.update();
+            if !app.world().resource::<ShapeLibrary>().is_loading() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let library = app.world().resource::<ShapeLibrary>();
+        assert!(!library.is_loading());
+        assert_eq!(library.definitions(), ShapeLibrary::default().definitions());
+    }
+}
@@ -0,0 +1,377 @@
+//! Versioned timer/appearance snapshot shared by every persistence backend:
+//! the Chrome side panel (via `chrome.storage`) and native builds (via a file
+//! in the user's state directory).
+#![cfg_attr(
+    not(any(test, feature = "persistence", target_arch = "wasm32")),
+    allow(dead_code)
+)]
+
+use crate::resources::{HourglassConfig, TimerState};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(crate) const SNAPSHOT_VERSION: u8 = 1;
+const MAX_DURATION_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SnapshotTimerStatus {
+    Idle,
+    Running,
+    Paused,
+    Finished,
+}
+
+/// Versioned wire format shared with the side-panel loader, the service worker
+/// and the native state file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SnapshotV1 {
+    pub version: u8,
+    #[serde(default)]
+    pub revision: u64,
+    #[serde(default)]
+    pub source_id: String,
+    pub duration_ms: f64,
+    pub remaining_ms: f64,
+    pub status: SnapshotTimerStatus,
+    pub deadline_ms: Option<f64>,
+    #[serde(default)]
+    pub run_id: Option<String>,
+    #[serde(default)]
+    pub notified_run_id: Option<String>,
+    /// Serialised through `HourglassConfig`'s own serde impl, so the wire
+    /// names live next to the resource they describe.
+    pub appearance: HourglassConfig,
+}
+
+pub(crate) fn snapshot_from_resources(
+    timer_state: &TimerState,
+    deadline_ms: Option<f64>,
+    config: &HourglassConfig,
+) -> SnapshotV1 {
+    SnapshotV1 {
+        version: SNAPSHOT_VERSION,
+        revision: 0,
+        source_id: String::new(),
+        duration_ms: f64::from(timer_state.duration) * 1000.0,
+        remaining_ms: f64::from(timer_state.remaining) * 1000.0,
+        status: timer_status(timer_state),
+        deadline_ms,
+        run_id: None,
+        notified_run_id: None,
+        appearance: config.clone(),
+    }
+}
+
+pub(crate) fn timer_status(timer_state: &TimerState) -> SnapshotTimerStatus {
+    if timer_state.remaining <= 0.0 {
+        SnapshotTimerStatus::Finished
+    } else if timer_state.is_running {
+        SnapshotTimerStatus::Running
+    } else if timer_state.remaining >= timer_state.duration {
+        SnapshotTimerStatus::Idle
+    } else {
+        SnapshotTimerStatus::Paused
+    }
+}
+
+/// Normalise `snapshot` into the live resources. A running snapshot resumes
+/// against its absolute `deadline_ms`, so time spent closed still counts.
+/// Returns `false` (leaving everything untouched) for unknown versions.
+pub(crate) fn apply_snapshot(
+    snapshot: SnapshotV1,
+    now_ms: f64,
+    timer_state: &mut TimerState,
+    deadline: &mut Option<f64>,
+    config: &mut HourglassConfig,
+) -> bool {
+    if snapshot.version != SNAPSHOT_VERSION {
+        return false;
+    }
+
+    let has_valid_duration = snapshot.duration_ms.is_finite() && snapshot.duration_ms >= 0.0;
+    let duration_ms = if has_valid_duration {
+        finite_clamp(snapshot.duration_ms, 0.0, MAX_DURATION_MS)
+    } else {
+        f64::from(TimerState::default().duration) * 1000.0
+    };
+    let stored_remaining_ms = if has_valid_duration {
+        finite_clamp(snapshot.remaining_ms, 0.0, duration_ms)
+    } else {
+        duration_ms
+    };
+    let mut resolved_deadline = snapshot.deadline_ms.filter(|value| value.is_finite());
+
+    let (remaining_ms, is_running) = if !has_valid_duration {
+        resolved_deadline = None;
+        (duration_ms, false)
+    } else {
+        match snapshot.status {
+            SnapshotTimerStatus::Running => {
+                let value = resolved_deadline
+                    .map(|value| (value - now_ms).max(0.0))
+                    .unwrap_or(stored_remaining_ms);
+                if resolved_deadline.is_none() && value > 0.0 {
+                    resolved_deadline = Some(now_ms + value);
+                }
+                (value, value > 0.0)
+            }
+            SnapshotTimerStatus::Paused => (stored_remaining_ms, false),
+            SnapshotTimerStatus::Idle => (duration_ms, false),
+            SnapshotTimerStatus::Finished => (0.0, false),
+        }
+    };
+
+    timer_state.duration = (duration_ms / 1000.0) as f32;
+    timer_state.remaining = (remaining_ms / 1000.0) as f32;
+    timer_state.is_running = is_running;
+    *deadline = if is_running { resolved_deadline } else { None };
+
+    let appearance = snapshot.appearance;
+    let [red, green, blue, alpha] = appearance.color.to_srgba().to_f32_array();
+    *config = HourglassConfig {
+        color: Color::srgba(
+            red.clamp(0.0, 1.0),
+            green.clamp(0.0, 1.0),
+            blue.clamp(0.0, 1.0),
+            alpha.clamp(0.0, 1.0),
+        ),
+        gradient: appearance.gradient.sanitized(),
+        ..appearance
+    };
+    true
+}
+
+fn finite_clamp(value: f64, minimum: f64, maximum: f64) -> f64 {
+    if value.is_finite() {
+        value.clamp(minimum, maximum)
+    } else {
+        minimum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{
+        ColorGradient, ColorMode, GradientKey, GradientStop, HourglassShape, ShapeMode, ViewMode,
+    };
+
+    fn snapshot(status: SnapshotTimerStatus, deadline_ms: Option<f64>) -> SnapshotV1 {
+        SnapshotV1 {
+            version: SNAPSHOT_VERSION,
+            revision: 2,
+            source_id: "test".to_string(),
+            duration_ms: 180_000.0,
+            remaining_ms: 90_000.0,
+            status,
+            deadline_ms,
+            run_id: Some("run".to_string()),
+            notified_run_id: None,
+            appearance: HourglassConfig {
+                color: Color::srgba(0.8, 0.6, 0.2, 1.0),
+                ..default()
+            },
+        }
+    }
+
+    #[test]
+    fn running_restore_uses_absolute_deadline() {
+        let mut timer = TimerState::default();
+        let mut deadline = None;
+        let mut config = HourglassConfig::default();
+        assert!(apply_snapshot(
+            snapshot(SnapshotTimerStatus::Running, Some(160_000.0)),
+            100_000.0,
+            &mut timer,
+            &mut deadline,
+            &mut config,
+        ));
+        assert_eq!(timer.remaining, 60.0);
+        assert!(timer.is_running);
+        assert_eq!(deadline, Some(160_000.0));
+    }
+
+    #[test]
+    fn expired_restore_finishes_immediately() {
+        let mut timer = TimerState::default();
+        let mut deadline = None;
+        let mut config = HourglassConfig::default();
+        apply_snapshot(
+            snapshot(SnapshotTimerStatus::Running, Some(99_000.0)),
+            100_000.0,
+            &mut timer,
+            &mut deadline,
+            &mut config,
+        );
+        assert_eq!(timer.remaining, 0.0);
+        assert!(!timer.is_running);
+        assert_eq!(deadline, None);
+    }
+
+    #[test]
+    fn unsupported_snapshot_version_is_ignored() {
+        let mut value = snapshot(SnapshotTimerStatus::Paused, None);
+        value.version = 9;
+        let mut timer = TimerState::default();
+        let before = timer.clone();
+        assert!(!apply_snapshot(
+            value,
+            100_000.0,
+            &mut timer,
+            &mut None,
+            &mut HourglassConfig::default(),
+        ));
+        assert_eq!(timer, before);
+    }
+
+    #[test]
+    fn zero_duration_restore_is_preserved() {
+        let mut value = snapshot(SnapshotTimerStatus::Finished, None);
+        value.duration_ms = 0.0;
+        value.remaining_ms = 0.0;
+        let mut timer = TimerState::default();
+        let mut deadline = None;
+        let mut config = HourglassConfig::default();
+        assert!(apply_snapshot(
+            value,
+            100_000.0,
+            &mut timer,
+            &mut deadline,
+            &mut config,
+        ));
+        assert_eq!(timer.duration, 0.0);
+        assert_eq!(timer.remaining, 0.0);
+        assert!(!timer.is_running);
+        assert_eq!(deadline, None);
+    }
+
+    #[test]
+    fn zero_remaining_serializes_as_finished_even_if_running_is_inconsistent() {
+        assert_eq!(
+            timer_status(&TimerState {
+                duration: 0.0,
+                remaining: 0.0,
+                is_running: true,
+            }),
+            SnapshotTimerStatus::Finished
+        );
+    }
+
+    #[test]
+    fn snapshot_round_trip_preserves_wire_shape() {
+        let value = snapshot(SnapshotTimerStatus::Paused, None);
+        let json = serde_json::to_string(&value).unwrap();
+        assert!(json.contains("\"durationMs\":180000.0"));
+        assert!(json.contains("\"shapeMode\":\"static\""));
+        assert!(json.contains("\"colorMode\":\"static\""));
+        assert!(json.contains("\"shape\":\"classic\""));
+        assert!(json.contains("\"colorRgba\":[0.8,0.6,0.2,1.0]"));
+        assert_eq!(serde_json::from_str::<SnapshotV1>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn resources_round_trip_through_snapshot() {
+        let timer = TimerState {
+            duration: 1500.0,
+            remaining: 600.0,
+            is_running: false,
+        };
+        let config = HourglassConfig {
+            color: Color::srgb(0.1, 0.3, 0.8),
+            shape_type: HourglassShape::SLIM,
+            color_mode: ColorMode::Progress,
+            shape_mode: ShapeMode::Morphing,
+            gradient: ColorGradient {
+                key: GradientKey::Seconds,
+                stops: vec![
+                    GradientStop {
+                        at: 300.0,
+                        color: Color::srgb(0.0, 0.0, 1.0),
+                    },
+                    GradientStop {
+                        at: 0.0,
+                        color: Color::srgb(1.0, 0.0, 1.0),
+                    },
+                ],
+            },
+            view: ViewMode::ThreeD,
+        };
+        let mut restored_timer = TimerState::default();
+        let mut restored_config = HourglassConfig::default();
+        assert!(apply_snapshot(
+            snapshot_from_resources(&timer, None, &config),
+            0.0,
+            &mut restored_timer,
+            &mut None,
+            &mut restored_config,
+        ));
+        assert_eq!(restored_timer, timer);
+        assert_eq!(restored_config.shape_type, HourglassShape::SLIM);
+        assert_eq!(restored_config.shape_mode, ShapeMode::Morphing);
+        assert_eq!(restored_config.color, config.color);
+        assert_eq!(restored_config.color_mode, ColorMode::Progress);
+        assert_eq!(restored_config.gradient, config.gradient);
+        assert_eq!(restored_config.view, ViewMode::ThreeD);
+    }
+
+    #[test]
+    fn extension_appearance_json_is_clamped_on_restore() {
+        let json = r#"{
+            "version": 1,
+            "durationMs": 60000,
+            "remainingMs": 60000,
+            "status": "idle",
+            "deadlineMs": null,
+            "appearance": {
+                "colorMode": "rainbow",
+                "colorRgba": [1.5, 0.5, -0.25, 1.0],
+                "shape": "wide",
+                "shapeMode": "morphing",
+                "gradient": {
+                    "key": "fraction",
+                    "stops": [
+                        {"at": 0.25, "colorRgba": [0.0, 2.0, 0.0, 1.0]},
+                        {"at": 1.5, "colorRgba": [0.0, 0.0, 1.0, 1.0]}
+                    ]
+                }
+            }
+        }"#;
+        let value: SnapshotV1 = serde_json::from_str(json).unwrap();
+        let mut config = HourglassConfig::default();
+        assert!(apply_snapshot(
+            value,
+            0.0,
+            &mut TimerState::default(),
+            &mut None,
+            &mut config,
+        ));
+        assert_eq!(config.color_mode, ColorMode::Rainbow);
+        assert_eq!(config.shape_type, HourglassShape::WIDE);
+        assert_eq!(config.shape_mode, ShapeMode::Morphing);
+        assert_eq!(config.color, Color::srgba(1.0, 0.5, 0.0, 1.0));
+        assert_eq!(
+            config.gradient.stops,
+            [
+                GradientStop {
+                    at: 1.0,
+                    color: Color::srgba(0.0, 0.0, 1.0, 1.0),
+                },
+                GradientStop {
+                    at: 0.25,
+                    color: Color::srgba(0.0, 1.0, 0.0, 1.0),
+                },
+            ]
+        );
+
+        // Snapshots from before gradients keep the default one.
+        let mut value: serde_json::Value = serde_json::from_str(json).unwrap();
+        value["appearance"]
+            .as_object_mut()
+            .unwrap()
+            .remove("gradient");
+        let value: SnapshotV1 = serde_json::from_value(value).unwrap();
+        assert_eq!(value.appearance.gradient, ColorGradient::default());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::resources::TimerState;
+use crate::resources::{MAX_DURATION_SECS, TimerState};
 use bevy::prelude::*;
 
 pub struct TimerPlugin;
@@ -29,6 +29,7 @@ pub enum TimerCommand {
     Reset,
     Restart,
     Adjust(f32),
+    SetDuration(f32),
     Finish,
 }
 
@@ -41,6 +42,7 @@ impl Plugin for TimerPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<TimerCommand>()
             .add_event::<TimerStateChanged>()
+            .init_resource::<TimerClock>()
             .configure_sets(
                 Update,
                 (
@@ -87,6 +89,7 @@ fn apply_timer_command(timer_state: &mut TimerState, command: TimerCommand) -> b
             timer_state.is_running = timer_state.remaining > 0.0;
         }
         TimerCommand::Adjust(seconds) => timer_state.add_time(seconds),
+        TimerCommand::SetDuration(seconds) => timer_state.set_duration(seconds),
         TimerCommand::Finish => {
             timer_state.remaining = 0.0;
             timer_state.is_running = false;
@@ -98,34 +101,147 @@ fn apply_timer_command(timer_state: &mut TimerState, command: TimerCommand) -> b
     command == TimerCommand::Restart || *timer_state != previous
 }
 
+/// Wall-clock time in milliseconds since the Unix epoch. Plain web builds
+/// have no JavaScript bindings, so they count from the first call instead.
+pub(crate) fn now_ms() -> f64 {
+    #[cfg(all(target_arch = "wasm32", feature = "chrome_extension"))]
+    {
+        js_sys::Date::now()
+    }
+
+    #[cfg(all(target_arch = "wasm32", not(feature = "chrome_extension")))]
+    {
+        use bevy::platform::time::Instant;
+        use std::sync::OnceLock;
+        static START: OnceLock<Instant> = OnceLock::new();
+        START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0
+    }
+}
+
+/// The clock the countdown's deadline is kept against; tests swap in one
+/// they can move.
+#[derive(Resource, Clone, Copy)]
+struct TimerClock(fn() -> f64);
+
+impl Default for TimerClock {
+    fn default() -> Self {
+        Self(now_ms)
+    }
+}
+
+/// Counts down against a wall-clock deadline taken when the timer starts or
+/// changes. With power saving a frame can be a whole second apart, or much
+/// longer after the machine sleeps, and summing frame times would both miss
+/// the time spent suspended and drift over long runs. An app that steps time
+/// by hand through `TimeUpdateStrategy` counts down on its real time instead.
 #[cfg(not(all(feature = "chrome_extension", target_arch = "wasm32")))]
 fn update_timer(
-    time: Res<Time>,
+    clock: Res<TimerClock>,
+    strategy: Option<Res<bevy::time::TimeUpdateStrategy>>,
+    real_time: Res<Time<Real>>,
     mut timer_state: ResMut<TimerState>,
     mut changed: EventWriter<TimerStateChanged>,
+    mut deadline: Local<Option<(TimerState, f64)>>,
 ) {
-    if timer_state.is_running && timer_state.remaining > 0.0 {
-        let (remaining, is_running) = tick_countdown(timer_state.remaining, time.delta_secs());
-        timer_state.remaining = remaining;
-        timer_state.is_running = is_running;
-        if !is_running {
-            changed.write(TimerStateChanged(TimerCommand::Finish));
-        }
+    if !timer_state.is_running || timer_state.remaining <= 0.0 {
+        *deadline = None;
+        return;
+    }
+    let now = match strategy.as_deref() {
+        None | Some(bevy::time::TimeUpdateStrategy::Automatic) => (clock.0)(),
+        Some(_) => real_time.elapsed_secs_f64() * 1000.0,
+    };
+    // Anything that changed the timer since the last tick (a command, a
+    // restored session) moves the deadline.
+    let deadline_ms = match &*deadline {
+        Some((ticked, deadline_ms)) if *ticked == *timer_state => *deadline_ms,
+        _ => now + f64::from(timer_state.remaining) * 1000.0,
+    };
+    let (remaining, is_running) = remaining_until(deadline_ms, now);
+    timer_state.remaining = remaining;
+    timer_state.is_running = is_running;
+    *deadline = is_running.then(|| (timer_state.clone(), deadline_ms));
+    if !is_running {
+        changed.write(TimerStateChanged(TimerCommand::Finish));
     }
 }
 
-/// Advance the countdown by `delta` seconds, returning the new remaining time
-/// and whether the timer is still running. Remaining is clamped to 0 and the
-/// timer stops once it reaches 0 (a `delta` larger than `remaining` still
+/// The remaining seconds and whether the timer is still running at `now_ms`
+/// for a countdown ending at `deadline_ms`. Remaining is clamped to 0 and the
+/// timer stops once the deadline is reached (a clock past the deadline still
 /// yields 0, never a negative value).
 #[cfg(any(test, not(all(feature = "chrome_extension", target_arch = "wasm32"))))]
-fn tick_countdown(remaining: f32, delta: f32) -> (f32, bool) {
-    let new_remaining = remaining - delta;
-    if new_remaining <= 0.0 {
+fn remaining_until(deadline_ms: f64, now_ms: f64) -> (f32, bool) {
+    let remaining_ms = deadline_ms - now_ms;
+    if remaining_ms <= 0.0 {
         (0.0, false)
     } else {
-        (new_remaining, true)
+        ((remaining_ms / 1000.0) as f32, true)
+    }
+}
+
+/// Parse a human duration such as `25m`, `1h30m`, `90s`, `1:30:00` or a bare
+/// number of minutes (`25`). Returns seconds, or `None` when the text is not a
+/// duration or exceeds the 24 hour maximum.
+pub fn parse_duration(text: &str) -> Option<f32> {
+    let text = text.trim().to_ascii_lowercase();
+    if text.is_empty() {
+        return None;
     }
+
+    let seconds = if text.contains(':') {
+        // Clock notation: `mm:ss` or `hh:mm:ss`.
+        let parts: Vec<&str> = text.split(':').collect();
+        if parts.len() > 3 {
+            return None;
+        }
+        let mut total = 0.0;
+        for part in parts {
+            let value: u32 = part.parse().ok()?;
+            total = total * 60.0 + value as f32;
+        }
+        total
+    } else if let Ok(minutes) = text.parse::<f32>() {
+        minutes * 60.0
+    } else {
+        // Unit notation: one or more `<number><unit>` groups, e.g. `1h30m`.
+        let mut total = 0.0;
+        let mut number = String::new();
+        for ch in text.chars() {
+            if ch.is_ascii_digit() || ch == '.' {
+                number.push(ch);
+                continue;
+            }
+            if ch.is_whitespace() {
+                continue;
+            }
+            let value: f32 = number.parse().ok()?;
+            number.clear();
+            total += value
+                * match ch {
+                    'h' => 3600.0,
+                    'm' => 60.0,
+                    's' => 1.0,
+                    _ => return None,
+                };
+        }
+        if !number.is_empty() {
+            return None;
+        }
+        total
+    };
+
+    (seconds.is_finite() && (0.0..=MAX_DURATION_SECS).contains(&seconds)).then_some(seconds)
 }
 
 #[cfg(test)]
@@ -167,6 +283,40 @@ mod tests {
         assert!(timer.is_running);
     }
 
+    #[test]
+    fn set_duration_replaces_duration_and_stops() {
+        let mut timer = state(180.0, 42.0, true);
+        assert!(apply_timer_command(
+            &mut timer,
+            TimerCommand::SetDuration(1500.0)
+        ));
+        assert_eq!((timer.duration, timer.remaining), (1500.0, 1500.0));
+        assert!(!timer.is_running);
+        assert!(!apply_timer_command(
+            &mut timer,
+            TimerCommand::SetDuration(1500.0)
+        ));
+    }
+
+    #[test]
+    fn parse_duration_accepts_units_clock_and_bare_minutes() {
+        assert_eq!(parse_duration("25m"), Some(1500.0));
+        assert_eq!(parse_duration("1h30m"), Some(5400.0));
+        assert_eq!(parse_duration("90s"), Some(90.0));
+        assert_eq!(parse_duration("1h 5s"), Some(3605.0));
+        assert_eq!(parse_duration("1:30"), Some(90.0));
+        assert_eq!(parse_duration("1:30:00"), Some(5400.0));
+        assert_eq!(parse_duration("25"), Some(1500.0));
+        assert_eq!(parse_duration(" 2.5M "), Some(150.0));
+    }
+
+    #[test]
+    fn parse_duration_rejects_garbage_and_out_of_range() {
+        for text in ["", "m", "25x", "25m5", "1:2:3:4", "-5m", "25h"] {
+            assert_eq!(parse_duration(text), None, "{text:?}");
+        }
+    }
+
     #[test]
     fn no_op_command_does_not_report_change() {
         let mut timer = state(180.0, 180.0, false);
@@ -189,28 +339,67 @@ mod tests {
 
     #[test]
     fn normal_tick_decrements_and_keeps_running() {
-        assert_eq!(tick_countdown(10.0, 1.0), (9.0, true));
+        assert_eq!(remaining_until(10_000.0, 1_000.0), (9.0, true));
     }
 
     #[test]
     fn exact_zero_stops() {
-        assert_eq!(tick_countdown(1.0, 1.0), (0.0, false));
+        assert_eq!(remaining_until(1_000.0, 1_000.0), (0.0, false));
     }
 
     #[test]
     fn overshoot_clamps_to_zero() {
-        assert_eq!(tick_countdown(0.5, 2.0), (0.0, false));
+        assert_eq!(remaining_until(500.0, 2_000.0), (0.0, false));
     }
 
     #[test]
-    fn zero_delta_leaves_remaining_unchanged() {
-        assert_eq!(tick_countdown(10.0, 0.0), (10.0, true));
+    fn clock_at_start_leaves_remaining_unchanged() {
+        assert_eq!(remaining_until(10_000.0, 0.0), (10.0, true));
     }
 
     #[test]
-    fn small_delta_decrements() {
-        let (remaining, running) = tick_countdown(10.0, 0.016);
+    fn small_step_decrements() {
+        let (remaining, running) = remaining_until(10_000.0, 16.0);
         assert!((remaining - 9.984).abs() < 1e-5);
         assert!(running);
     }
+
+    #[test]
+    fn countdown_follows_the_wall_clock_across_long_gaps() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static NOW_MS: AtomicU64 = AtomicU64::new(1_000_000);
+        fn test_clock() -> f64 {
+            NOW_MS.load(Ordering::Relaxed) as f64
+        }
+        let advance = |ms: u64| NOW_MS.fetch_add(ms, Ordering::Relaxed);
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TimerPlugin))
+            .insert_resource(TimerClock(test_clock))
+            .insert_resource(state(7200.0, 7200.0, false));
+        app.world_mut().send_event(TimerCommand::Start);
+        app.update();
+
+        // A long first stretch, as if the machine slept with no frames.
+        advance(3_600_000);
+        app.update();
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!(timer.remaining, 3600.0);
+        assert!(timer.is_running);
+
+        // Adding time while running moves the deadline with it.
+        app.world_mut().send_event(TimerCommand::Adjust(60.0));
+        app.update();
+        advance(3_660_000);
+        app.update();
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!(timer.remaining, 0.0);
+        assert!(!timer.is_running);
+        let finished = app
+            .world_mut()
+            .resource_mut::<Events<TimerStateChanged>>()
+            .drain()
+            .any(|event| event == TimerStateChanged(TimerCommand::Finish));
+        assert!(finished);
+    }
 }
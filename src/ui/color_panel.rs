@@ -1,65 +1,179 @@
 use crate::resources::{
-    AppearanceStateChanged, COLOR_PALETTE, ColorMode, HourglassConfig, PendingFlip,
+    AppearanceStateChanged, ColorGradient, ColorMode, ColorPalette, HourglassConfig, PendingFlip,
+    RecentColors, SandColorPreview, TimerState, parse_color,
 };
 use crate::timer::{TimerCommand, TimerSystems};
+use crate::ui::command_palette::command_palette_open;
 use crate::ui::{ColorRowMarker, extension_appearance_change_command};
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
 use bevy::prelude::*;
+use bevy::ui::{FocusPolicy, RelativeCursorPosition};
+use bevy::window::PrimaryWindow;
 use rand::Rng;
 
+/// Cells across and down the picker's saturation/value square.
+const SAT_VAL_COLUMNS: usize = 16;
+const SAT_VAL_ROWS: usize = 10;
+/// Cells along the hue and alpha strips.
+const STRIP_CELLS: usize = 16;
+const PICKER_CELL_SIZE: f32 = 12.0;
+
 pub struct ColorPanelPlugin;
 
 impl Plugin for ColorPanelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PostStartup, spawn_color_buttons)
+        app.init_resource::<ColorPalette>()
+            .init_resource::<RecentColors>()
+            .init_resource::<ColorPickerState>()
+            .init_resource::<SandColorPreview>()
+            .register_type::<ColorPalette>()
+            .register_type::<RecentColors>()
+            .register_type::<SandColorPreview>()
+            .add_event::<ColorPickerRequest>()
+            .add_systems(Startup, spawn_color_picker)
+            .add_systems(PostStartup, spawn_color_buttons)
             .add_systems(
                 Update,
                 (
-                    handle_color_button_clicks,
+                    highlight_color_buttons,
+                    edit_palette_with_mouse,
                     handle_random_color_button,
                     handle_rainbow_color_button,
+                    handle_progress_color_button,
+                )
+                    .in_set(TimerSystems::Input),
+            )
+            .add_systems(Update, record_recent_colors.in_set(TimerSystems::Observe))
+            .add_systems(Update, rebuild_color_swatches.after(TimerSystems::Observe))
+            .add_systems(
+                Update,
+                (
+                    (
+                        handle_color_picker_buttons,
+                        drag_color_picker,
+                        handle_color_picker_keys.run_if(not(command_palette_open)),
+                    ),
+                    apply_color_picker_requests,
+                    preview_picked_color,
                 )
+                    .chain()
                     .in_set(TimerSystems::Input),
             )
-            .add_systems(Update, (update_rainbow_color,));
+            .add_systems(Update, update_color_picker_view.after(TimerSystems::Input))
+            .add_systems(Update, (update_rainbow_color,))
+            .add_systems(
+                Update,
+                update_progress_color
+                    .after(TimerSystems::Tick)
+                    .before(TimerSystems::Observe),
+            );
     }
 }
 
+/// Where a swatch in the colour row comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SwatchSlot {
+    Palette(usize),
+    Recent(usize),
+}
+
 #[derive(Component)]
 struct ColorButton {
     color: Color,
+    slot: SwatchSlot,
 }
 
+/// Everything `rebuild_color_swatches` replaces: the swatches and the
+/// divider in front of the recent colours.
+#[derive(Component)]
+struct SwatchRowItem;
+
 #[derive(Component)]
 struct RandomColorButton;
 
 #[derive(Component)]
 struct RainbowColorButton;
 
-fn spawn_color_buttons(mut commands: Commands, query: Query<Entity, With<ColorRowMarker>>) {
-    // Find the color row container
-    if let Ok(panel_entity) = query.single() {
-        commands.entity(panel_entity).with_children(|parent| {
-            // Add color buttons in horizontal layout
-            for (i, &color) in COLOR_PALETTE.iter().enumerate() {
-                parent.spawn((
-                    Name::new(format!("Color Button {i}")),
-                    ColorButton { color },
-                    Button,
+#[derive(Component)]
+struct ProgressColorButton;
+
+fn color_swatch(slot: SwatchSlot, color: Color) -> impl Bundle {
+    let name = match slot {
+        SwatchSlot::Palette(index) => format!("Color Button {index}"),
+        SwatchSlot::Recent(index) => format!("Recent Color Button {index}"),
+    };
+    (
+        Name::new(name),
+        ColorButton { color, slot },
+        SwatchRowItem,
+        Button,
+        // Locates drops and right-clicks when editing the palette
+        RelativeCursorPosition::default(),
+        Node {
+            width: Val::Px(20.0),
+            height: Val::Px(20.0),
+            margin: UiRect::horizontal(Val::Px(2.0)),
+            border: UiRect::all(Val::Px(1.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            flex_shrink: 0.0, // Prevent shrinking
+            ..default()
+        },
+        BackgroundColor(color),
+        BorderColor(Color::WHITE),
+    )
+}
+
+/// The palette swatches followed by the recent colours, which are set off
+/// by a thin divider.
+fn spawn_swatches(
+    commands: &mut Commands,
+    palette: &ColorPalette,
+    recent: &RecentColors,
+) -> Vec<Entity> {
+    let mut swatches: Vec<Entity> = palette
+        .0
+        .iter()
+        .enumerate()
+        .map(|(i, &(_, color))| {
+            commands
+                .spawn(color_swatch(SwatchSlot::Palette(i), color))
+                .id()
+        })
+        .collect();
+    if !recent.0.is_empty() {
+        swatches.push(
+            commands
+                .spawn((
+                    Name::new("Recent Colors Divider"),
+                    SwatchRowItem,
                     Node {
-                        width: Val::Px(20.0),
-                        height: Val::Px(20.0),
-                        margin: UiRect::horizontal(Val::Px(2.0)),
-                        border: UiRect::all(Val::Px(1.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        flex_shrink: 0.0, // Prevent shrinking
+                        width: Val::Px(1.0),
+                        height: Val::Px(16.0),
+                        margin: UiRect::horizontal(Val::Px(3.0)),
+                        flex_shrink: 0.0,
                         ..default()
                     },
-                    BackgroundColor(color),
-                    BorderColor(Color::WHITE),
-                ));
-            }
+                    BackgroundColor(Color::srgb(0.6, 0.6, 0.6)),
+                ))
+                .id(),
+        );
+    }
+    swatches.extend(recent.0.iter().enumerate().map(|(i, &color)| {
+        commands
+            .spawn(color_swatch(SwatchSlot::Recent(i), color))
+            .id()
+    }));
+    swatches
+}
 
+/// The random, rainbow and custom colour buttons. The swatches are inserted
+/// in front of them by `rebuild_color_swatches`.
+fn spawn_color_buttons(mut commands: Commands, query: Query<Entity, With<ColorRowMarker>>) {
+    // Find the color row container
+    if let Ok(panel_entity) = query.single() {
+        commands.entity(panel_entity).with_children(|parent| {
             // Add Random Color Button with multi-colored squares pattern
             parent
                 .spawn((
@@ -187,29 +301,213 @@ fn spawn_color_buttons(mut commands: Commands, query: Query<Entity, With<ColorRo
                         ));
                     }
                 });
+
+            // Progress colours: stripes running through the default gradient
+            // from a full timer on the left to the deadline on the right.
+            parent
+                .spawn((
+                    Name::new("Progress Color Button"),
+                    ProgressColorButton,
+                    Button,
+                    Node {
+                        width: Val::Px(28.0),
+                        height: Val::Px(20.0),
+                        margin: UiRect::horizontal(Val::Px(2.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        flex_shrink: 0.0,
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+                    BorderColor(Color::WHITE),
+                ))
+                .with_children(|parent| {
+                    let gradient = ColorGradient::default();
+                    for stripe in 0..13 {
+                        parent.spawn((
+                            Node {
+                                width: Val::Px(2.0),
+                                height: Val::Px(18.0),
+                                ..default()
+                            },
+                            BackgroundColor(gradient.sample(1.0 - stripe as f32 / 12.0)),
+                        ));
+                    }
+                });
+
+            // Opens the custom colour picker
+            parent.spawn((
+                Name::new("Custom Color Button"),
+                PickerButton::Toggle,
+                Button,
+                Node {
+                    width: Val::Px(20.0),
+                    height: Val::Px(20.0),
+                    margin: UiRect::horizontal(Val::Px(2.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_shrink: 0.0,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+                BorderColor(Color::WHITE),
+                children![(
+                    Text::new("+"),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                )],
+            ));
         });
     }
 }
 
-fn handle_color_button_clicks(
-    mut interaction_query: Query<
-        (&Interaction, &ColorButton, &mut BorderColor),
-        (Changed<Interaction>, With<Button>),
-    >,
+/// Replace the swatches in place whenever the palette or the recent colours
+/// change (edited in the row, restored from storage or set by the config
+/// file), keeping the random, rainbow and custom buttons after them.
+fn rebuild_color_swatches(
+    mut commands: Commands,
+    palette: Res<ColorPalette>,
+    recent: Res<RecentColors>,
+    row_query: Query<Entity, With<ColorRowMarker>>,
+    item_query: Query<Entity, With<SwatchRowItem>>,
+) {
+    if !palette.is_changed() && !recent.is_changed() {
+        return;
+    }
+    let Ok(row) = row_query.single() else {
+        return;
+    };
+    for item in &item_query {
+        commands.entity(item).despawn();
+    }
+    let swatches = spawn_swatches(&mut commands, &palette, &recent);
+    commands.entity(row).insert_children(0, &swatches);
+}
+
+/// How far the pointer may move, in logical pixels, before pressing a swatch
+/// counts as dragging it rather than clicking it.
+const SWATCH_DRAG_THRESHOLD: f32 = 4.0;
+
+/// A swatch the left button went down on, and where the pointer was then.
+struct SwatchPress {
+    slot: SwatchSlot,
+    color: Color,
+    cursor: Option<Vec2>,
+}
+
+/// Click a swatch to use its colour for the sand. Drag it onto a palette
+/// swatch to move it there instead (a recent colour is added to the palette
+/// at that spot); right-click a swatch to remove it.
+fn edit_palette_with_mouse(
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    swatch_query: Query<(&ColorButton, &RelativeCursorPosition)>,
+    mut palette: ResMut<ColorPalette>,
+    mut recent: ResMut<RecentColors>,
     mut config: ResMut<HourglassConfig>,
     mut pending_flip: ResMut<PendingFlip>,
     mut timer_commands: EventWriter<TimerCommand>,
     mut appearance_changed: EventWriter<AppearanceStateChanged>,
+    mut pressed: Local<Option<SwatchPress>>,
 ) {
-    for (interaction, color_button, mut border_color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                config.color = color_button.color;
+    let under_cursor = || {
+        swatch_query
+            .iter()
+            .find(|(_, cursor)| cursor.mouse_over())
+            .map(|(button, _)| (button.slot, button.color))
+    };
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    if mouse.just_pressed(MouseButton::Left) {
+        *pressed = under_cursor().map(|(slot, color)| SwatchPress {
+            slot,
+            color,
+            cursor,
+        });
+    }
+    if mouse.just_released(MouseButton::Left)
+        && let Some(press) = pressed.take()
+    {
+        let dragged = press
+            .cursor
+            .zip(cursor)
+            .is_some_and(|(from, to)| from.distance(to) > SWATCH_DRAG_THRESHOLD);
+        match under_cursor() {
+            Some((slot, _)) if slot == press.slot && !dragged => {
+                config.color = press.color;
                 config.color_mode = ColorMode::Static;
                 if let Some(command) = extension_appearance_change_command(&mut pending_flip) {
                     timer_commands.write(command);
                 }
                 appearance_changed.write_default();
+            }
+            Some((SwatchSlot::Palette(target), _)) => match press.slot {
+                SwatchSlot::Palette(from) if from != target => palette.move_swatch(from, target),
+                SwatchSlot::Palette(_) => {}
+                SwatchSlot::Recent(from) => {
+                    let color = recent.0.remove(from);
+                    if palette.add(color) {
+                        let added = palette.0.len() - 1;
+                        palette.move_swatch(added, target);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    if mouse.just_pressed(MouseButton::Right) {
+        match under_cursor() {
+            Some((SwatchSlot::Palette(index), _)) if !palette.remove(index) => {
+                warn!("The palette needs at least one colour");
+            }
+            Some((SwatchSlot::Palette(_), _)) => {}
+            Some((SwatchSlot::Recent(index), _)) => {
+                recent.0.remove(index);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Remember static colours that were applied but are not in the palette.
+fn record_recent_colors(
+    mut appearance_changes: EventReader<AppearanceStateChanged>,
+    config: Res<HourglassConfig>,
+    palette: Res<ColorPalette>,
+    mut recent: ResMut<RecentColors>,
+) {
+    if appearance_changes.read().count() == 0 {
+        return;
+    }
+    if config.color_mode == ColorMode::Static
+        && !palette.contains(config.color)
+        && recent.0.first() != Some(&config.color)
+    {
+        recent.record(config.color);
+    }
+}
+
+/// Outline the swatch under the pointer; `edit_palette_with_mouse` applies
+/// its colour once the press ends without a drag.
+fn highlight_color_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &mut BorderColor),
+        (Changed<Interaction>, With<Button>, With<ColorButton>),
+    >,
+) {
+    for (interaction, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
                 *border_color = BorderColor(Color::srgb(0.0, 1.0, 0.0));
             }
             Interaction::Hovered => {
@@ -233,7 +531,7 @@ fn color_dist_sq(a: Srgba, b: Srgba) -> f32 {
 
 /// Pick a random sRGB color that is at least `min_dist_sq` (squared RGB
 /// distance) away from `current`, re-rolling until the constraint is met.
-fn pick_distinct_color(current: Srgba, min_dist_sq: f32, rng: &mut impl Rng) -> Srgba {
+pub(crate) fn pick_distinct_color(current: Srgba, min_dist_sq: f32, rng: &mut impl Rng) -> Srgba {
     let mut new_color = Srgba::rgb(
         rng.gen_range(0.0..1.0),
         rng.gen_range(0.0..1.0),
@@ -317,6 +615,36 @@ fn handle_rainbow_color_button(
     }
 }
 
+fn handle_progress_color_button(
+    mut interaction_query: Query<
+        (&Interaction, &mut BorderColor),
+        (Changed<Interaction>, With<ProgressColorButton>),
+    >,
+    mut config: ResMut<HourglassConfig>,
+    mut pending_flip: ResMut<PendingFlip>,
+    mut timer_commands: EventWriter<TimerCommand>,
+    mut appearance_changed: EventWriter<AppearanceStateChanged>,
+) {
+    for (interaction, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                config.color_mode = ColorMode::Progress;
+                if let Some(command) = extension_appearance_change_command(&mut pending_flip) {
+                    timer_commands.write(command);
+                }
+                appearance_changed.write_default();
+                *border_color = BorderColor(Color::srgb(0.0, 1.0, 0.0));
+            }
+            Interaction::Hovered => {
+                *border_color = BorderColor(Color::srgb(0.8, 0.8, 0.8));
+            }
+            Interaction::None => {
+                *border_color = BorderColor(Color::WHITE);
+            }
+        }
+    }
+}
+
 fn update_rainbow_color(time: Res<Time>, mut config: ResMut<HourglassConfig>) {
     if config.color_mode == ColorMode::Rainbow {
         // Cycle through hue over time (0-360 degrees)
@@ -327,6 +655,25 @@ fn update_rainbow_color(time: Res<Time>, mut config: ResMut<HourglassConfig>) {
     }
 }
 
+/// Smallest Oklab distance worth redrawing the sand for. The gradient drifts
+/// a little every frame; waiting for a visible step keeps recolouring rare.
+const PROGRESS_COLOR_STEP: f32 = 0.004;
+
+fn update_progress_color(timer_state: Res<TimerState>, mut config: ResMut<HourglassConfig>) {
+    if config.color_mode != ColorMode::Progress {
+        return;
+    }
+    let target = config.gradient.color_at(&timer_state);
+    if oklab_distance(config.color, target) > PROGRESS_COLOR_STEP {
+        config.color = target;
+    }
+}
+
+fn oklab_distance(a: Color, b: Color) -> f32 {
+    let (a, b) = (Oklaba::from(a), Oklaba::from(b));
+    Vec3::new(a.lightness - b.lightness, a.a - b.a, a.b - b.b).length()
+}
+
 /// Hue (in degrees, 0-360) for the rainbow animation at the given elapsed
 /// time. Completes one full cycle every 6 seconds.
 fn rainbow_hue(elapsed_secs: f32) -> f32 {
@@ -357,6 +704,588 @@ fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> Color {
     Color::srgb(r + m, g + m, b + m)
 }
 
+// Helper function to convert HSV to RGB
+pub(crate) fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> Color {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let c = value * saturation;
+    let x = c * (1.0 - (sector % 2.0 - 1.0).abs());
+    let m = value - c;
+
+    let (r, g, b) = match sector as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    Color::srgb(r + m, g + m, b + m)
+}
+
+/// Inverse of `hsv_to_rgb`: (hue in degrees, saturation, value). Greys have
+/// no hue and report 0.
+pub(crate) fn rgb_to_hsv(color: Color) -> (f32, f32, f32) {
+    let Srgba {
+        red, green, blue, ..
+    } = color.to_srgba();
+    let max = red.max(green).max(blue);
+    let delta = max - red.min(green).min(blue);
+
+    let hue = if delta <= f32::EPSILON {
+        0.0
+    } else if max == red {
+        60.0 * ((green - blue) / delta).rem_euclid(6.0)
+    } else if max == green {
+        60.0 * ((blue - red) / delta + 2.0)
+    } else {
+        60.0 * ((red - green) / delta + 4.0)
+    };
+    let saturation = if max <= 0.0 { 0.0 } else { delta / max };
+
+    (hue, saturation, max)
+}
+
+/// Parse the picker's text field: anything `parse_color` accepts (including
+/// `#rrggbbaa`), or `r, g, b[, a]` channels in 0-255 with an optional
+/// `rgb(...)` wrapper.
+fn parse_picker_text(text: &str) -> Option<Color> {
+    parse_color(text).or_else(|| {
+        let channels: Vec<u8> = text
+            .trim()
+            .trim_start_matches("rgba")
+            .trim_start_matches("rgb")
+            .trim_matches(|c| c == '(' || c == ')')
+            .split([',', ' '])
+            .filter(|channel| !channel.is_empty())
+            .map(|channel| channel.parse().ok())
+            .collect::<Option<_>>()?;
+        match channels[..] {
+            [red, green, blue] => Some(Color::srgb_u8(red, green, blue)),
+            [red, green, blue, alpha] => Some(Color::srgba_u8(red, green, blue, alpha)),
+            _ => None,
+        }
+    })
+}
+
+/// The custom colour picker. While it is open the picked colour is previewed
+/// on the hourglass through `SandColorPreview`.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub(crate) struct ColorPickerState {
+    hue: f32,
+    saturation: f32,
+    value: f32,
+    alpha: f32,
+    /// Contents of the hex/RGB field while it has keyboard focus.
+    text: Option<String>,
+    open: bool,
+}
+
+impl Default for ColorPickerState {
+    fn default() -> Self {
+        Self {
+            hue: 0.0,
+            saturation: 1.0,
+            value: 1.0,
+            alpha: 1.0,
+            text: None,
+            open: false,
+        }
+    }
+}
+
+impl ColorPickerState {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn color(&self) -> Color {
+        hsv_to_rgb(self.hue, self.saturation, self.value).with_alpha(self.alpha)
+    }
+
+    fn set_color(&mut self, color: Color) {
+        let (hue, saturation, value) = rgb_to_hsv(color);
+        // Keep the current hue for greys so the square does not jump to red.
+        if saturation > 0.0 && value > 0.0 {
+            self.hue = hue;
+        }
+        self.saturation = saturation;
+        self.value = value;
+        self.alpha = color.alpha();
+    }
+}
+
+/// Run condition: the picker's text field is consuming keyboard input.
+pub(crate) fn color_picker_typing(state: Option<Res<ColorPickerState>>) -> bool {
+    state.is_some_and(|state| state.text.is_some())
+}
+
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorPickerRequest {
+    Open,
+    Apply,
+    AddToPalette,
+    Cancel,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum PickerButton {
+    /// The "+" chip in the colour row.
+    Toggle,
+    Text,
+    Apply,
+    AddToPalette,
+    Cancel,
+}
+
+/// Draggable parts of the picker, read through `RelativeCursorPosition`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum PickerArea {
+    SaturationValue,
+    Hue,
+    Alpha,
+}
+
+/// Where the current pick sits within a `PickerArea`.
+#[derive(Component)]
+struct PickerMarker(PickerArea);
+
+/// Cells recoloured as the pick changes.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+enum PickerSwatch {
+    SaturationValue { saturation: f32, value: f32 },
+    Alpha(f32),
+    Preview,
+}
+
+#[derive(Component)]
+struct ColorPickerRoot;
+
+#[derive(Component)]
+struct PickerText;
+
+fn picker_area(area: PickerArea, columns: usize, rows: usize) -> impl Bundle {
+    (
+        area,
+        Interaction::default(),
+        RelativeCursorPosition::default(),
+        Node {
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::px(columns as u16, PICKER_CELL_SIZE),
+            grid_template_rows: RepeatedGridTrack::px(rows as u16, PICKER_CELL_SIZE),
+            ..default()
+        },
+    )
+}
+
+fn picker_marker(area: PickerArea) -> impl Bundle {
+    let size = match area {
+        PickerArea::SaturationValue => Vec2::splat(8.0),
+        PickerArea::Hue | PickerArea::Alpha => Vec2::new(3.0, PICKER_CELL_SIZE),
+    };
+    (
+        PickerMarker(area),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(size.x),
+            height: Val::Px(size.y),
+            margin: UiRect::new(
+                Val::Px(-size.x / 2.0),
+                Val::ZERO,
+                Val::Px(-size.y / 2.0),
+                Val::ZERO,
+            ),
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        BorderColor(Color::WHITE),
+    )
+}
+
+fn picker_chip(label: &str, button: PickerButton) -> impl Bundle {
+    (
+        button,
+        Button,
+        Node {
+            flex_grow: 1.0,
+            height: Val::Px(22.0),
+            border: UiRect::all(Val::Px(1.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+        BorderColor(Color::WHITE),
+        children![(
+            Text::new(label),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )],
+    )
+}
+
+fn spawn_color_picker(mut commands: Commands) {
+    let cell = |color: Color| {
+        (
+            Node {
+                width: Val::Px(PICKER_CELL_SIZE),
+                height: Val::Px(PICKER_CELL_SIZE),
+                ..default()
+            },
+            BackgroundColor(color),
+        )
+    };
+
+    commands
+        .spawn((
+            Name::new("Color Picker"),
+            ColorPickerRoot,
+            Interaction::default(),
+            FocusPolicy::Block,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(34.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(
+                    -(SAT_VAL_COLUMNS as f32 * PICKER_CELL_SIZE) / 2.0 - 8.0,
+                )),
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(7.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BorderColor(Color::srgb(0.65, 0.65, 0.65)),
+            ZIndex(190),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(picker_area(
+                    PickerArea::SaturationValue,
+                    SAT_VAL_COLUMNS,
+                    SAT_VAL_ROWS,
+                ))
+                .with_children(|area| {
+                    for row in 0..SAT_VAL_ROWS {
+                        for column in 0..SAT_VAL_COLUMNS {
+                            let saturation = (column as f32 + 0.5) / SAT_VAL_COLUMNS as f32;
+                            let value = 1.0 - (row as f32 + 0.5) / SAT_VAL_ROWS as f32;
+                            area.spawn((
+                                cell(hsv_to_rgb(0.0, saturation, value)),
+                                PickerSwatch::SaturationValue { saturation, value },
+                            ));
+                        }
+                    }
+                    area.spawn(picker_marker(PickerArea::SaturationValue));
+                });
+
+            parent
+                .spawn(picker_area(PickerArea::Hue, STRIP_CELLS, 1))
+                .with_children(|strip| {
+                    for index in 0..STRIP_CELLS {
+                        let hue = (index as f32 + 0.5) / STRIP_CELLS as f32 * 360.0;
+                        strip.spawn(cell(hsv_to_rgb(hue, 1.0, 1.0)));
+                    }
+                    strip.spawn(picker_marker(PickerArea::Hue));
+                });
+
+            parent
+                .spawn(picker_area(PickerArea::Alpha, STRIP_CELLS, 1))
+                .with_children(|strip| {
+                    for index in 0..STRIP_CELLS {
+                        let alpha = (index as f32 + 0.5) / STRIP_CELLS as f32;
+                        strip.spawn((
+                            cell(Color::WHITE.with_alpha(alpha)),
+                            PickerSwatch::Alpha(alpha),
+                        ));
+                    }
+                    strip.spawn(picker_marker(PickerArea::Alpha));
+                });
+
+            parent.spawn((
+                Node {
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                },
+                children![
+                    (
+                        PickerSwatch::Preview,
+                        Node {
+                            width: Val::Px(24.0),
+                            height: Val::Px(24.0),
+                            border: UiRect::all(Val::Px(1.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::WHITE),
+                        BorderColor(Color::WHITE),
+                    ),
+                    (
+                        Name::new("Color Picker Text Field"),
+                        PickerButton::Text,
+                        Button,
+                        Node {
+                            flex_grow: 1.0,
+                            height: Val::Px(24.0),
+                            border: UiRect::all(Val::Px(1.0)),
+                            padding: UiRect::horizontal(Val::Px(4.0)),
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.05, 0.05, 0.05)),
+                        BorderColor(Color::WHITE),
+                        children![(
+                            PickerText,
+                            Text::new(""),
+                            TextFont {
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        )],
+                    ),
+                ],
+            ));
+
+            parent.spawn((
+                Node {
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                },
+                children![
+                    picker_chip("Apply", PickerButton::Apply),
+                    picker_chip("+ Palette", PickerButton::AddToPalette),
+                    picker_chip("Cancel", PickerButton::Cancel),
+                ],
+            ));
+        });
+}
+
+fn handle_color_picker_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &PickerButton, &mut BorderColor),
+        Changed<Interaction>,
+    >,
+    mut state: ResMut<ColorPickerState>,
+    mut requests: EventWriter<ColorPickerRequest>,
+) {
+    for (interaction, button, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match button {
+                    PickerButton::Toggle if state.is_open() => {
+                        requests.write(ColorPickerRequest::Cancel);
+                    }
+                    PickerButton::Toggle => {
+                        requests.write(ColorPickerRequest::Open);
+                    }
+                    PickerButton::Text => state.text = Some(String::new()),
+                    PickerButton::Apply => {
+                        requests.write(ColorPickerRequest::Apply);
+                    }
+                    PickerButton::AddToPalette => {
+                        requests.write(ColorPickerRequest::AddToPalette);
+                    }
+                    PickerButton::Cancel => {
+                        requests.write(ColorPickerRequest::Cancel);
+                    }
+                }
+                *border_color = BorderColor(Color::srgb(0.0, 1.0, 0.0));
+            }
+            Interaction::Hovered => {
+                *border_color = BorderColor(Color::srgb(0.8, 0.8, 0.8));
+            }
+            Interaction::None => {
+                *border_color = BorderColor(Color::WHITE);
+            }
+        }
+    }
+}
+
+/// Pick from the square and strips while the mouse is held on them.
+fn drag_color_picker(
+    areas: Query<(&Interaction, &RelativeCursorPosition, &PickerArea)>,
+    mut state: ResMut<ColorPickerState>,
+) {
+    if !state.is_open() {
+        return;
+    }
+    for (interaction, cursor, area) in &areas {
+        let (Interaction::Pressed, Some(position)) = (interaction, cursor.normalized) else {
+            continue;
+        };
+        let position = position.clamp(Vec2::ZERO, Vec2::ONE);
+        let mut picked = state.clone();
+        match area {
+            PickerArea::SaturationValue => {
+                picked.saturation = position.x;
+                picked.value = 1.0 - position.y;
+            }
+            PickerArea::Hue => picked.hue = position.x * 360.0,
+            PickerArea::Alpha => picked.alpha = position.x,
+        }
+        picked.text = None;
+        state.set_if_neq(picked);
+    }
+}
+
+/// Typing into the hex/RGB field; otherwise Enter applies and Escape
+/// cancels the open picker.
+fn handle_color_picker_keys(
+    mut keyboard: EventReader<KeyboardInput>,
+    mut state: ResMut<ColorPickerState>,
+    mut requests: EventWriter<ColorPickerRequest>,
+) {
+    if !state.is_open() {
+        keyboard.clear();
+        return;
+    }
+
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let Some(text) = state.text.as_mut() else {
+            match event.logical_key {
+                Key::Enter => {
+                    requests.write(ColorPickerRequest::Apply);
+                }
+                Key::Escape => {
+                    requests.write(ColorPickerRequest::Cancel);
+                }
+                _ => {}
+            }
+            continue;
+        };
+        match &event.logical_key {
+            Key::Enter => match parse_picker_text(text) {
+                Some(color) => {
+                    state.set_color(color);
+                    state.text = None;
+                }
+                None => warn!("`{text}` is not a colour"),
+            },
+            Key::Escape => state.text = None,
+            Key::Backspace => {
+                text.pop();
+            }
+            Key::Space => text.push(' '),
+            Key::Character(characters) => text.push_str(characters),
+            _ => {}
+        }
+    }
+}
+
+/// Opening starts from the current colour; Apply announces the picked colour
+/// like a swatch click, Cancel just closes. Adding to the palette keeps the
+/// picker open.
+fn apply_color_picker_requests(
+    mut requests: EventReader<ColorPickerRequest>,
+    mut state: ResMut<ColorPickerState>,
+    mut palette: ResMut<ColorPalette>,
+    mut recent: ResMut<RecentColors>,
+    mut config: ResMut<HourglassConfig>,
+    mut pending_flip: ResMut<PendingFlip>,
+    mut timer_commands: EventWriter<TimerCommand>,
+    mut appearance_changed: EventWriter<AppearanceStateChanged>,
+) {
+    for request in requests.read() {
+        match request {
+            ColorPickerRequest::Open if !state.is_open() => {
+                state.set_color(config.color);
+                state.text = None;
+                state.open = true;
+            }
+            ColorPickerRequest::Open => {}
+            ColorPickerRequest::Apply => {
+                if !state.is_open() {
+                    continue;
+                }
+                state.open = false;
+                state.text = None;
+                config.color = state.color();
+                config.color_mode = ColorMode::Static;
+                if let Some(command) = extension_appearance_change_command(&mut pending_flip) {
+                    timer_commands.write(command);
+                }
+                appearance_changed.write_default();
+            }
+            ColorPickerRequest::AddToPalette => {
+                let color = state.color();
+                if palette.add(color) {
+                    recent.0.retain(|&recent| recent != color);
+                }
+            }
+            ColorPickerRequest::Cancel => {
+                state.open = false;
+                state.text = None;
+            }
+        }
+    }
+}
+
+/// Show the pick on the hourglass while the picker is open. The preview
+/// leaves `HourglassConfig` alone, so persistence and the extension only see
+/// the applied colour.
+fn preview_picked_color(state: Res<ColorPickerState>, mut preview: ResMut<SandColorPreview>) {
+    if !state.is_changed() {
+        return;
+    }
+    preview.set_if_neq(SandColorPreview(state.is_open().then(|| state.color())));
+}
+
+fn update_color_picker_view(
+    state: Res<ColorPickerState>,
+    mut root_query: Query<&mut Node, With<ColorPickerRoot>>,
+    mut marker_query: Query<(&PickerMarker, &mut Node), Without<ColorPickerRoot>>,
+    mut swatch_query: Query<(&PickerSwatch, &mut BackgroundColor)>,
+    mut text_query: Query<&mut Text, With<PickerText>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    if let Ok(mut root) = root_query.single_mut() {
+        root.display = if state.is_open() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    if !state.is_open() {
+        return;
+    }
+
+    let color = state.color();
+    for (marker, mut node) in &mut marker_query {
+        let (x, y) = match marker.0 {
+            PickerArea::SaturationValue => (state.saturation, 1.0 - state.value),
+            PickerArea::Hue => (state.hue / 360.0, 0.5),
+            PickerArea::Alpha => (state.alpha, 0.5),
+        };
+        node.left = Val::Percent(x * 100.0);
+        node.top = Val::Percent(y * 100.0);
+    }
+    for (swatch, mut background) in &mut swatch_query {
+        background.0 = match *swatch {
+            PickerSwatch::SaturationValue { saturation, value } => {
+                hsv_to_rgb(state.hue, saturation, value)
+            }
+            PickerSwatch::Alpha(alpha) => color.with_alpha(alpha),
+            PickerSwatch::Preview => color,
+        };
+    }
+    if let Ok(mut text) = text_query.single_mut() {
+        text.0 = match &state.text {
+            Some(typed) => format!("{typed}_"),
+            None => color.to_srgba().to_hex(),
+        };
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,6 +1306,194 @@ mod tests {
         assert_abs_diff_eq!(b, expected.2, epsilon = 1e-5);
     }
 
+    fn swatch_colors(app: &mut App) -> Vec<Color> {
+        let world = app.world_mut();
+        let row = world
+            .query_filtered::<Entity, With<ColorRowMarker>>()
+            .single(world)
+            .unwrap();
+        let children: Vec<Entity> = world.get::<Children>(row).unwrap().to_vec();
+        children
+            .into_iter()
+            .filter_map(|child| world.get::<ColorButton>(child).map(|button| button.color))
+            .collect()
+    }
+
+    fn color_panel_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(crate::timer::TimerPlugin)
+            .init_resource::<HourglassConfig>()
+            .init_resource::<crate::resources::TimerState>()
+            .init_resource::<PendingFlip>()
+            .add_event::<AppearanceStateChanged>()
+            .add_event::<KeyboardInput>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .add_plugins(ColorPanelPlugin);
+        app.world_mut().spawn((ColorRowMarker, Node::default()));
+        app.update();
+        app
+    }
+
+    fn appearance_changes(app: &App) -> usize {
+        app.world()
+            .resource::<Events<AppearanceStateChanged>>()
+            .len()
+    }
+
+    /// Put the cursor over the swatch in `slot` and nowhere else.
+    fn hover_swatch(app: &mut App, slot: SwatchSlot) {
+        let world = app.world_mut();
+        let mut swatches = world.query::<(&ColorButton, &mut RelativeCursorPosition)>();
+        for (button, mut cursor) in swatches.iter_mut(world) {
+            *cursor = RelativeCursorPosition {
+                normalized_visible_node_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+                normalized: (button.slot == slot).then_some(Vec2::splat(0.5)),
+            };
+        }
+    }
+
+    fn click(app: &mut App, button: MouseButton, release: bool) {
+        let mut mouse = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse.clear();
+        if release {
+            mouse.release(button);
+        } else {
+            mouse.press(button);
+        }
+        app.update();
+    }
+
+    fn type_into_picker(app: &mut App, keys: &[Key]) {
+        for key in keys {
+            app.world_mut().send_event(KeyboardInput {
+                key_code: KeyCode::Unidentified(bevy::input::keyboard::NativeKeyCode::Unidentified),
+                logical_key: key.clone(),
+                state: ButtonState::Pressed,
+                text: None,
+                repeat: false,
+                window: Entity::PLACEHOLDER,
+            });
+        }
+        app.update();
+    }
+
+    #[test]
+    fn palette_changes_rebuild_swatches_before_special_buttons() {
+        let mut app = color_panel_app();
+        assert_eq!(
+            swatch_colors(&mut app),
+            ColorPalette::default()
+                .0
+                .iter()
+                .map(|e| e.1)
+                .collect::<Vec<_>>()
+        );
+
+        let teal = Color::srgb(0.0, 0.5, 0.5);
+        app.world_mut().resource_mut::<ColorPalette>().0 = vec![("teal".to_string(), teal)];
+        app.update();
+        assert_eq!(swatch_colors(&mut app), vec![teal]);
+
+        let world = app.world_mut();
+        let row = world
+            .query_filtered::<Entity, With<ColorRowMarker>>()
+            .single(world)
+            .unwrap();
+        let first = world.get::<Children>(row).unwrap()[0];
+        assert!(world.get::<ColorButton>(first).is_some());
+    }
+
+    #[test]
+    fn dragging_and_right_clicking_swatches_edits_the_palette() {
+        let mut app = color_panel_app();
+        let builtin: Vec<Color> = ColorPalette::default().0.iter().map(|e| e.1).collect();
+
+        hover_swatch(&mut app, SwatchSlot::Palette(3));
+        click(&mut app, MouseButton::Left, false);
+        hover_swatch(&mut app, SwatchSlot::Palette(0));
+        click(&mut app, MouseButton::Left, true);
+        let colors = swatch_colors(&mut app);
+        assert_eq!(colors[0], builtin[3]);
+        assert_eq!(colors[1], builtin[0]);
+        assert_eq!(
+            *app.world().resource::<HourglassConfig>(),
+            HourglassConfig::default()
+        );
+        assert_eq!(appearance_changes(&app), 0);
+
+        hover_swatch(&mut app, SwatchSlot::Palette(0));
+        click(&mut app, MouseButton::Right, false);
+        let mut expected = builtin.clone();
+        expected.remove(3);
+        assert_eq!(swatch_colors(&mut app), expected);
+    }
+
+    #[test]
+    fn clicking_a_swatch_applies_its_color_but_dragging_it_does_not() {
+        let mut app = color_panel_app();
+        let window = app
+            .world_mut()
+            .spawn((Window::default(), PrimaryWindow))
+            .id();
+        let move_cursor = |app: &mut App, x: f32| {
+            app.world_mut()
+                .get_mut::<Window>(window)
+                .unwrap()
+                .set_cursor_position(Some(Vec2::new(x, 20.0)));
+        };
+        let blue = ColorPalette::default().0[3].1;
+
+        // Pressed, pulled past the threshold and let go over the same swatch.
+        move_cursor(&mut app, 100.0);
+        hover_swatch(&mut app, SwatchSlot::Palette(3));
+        click(&mut app, MouseButton::Left, false);
+        move_cursor(&mut app, 100.0 + SWATCH_DRAG_THRESHOLD * 2.0);
+        click(&mut app, MouseButton::Left, true);
+        assert_eq!(
+            *app.world().resource::<HourglassConfig>(),
+            HourglassConfig::default()
+        );
+        assert_eq!(appearance_changes(&app), 0);
+
+        // A small wobble is still a click.
+        click(&mut app, MouseButton::Left, false);
+        move_cursor(&mut app, 100.0 + SWATCH_DRAG_THRESHOLD * 2.0 + 1.0);
+        click(&mut app, MouseButton::Left, true);
+        let config = app.world().resource::<HourglassConfig>();
+        assert_eq!(config.color, blue);
+        assert_eq!(config.color_mode, ColorMode::Static);
+        assert_eq!(appearance_changes(&app), 1);
+    }
+
+    #[test]
+    fn applied_custom_colors_become_recent_swatches() {
+        let mut app = color_panel_app();
+        let custom = Color::srgb(0.9, 0.1, 0.5);
+        app.world_mut().send_event(ColorPickerRequest::Open);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ColorPickerState>()
+            .set_color(custom);
+        app.world_mut().send_event(ColorPickerRequest::Apply);
+        app.update();
+        app.update();
+
+        let applied = app.world().resource::<HourglassConfig>().color;
+        assert_eq!(app.world().resource::<RecentColors>().0, [applied]);
+        let palette_len = ColorPalette::default().0.len();
+        assert_eq!(swatch_colors(&mut app).len(), palette_len + 1);
+
+        // Dropping the recent colour on a swatch moves it into the palette.
+        hover_swatch(&mut app, SwatchSlot::Recent(0));
+        click(&mut app, MouseButton::Left, false);
+        hover_swatch(&mut app, SwatchSlot::Palette(1));
+        click(&mut app, MouseButton::Left, true);
+        let palette = app.world().resource::<ColorPalette>();
+        assert_eq!(palette.0[1].1, applied);
+        assert!(app.world().resource::<RecentColors>().0.is_empty());
+    }
+
     #[test]
     fn color_dist_sq_identical_is_zero() {
         let c = Srgba::rgb(0.5, 0.5, 0.5);
@@ -484,4 +1601,154 @@ mod tests {
         assert_rgb(hsl_to_rgb(0.0, 1.0, 0.0), (0.0, 0.0, 0.0)); // black
         assert_rgb(hsl_to_rgb(0.0, 1.0, 1.0), (1.0, 1.0, 1.0)); // white
     }
+
+    #[test]
+    fn hsv_to_rgb_primary_and_secondary_hues() {
+        assert_rgb(hsv_to_rgb(0.0, 1.0, 1.0), (1.0, 0.0, 0.0)); // red
+        assert_rgb(hsv_to_rgb(60.0, 1.0, 1.0), (1.0, 1.0, 0.0)); // yellow
+        assert_rgb(hsv_to_rgb(180.0, 1.0, 1.0), (0.0, 1.0, 1.0)); // cyan
+        assert_rgb(hsv_to_rgb(240.0, 1.0, 1.0), (0.0, 0.0, 1.0)); // blue
+        assert_rgb(hsv_to_rgb(300.0, 1.0, 1.0), (1.0, 0.0, 1.0)); // magenta
+        assert_rgb(hsv_to_rgb(360.0, 1.0, 1.0), (1.0, 0.0, 0.0)); // wraps
+        assert_rgb(hsv_to_rgb(0.0, 1.0, 0.5), (0.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn rgb_to_hsv_greys_have_no_hue_or_saturation() {
+        for grey in [0.0, 0.25, 1.0] {
+            let (hue, saturation, value) = rgb_to_hsv(Color::srgb(grey, grey, grey));
+            assert_abs_diff_eq!(hue, 0.0);
+            assert_abs_diff_eq!(saturation, 0.0);
+            assert_abs_diff_eq!(value, grey);
+        }
+    }
+
+    #[test]
+    fn rgb_hsv_round_trips_and_agrees_with_bevy() {
+        let steps = [0.0, 0.1, 0.35, 0.5, 0.8, 1.0];
+        for red in steps {
+            for green in steps {
+                for blue in steps {
+                    let color = Color::srgb(red, green, blue);
+                    let (hue, saturation, value) = rgb_to_hsv(color);
+                    assert!((0.0..360.0).contains(&hue), "hue {hue} out of range");
+                    assert_rgb(hsv_to_rgb(hue, saturation, value), (red, green, blue));
+
+                    let bevy = Hsva::from(color);
+                    assert_abs_diff_eq!(saturation, bevy.saturation, epsilon = 1e-5);
+                    assert_abs_diff_eq!(value, bevy.value, epsilon = 1e-5);
+                    if saturation > 0.0 {
+                        assert_abs_diff_eq!(hue, bevy.hue, epsilon = 1e-3);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn picker_text_accepts_hex_rgb_and_names() {
+        assert_eq!(
+            parse_picker_text("#ff800080"),
+            Some(Color::srgba_u8(255, 128, 0, 128))
+        );
+        assert_eq!(
+            parse_picker_text("rgb(255, 128, 0)"),
+            Some(Color::srgb_u8(255, 128, 0))
+        );
+        assert_eq!(
+            parse_picker_text("10 20 30 40"),
+            Some(Color::srgba_u8(10, 20, 30, 40))
+        );
+        assert_eq!(parse_picker_text("red"), parse_color("red"));
+        assert_eq!(parse_picker_text("256, 0, 0"), None);
+        assert_eq!(parse_picker_text("1, 2"), None);
+        assert_eq!(parse_picker_text("blurple"), None);
+    }
+
+    #[test]
+    fn color_picker_previews_silently_then_commits_a_static_color() {
+        let mut app = color_panel_app();
+        app.world_mut().resource_mut::<HourglassConfig>().color_mode = ColorMode::Rainbow;
+        app.world_mut().send_event(ColorPickerRequest::Open);
+        app.update();
+
+        let brand = Color::srgba(0.2, 0.4, 0.9, 0.75);
+        app.world_mut()
+            .resource_mut::<ColorPickerState>()
+            .set_color(brand);
+        app.update();
+        let preview = app.world().resource::<SandColorPreview>().0.unwrap();
+        assert_rgb(preview, (0.2, 0.4, 0.9));
+        let config = app.world().resource::<HourglassConfig>();
+        assert_eq!(config.color_mode, ColorMode::Rainbow);
+        assert_ne!(config.color, preview);
+        assert_eq!(appearance_changes(&app), 0);
+
+        app.world_mut().send_event(ColorPickerRequest::Apply);
+        app.update();
+        let config = app.world().resource::<HourglassConfig>();
+        assert_eq!(config.color_mode, ColorMode::Static);
+        assert_abs_diff_eq!(config.color.alpha(), 0.75, epsilon = 1e-5);
+        assert_eq!(appearance_changes(&app), 1);
+        assert!(!app.world().resource::<ColorPickerState>().is_open());
+        assert_eq!(app.world().resource::<SandColorPreview>().0, None);
+    }
+
+    #[test]
+    fn cancelling_the_color_picker_keeps_the_previous_look() {
+        let mut app = color_panel_app();
+        let original = app.world().resource::<HourglassConfig>().clone();
+        app.world_mut().send_event(ColorPickerRequest::Open);
+        app.update();
+        // Clicking the text field focuses it with an empty buffer.
+        app.world_mut().resource_mut::<ColorPickerState>().text = Some(String::new());
+        type_into_picker(&mut app, &[Key::Character("#00ff00".into())]);
+        assert_eq!(
+            app.world().resource::<ColorPickerState>().text.as_deref(),
+            Some("#00ff00")
+        );
+        type_into_picker(&mut app, &[Key::Enter]);
+        assert_rgb(
+            app.world().resource::<SandColorPreview>().0.unwrap(),
+            (0.0, 1.0, 0.0),
+        );
+        assert_eq!(*app.world().resource::<HourglassConfig>(), original);
+
+        type_into_picker(&mut app, &[Key::Escape]);
+        assert_eq!(*app.world().resource::<HourglassConfig>(), original);
+        assert!(!app.world().resource::<ColorPickerState>().is_open());
+        assert_eq!(app.world().resource::<SandColorPreview>().0, None);
+        assert_eq!(appearance_changes(&app), 0);
+    }
+
+    #[test]
+    fn progress_mode_follows_the_remaining_time_in_visible_steps() {
+        let mut app = color_panel_app();
+        app.world_mut().resource_mut::<HourglassConfig>().color_mode = ColorMode::Progress;
+        app.update();
+        assert_rgb(
+            app.world().resource::<HourglassConfig>().color,
+            (0.2, 0.7, 0.25),
+        );
+
+        app.world_mut()
+            .resource_mut::<crate::resources::TimerState>()
+            .remaining = 0.0;
+        app.update();
+        assert_rgb(
+            app.world().resource::<HourglassConfig>().color,
+            (0.85, 0.15, 0.15),
+        );
+
+        // A sliver of progress is below the redraw step and leaves the colour alone.
+        app.world_mut()
+            .resource_mut::<crate::resources::TimerState>()
+            .remaining = 0.1;
+        app.update();
+        assert_rgb(
+            app.world().resource::<HourglassConfig>().color,
+            (0.85, 0.15, 0.15),
+        );
+        assert_eq!(appearance_changes(&app), 0);
+    }
 }
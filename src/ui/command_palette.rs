@@ -0,0 +1,705 @@
+use crate::presets::{PresetCommand, TimerPresets};
+use crate::resources::{
+    AppearanceStateChanged, ColorMode, ColorPalette, HourglassConfig, PendingFlip, ShapeMode,
+    TimerState, ViewMode,
+};
+use crate::shapes::ShapeLibrary;
+use crate::timer::{TimerCommand, TimerSystems, parse_duration};
+use crate::ui::color_panel::pick_distinct_color;
+use crate::ui::extension_appearance_change_command;
+use crate::ui::shortcuts::KeyBindings;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use std::sync::Arc;
+
+/// Number of result rows shown under the query line.
+const MAX_VISIBLE_RESULTS: usize = 6;
+
+pub struct CommandPalettePlugin;
+
+impl Plugin for CommandPalettePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CommandPaletteState>()
+            .init_resource::<PaletteActions>()
+            .init_resource::<ColorPalette>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<TimerPresets>()
+            .init_resource::<ShapeLibrary>()
+            .add_event::<PresetCommand>()
+            .add_systems(Startup, spawn_command_palette)
+            .add_systems(
+                Update,
+                (
+                    sync_palette_color_actions,
+                    sync_palette_preset_actions,
+                    sync_palette_shape_actions,
+                ),
+            )
+            .add_systems(
+                Update,
+                (handle_palette_input, run_selected_palette_action)
+                    .chain()
+                    .in_set(TimerSystems::Input),
+            )
+            .add_systems(Update, update_palette_view.after(TimerSystems::Input));
+
+        register_builtin_actions(app);
+    }
+}
+
+/// What a palette action does once chosen. Timer effects are sent as
+/// `TimerCommand`s; appearance effects edit `HourglassConfig` and go through
+/// the same change notification as the colour and shape panels; preset
+/// effects are sent as `PresetCommand`s.
+#[derive(Clone)]
+pub enum PaletteEffect {
+    Timer(TimerCommand),
+    Appearance(Arc<dyn Fn(&mut HourglassConfig) + Send + Sync>),
+    Preset(PresetCommand),
+}
+
+/// One entry in the command palette.
+#[derive(Clone)]
+pub struct PaletteAction {
+    pub label: String,
+    pub effect: PaletteEffect,
+}
+
+impl PaletteAction {
+    pub fn timer(label: impl Into<String>, command: TimerCommand) -> Self {
+        Self {
+            label: label.into(),
+            effect: PaletteEffect::Timer(command),
+        }
+    }
+
+    pub fn appearance(
+        label: impl Into<String>,
+        apply: impl Fn(&mut HourglassConfig) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            effect: PaletteEffect::Appearance(Arc::new(apply)),
+        }
+    }
+
+    pub fn preset(label: impl Into<String>, command: PresetCommand) -> Self {
+        Self {
+            label: label.into(),
+            effect: PaletteEffect::Preset(command),
+        }
+    }
+}
+
+/// Builds an action from free text such as `set 25m`, for commands whose
+/// argument cannot be enumerated up front.
+pub type PaletteParser = fn(&str) -> Option<PaletteAction>;
+
+/// Registry of everything the palette can run. Plugins contribute entries
+/// through `CommandPaletteAppExt`.
+#[derive(Resource, Default)]
+pub struct PaletteActions {
+    actions: Vec<PaletteAction>,
+    parsers: Vec<PaletteParser>,
+}
+
+impl PaletteActions {
+    pub fn register(&mut self, action: PaletteAction) {
+        self.actions.push(action);
+    }
+
+    pub fn register_parser(&mut self, parser: PaletteParser) {
+        self.parsers.push(parser);
+    }
+
+    /// Actions matching `query`, best first. Parsed actions come before the
+    /// fuzzy matches because they consumed the whole query.
+    fn matches(&self, query: &str) -> Vec<PaletteAction> {
+        let mut results: Vec<PaletteAction> = self
+            .parsers
+            .iter()
+            .filter_map(|parse| parse(query))
+            .collect();
+
+        let mut scored: Vec<(i32, &PaletteAction)> = self
+            .actions
+            .iter()
+            .filter_map(|action| fuzzy_score(query, &action.label).map(|score| (score, action)))
+            .collect();
+        // Stable sort keeps registration order between equal scores.
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        results.extend(scored.into_iter().map(|(_, action)| action.clone()));
+        results
+    }
+}
+
+/// Lets other plugins add palette entries from their `build`.
+pub trait CommandPaletteAppExt {
+    fn register_palette_action(&mut self, action: PaletteAction) -> &mut Self;
+    fn register_palette_parser(&mut self, parser: PaletteParser) -> &mut Self;
+}
+
+impl CommandPaletteAppExt for App {
+    fn register_palette_action(&mut self, action: PaletteAction) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<PaletteActions>()
+            .register(action);
+        self
+    }
+
+    fn register_palette_parser(&mut self, parser: PaletteParser) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<PaletteActions>()
+            .register_parser(parser);
+        self
+    }
+}
+
+/// Run condition: the palette is open and consuming keyboard input.
+pub(crate) fn command_palette_open(state: Option<Res<CommandPaletteState>>) -> bool {
+    state.is_some_and(|state| state.open)
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct CommandPaletteState {
+    open: bool,
+    query: String,
+    selected: usize,
+    /// Set by Enter; consumed by `run_selected_palette_action`.
+    submitted: bool,
+}
+
+#[derive(Component)]
+struct CommandPaletteRoot;
+
+#[derive(Component)]
+struct PaletteQueryText;
+
+#[derive(Component)]
+struct PaletteResultRow(usize);
+
+fn register_builtin_actions(app: &mut App) {
+    app.register_palette_action(PaletteAction::timer("start", TimerCommand::Start))
+        .register_palette_action(PaletteAction::timer("pause", TimerCommand::Pause))
+        .register_palette_action(PaletteAction::timer("reset", TimerCommand::Reset))
+        .register_palette_action(PaletteAction::timer("restart", TimerCommand::Restart))
+        .register_palette_action(PaletteAction::appearance("rainbow", |config| {
+            config.color_mode = ColorMode::Rainbow;
+        }))
+        .register_palette_action(PaletteAction::appearance("progress colors", |config| {
+            config.color_mode = ColorMode::Progress;
+        }))
+        .register_palette_action(PaletteAction::appearance("random color", |config| {
+            let current = config.color.to_srgba();
+            config.color = pick_distinct_color(current, 0.3 * 0.3, &mut rand::thread_rng()).into();
+            config.color_mode = ColorMode::Random;
+        }))
+        .register_palette_action(PaletteAction::appearance("morphing", |config| {
+            config.shape_mode = match config.shape_mode {
+                ShapeMode::Static => ShapeMode::Morphing,
+                ShapeMode::Morphing => ShapeMode::Static,
+            };
+        }))
+        .register_palette_action(PaletteAction::appearance("3d view", |config| {
+            config.view = match config.view {
+                ViewMode::TwoD => ViewMode::ThreeD,
+                ViewMode::ThreeD => ViewMode::TwoD,
+            };
+        }))
+        .register_palette_action(PaletteAction::preset(
+            "save current as preset",
+            PresetCommand::SaveCurrent(None),
+        ))
+        .register_palette_parser(parse_set_duration)
+        .register_palette_parser(parse_save_preset);
+
+    let palette = app.world().resource::<ColorPalette>().clone();
+    let presets = app.world().resource::<TimerPresets>().clone();
+    let shapes = app.world().resource::<ShapeLibrary>().clone();
+    let mut actions = app.world_mut().resource_mut::<PaletteActions>();
+    actions.set_color_actions(&palette);
+    actions.set_preset_actions(&presets);
+    actions.set_shape_actions(&shapes);
+}
+
+const COLOR_ACTION_PREFIX: &str = "color ";
+const PRESET_ACTION_PREFIX: &str = "preset ";
+const SHAPE_ACTION_PREFIX: &str = "shape ";
+
+impl PaletteActions {
+    /// Replace every action whose label starts with `prefix`.
+    fn replace_group(&mut self, prefix: &str, actions: impl IntoIterator<Item = PaletteAction>) {
+        self.actions
+            .retain(|action| !action.label.starts_with(prefix));
+        self.actions.extend(actions);
+    }
+
+    /// Replace the `color <name>` entries with one per palette swatch.
+    fn set_color_actions(&mut self, palette: &ColorPalette) {
+        let actions = palette.0.iter().map(|(name, color)| {
+            let color = *color;
+            PaletteAction::appearance(format!("{COLOR_ACTION_PREFIX}{name}"), move |config| {
+                config.color = color;
+                config.color_mode = ColorMode::Static;
+            })
+        });
+        self.replace_group(COLOR_ACTION_PREFIX, actions.collect::<Vec<_>>());
+    }
+
+    /// Replace the `preset <name>` entries with one per saved preset.
+    fn set_preset_actions(&mut self, presets: &TimerPresets) {
+        let actions = presets.0.iter().map(|preset| {
+            PaletteAction::preset(
+                format!("{PRESET_ACTION_PREFIX}{}", preset.name.to_lowercase()),
+                PresetCommand::Apply(preset.name.clone()),
+            )
+        });
+        self.replace_group(PRESET_ACTION_PREFIX, actions.collect::<Vec<_>>());
+    }
+
+    /// Replace the `shape <name>` entries with one per loaded shape.
+    fn set_shape_actions(&mut self, shapes: &ShapeLibrary) {
+        let actions = shapes.shapes().map(|shape| {
+            PaletteAction::appearance(
+                format!("{SHAPE_ACTION_PREFIX}{}", shape.name()),
+                move |config| {
+                    config.shape_type = shape.clone();
+                    config.shape_mode = ShapeMode::Static;
+                },
+            )
+        });
+        self.replace_group(SHAPE_ACTION_PREFIX, actions.collect::<Vec<_>>());
+    }
+}
+
+fn sync_palette_color_actions(palette: Res<ColorPalette>, mut actions: ResMut<PaletteActions>) {
+    if palette.is_changed() && !palette.is_added() {
+        actions.set_color_actions(&palette);
+    }
+}
+
+fn sync_palette_preset_actions(presets: Res<TimerPresets>, mut actions: ResMut<PaletteActions>) {
+    if presets.is_changed() && !presets.is_added() {
+        actions.set_preset_actions(&presets);
+    }
+}
+
+fn sync_palette_shape_actions(shapes: Res<ShapeLibrary>, mut actions: ResMut<PaletteActions>) {
+    if shapes.is_changed() && !shapes.is_added() {
+        actions.set_shape_actions(&shapes);
+    }
+}
+
+/// `set 25m`, `set 1:30:00`, ... → `TimerCommand::SetDuration`.
+fn parse_set_duration(query: &str) -> Option<PaletteAction> {
+    let argument = query.trim().strip_prefix("set ")?;
+    let seconds = parse_duration(argument)?;
+    let preview = TimerState {
+        remaining: seconds,
+        ..default()
+    };
+    Some(PaletteAction::timer(
+        format!("set {}", preview.format_time()),
+        TimerCommand::SetDuration(seconds),
+    ))
+}
+
+/// `save preset Tea` → save the current setup under that name.
+fn parse_save_preset(query: &str) -> Option<PaletteAction> {
+    let name = query.trim().strip_prefix("save preset ")?.trim();
+    (!name.is_empty()).then(|| {
+        PaletteAction::preset(
+            format!("save preset {name}"),
+            PresetCommand::SaveCurrent(Some(name.to_string())),
+        )
+    })
+}
+
+/// Case-insensitive subsequence match. Returns `None` when `query` is not a
+/// subsequence of `candidate`; otherwise a score that rewards consecutive runs
+/// and matches at word starts, and penalises leftover characters. Every start
+/// position for the first character is tried so `sl` prefers the `slim` in
+/// `shape slim` over the leading `s`.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.trim().chars().flat_map(char::to_lowercase).collect();
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let leftover_penalty = candidate.len().saturating_sub(query.len()) as i32 / 4;
+    let Some(&first) = query.first() else {
+        return Some(-leftover_penalty);
+    };
+
+    (0..candidate.len())
+        .filter(|&start| candidate[start] == first)
+        .filter_map(|start| {
+            let mut score = 0;
+            let mut query_index = 0;
+            let mut previous_match: Option<usize> = None;
+            for (index, &ch) in candidate.iter().enumerate().skip(start) {
+                if query_index == query.len() {
+                    break;
+                }
+                if ch != query[query_index] {
+                    continue;
+                }
+                score += 1;
+                if previous_match.is_some_and(|previous| previous + 1 == index) {
+                    score += 3;
+                }
+                if index == 0 || candidate[index - 1] == ' ' {
+                    score += 2;
+                }
+                previous_match = Some(index);
+                query_index += 1;
+            }
+            (query_index == query.len()).then_some(score)
+        })
+        .max()
+        .map(|score| score - leftover_penalty)
+}
+
+fn spawn_command_palette(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Command Palette"),
+            CommandPaletteRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Percent(50.0),
+                width: Val::Px(320.0),
+                margin: UiRect::left(Val::Px(-160.0)),
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BorderColor(Color::srgb(0.65, 0.65, 0.65)),
+            ZIndex(200),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                PaletteQueryText,
+                Text::new("> "),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(4.0)),
+                    ..default()
+                },
+            ));
+            for index in 0..MAX_VISIBLE_RESULTS {
+                parent.spawn((
+                    PaletteResultRow(index),
+                    Text::new(""),
+                    TextFont {
+                        font_size: 15.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    Node {
+                        padding: UiRect::horizontal(Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                ));
+            }
+        });
+}
+
+fn handle_palette_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut keyboard: EventReader<KeyboardInput>,
+    mut state: ResMut<CommandPaletteState>,
+    actions: Res<PaletteActions>,
+) {
+    let modifier_held = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    if bindings.command_palette.just_pressed(&keys) {
+        let open = !state.open;
+        *state = CommandPaletteState { open, ..default() };
+        keyboard.clear();
+        return;
+    }
+    if !state.open {
+        keyboard.clear();
+        return;
+    }
+
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Escape => {
+                *state = CommandPaletteState::default();
+                return;
+            }
+            Key::Enter => state.submitted = true,
+            Key::Backspace => {
+                state.query.pop();
+                state.selected = 0;
+            }
+            Key::ArrowDown => {
+                let last = actions
+                    .matches(&state.query)
+                    .len()
+                    .min(MAX_VISIBLE_RESULTS)
+                    .saturating_sub(1);
+                state.selected = (state.selected + 1).min(last);
+            }
+            Key::ArrowUp => state.selected = state.selected.saturating_sub(1),
+            Key::Space => {
+                state.query.push(' ');
+                state.selected = 0;
+            }
+            Key::Character(text) if !modifier_held => {
+                state.query.push_str(text);
+                state.selected = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn run_selected_palette_action(
+    mut state: ResMut<CommandPaletteState>,
+    actions: Res<PaletteActions>,
+    mut config: ResMut<HourglassConfig>,
+    mut pending_flip: ResMut<PendingFlip>,
+    mut timer_commands: EventWriter<TimerCommand>,
+    mut appearance_changed: EventWriter<AppearanceStateChanged>,
+    mut preset_commands: EventWriter<PresetCommand>,
+) {
+    if !state.submitted {
+        return;
+    }
+    let matches = actions.matches(&state.query);
+    let Some(action) = matches.get(state.selected.min(matches.len().saturating_sub(1))) else {
+        state.submitted = false;
+        return;
+    };
+
+    match &action.effect {
+        PaletteEffect::Timer(command) => {
+            timer_commands.write(*command);
+        }
+        PaletteEffect::Appearance(apply) => {
+            apply(&mut config);
+            if let Some(command) = extension_appearance_change_command(&mut pending_flip) {
+                timer_commands.write(command);
+            }
+            appearance_changed.write_default();
+        }
+        PaletteEffect::Preset(command) => {
+            preset_commands.write(command.clone());
+        }
+    }
+    *state = CommandPaletteState::default();
+}
+
+fn update_palette_view(
+    state: Res<CommandPaletteState>,
+    actions: Res<PaletteActions>,
+    mut root_query: Query<&mut Node, With<CommandPaletteRoot>>,
+    mut query_text: Query<&mut Text, (With<PaletteQueryText>, Without<PaletteResultRow>)>,
+    mut rows: Query<
+        (&PaletteResultRow, &mut Text, &mut BackgroundColor),
+        Without<PaletteQueryText>,
+    >,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    for mut node in &mut root_query {
+        node.display = if state.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    if !state.open {
+        return;
+    }
+
+    for mut text in &mut query_text {
+        **text = format!("> {}", state.query);
+    }
+    let matches = actions.matches(&state.query);
+    let selected = state.selected.min(matches.len().saturating_sub(1));
+    for (row, mut text, mut background) in &mut rows {
+        **text = matches
+            .get(row.0)
+            .map(|action| action.label.clone())
+            .unwrap_or_default();
+        *background = BackgroundColor(if row.0 == selected && row.0 < matches.len() {
+            Color::srgb(0.3, 0.3, 0.3)
+        } else {
+            Color::NONE
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::HourglassShape;
+
+    fn palette_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, crate::timer::TimerPlugin))
+            .init_resource::<HourglassConfig>()
+            .init_resource::<PendingFlip>()
+            .init_resource::<TimerState>()
+            .add_event::<AppearanceStateChanged>()
+            .add_event::<KeyboardInput>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .add_plugins(CommandPalettePlugin);
+        app
+    }
+
+    fn submit(app: &mut App, query: &str) {
+        *app.world_mut().resource_mut::<CommandPaletteState>() = CommandPaletteState {
+            open: true,
+            query: query.to_string(),
+            selected: 0,
+            submitted: true,
+        };
+        app.update();
+    }
+
+    #[test]
+    fn fuzzy_score_requires_subsequence() {
+        assert!(fuzzy_score("strt", "start").is_some());
+        assert!(fuzzy_score("tarts", "start").is_none());
+        assert!(fuzzy_score("", "start").is_some());
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts_and_runs() {
+        let slim = fuzzy_score("sl", "shape slim").unwrap();
+        let scattered = fuzzy_score("sl", "shape classic").unwrap();
+        assert!(slim > scattered);
+        assert!(fuzzy_score("start", "start") > fuzzy_score("start", "restart"));
+    }
+
+    #[test]
+    fn parsed_duration_ranks_first() {
+        let mut actions = PaletteActions::default();
+        actions.register(PaletteAction::timer("reset", TimerCommand::Reset));
+        actions.register_parser(parse_set_duration);
+        let matches = actions.matches("set 25m");
+        assert_eq!(matches[0].label, "set 00:25:00");
+        assert!(matches!(
+            matches[0].effect,
+            PaletteEffect::Timer(TimerCommand::SetDuration(1500.0))
+        ));
+    }
+
+    #[test]
+    fn builtin_actions_cover_requested_commands() {
+        let app = palette_app();
+        let actions = app.world().resource::<PaletteActions>();
+        for query in [
+            "start",
+            "reset",
+            "shape slim",
+            "color red",
+            "rainbow",
+            "progress colors",
+            "morphing",
+            "3d view",
+        ] {
+            let matches = actions.matches(query);
+            assert_eq!(matches.first().map(|a| a.label.as_str()), Some(query));
+        }
+    }
+
+    #[test]
+    fn submitting_set_duration_updates_the_timer() {
+        let mut app = palette_app();
+        submit(&mut app, "set 25m");
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!(timer.duration, 1500.0);
+        assert!(!app.world().resource::<CommandPaletteState>().open);
+    }
+
+    #[test]
+    fn submitting_appearance_action_updates_config() {
+        let mut app = palette_app();
+        submit(&mut app, "shape slim");
+        submit(&mut app, "color red");
+        let config = app.world().resource::<HourglassConfig>();
+        assert_eq!(config.shape_type, HourglassShape::SLIM);
+        assert_eq!(
+            config.color,
+            crate::resources::palette_color_by_name("red").unwrap()
+        );
+        assert_eq!(config.color_mode, ColorMode::Static);
+    }
+
+    #[test]
+    fn color_actions_follow_the_palette() {
+        let mut app = palette_app();
+        app.update();
+        let teal = Color::srgb(0.0, 0.5, 0.5);
+        app.world_mut().resource_mut::<ColorPalette>().0 = vec![("teal".to_string(), teal)];
+        app.update();
+        let actions = app.world().resource::<PaletteActions>();
+        assert!(
+            actions
+                .matches("color red")
+                .iter()
+                .all(|a| a.label != "color red")
+        );
+
+        submit(&mut app, "color teal");
+        let config = app.world().resource::<HourglassConfig>();
+        assert_eq!(config.color, teal);
+    }
+
+    #[test]
+    fn contributed_actions_are_listed() {
+        let mut app = palette_app();
+        app.register_palette_action(PaletteAction::timer(
+            "add five minutes",
+            TimerCommand::Adjust(300.0),
+        ));
+        submit(&mut app, "add five");
+        let timer = app.world().resource::<TimerState>();
+        assert_eq!(timer.duration, 480.0);
+    }
+
+    #[test]
+    fn preset_actions_apply_and_save_presets() {
+        let mut app = palette_app();
+        app.add_plugins(crate::presets::PresetsPlugin);
+        submit(&mut app, "preset tea");
+        app.update();
+        assert_eq!(app.world().resource::<TimerState>().duration, 240.0);
+
+        submit(&mut app, "save preset Pasta");
+        app.update();
+        app.update();
+        let presets = app.world().resource::<TimerPresets>();
+        assert_eq!(presets.get("pasta").unwrap().duration, 240.0);
+        let actions = app.world().resource::<PaletteActions>();
+        assert_eq!(
+            actions
+                .matches("preset pasta")
+                .first()
+                .map(|a| a.label.as_str()),
+            Some("preset pasta")
+        );
+    }
+}
@@ -1,7 +1,11 @@
 pub mod color_panel;
+pub mod command_palette;
 pub mod pause_overlay;
+pub mod shape_editor;
 pub mod shape_panel;
+pub mod shortcuts;
 pub mod timer_panel;
+pub mod toast;
 
 use crate::resources::{AppearanceStateChanged, PendingFlip};
 use crate::timer::TimerCommand;
@@ -40,14 +44,17 @@ pub struct ShapeRowMarker;
 #[derive(Component)]
 pub struct BottomTimerMarker;
 
+#[cfg(feature = "chrome_extension")]
 #[derive(Component)]
 struct AppearanceControlsContainer;
 
 // Resource to track timer panel visibility
-#[derive(Resource, Default)]
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource, Default)]
 pub struct TimerPanelVisible(pub bool);
 
-#[derive(Resource)]
+#[derive(Resource, Reflect)]
+#[reflect(Resource, Default)]
 pub struct AppearancePanelVisible(pub bool);
 
 impl Default for AppearancePanelVisible {
@@ -62,11 +69,17 @@ impl Plugin for UIPlugin {
             color_panel::ColorPanelPlugin,
             timer_panel::TimerPanelPlugin,
             shape_panel::ShapePanelPlugin,
+            shape_editor::ShapeEditorPlugin,
             pause_overlay::PauseOverlayPlugin,
+            command_palette::CommandPalettePlugin,
+            shortcuts::ShortcutsPlugin,
+            toast::ToastPlugin,
         ))
         .add_event::<AppearanceStateChanged>()
         .init_resource::<TimerPanelVisible>()
-        .init_resource::<AppearancePanelVisible>();
+        .init_resource::<AppearancePanelVisible>()
+        .register_type::<TimerPanelVisible>()
+        .register_type::<AppearancePanelVisible>();
 
         #[cfg(feature = "chrome_extension")]
         app.add_systems(Startup, setup_sidebar_ui_layout);
@@ -76,6 +89,103 @@ impl Plugin for UIPlugin {
     }
 }
 
+#[cfg(not(feature = "chrome_extension"))]
+fn setup_ui_layout(mut commands: Commands) {
+    // Root UI container - vertical layout
+    commands
+        .spawn((
+            Name::new("UI Root"),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::NONE),
+        ))
+        .with_children(|parent| {
+            // Top controls container - narrow color panel only
+            parent
+                .spawn((
+                    Name::new("Top Controls Container"),
+                    TopControlsMarker,
+                    Node {
+                        width: Val::Percent(100.0),
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                ))
+                .with_children(|parent| {
+                    // Color selection row - narrow and centered, wrapping
+                    // once the edited palette outgrows the window
+                    parent.spawn((
+                        Name::new("Color Row Container"),
+                        ColorRowMarker,
+                        Node {
+                            width: Val::Percent(100.0),
+                            min_height: Val::Px(25.0),
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Row,
+                            flex_wrap: FlexWrap::Wrap,
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            padding: UiRect::vertical(Val::Px(2.0)),
+                            row_gap: Val::Px(2.0),
+                            ..default()
+                        },
+                    ));
+                });
+
+            // Shape selection row - positioned directly under color panel
+            parent.spawn((
+                Name::new("Shape Row Container"),
+                ShapeRowMarker,
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(50.0),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    padding: UiRect::vertical(Val::Px(2.0)),
+                    overflow: Overflow::clip_x(),
+                    ..default()
+                },
+            ));
+
+            // Center area (for hourglass) - takes remaining space
+            parent.spawn((
+                Name::new("Center Area"),
+                Node {
+                    width: Val::Percent(100.0),
+                    flex_grow: 1.0,
+                    display: Display::Flex,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+            ));
+
+            // Bottom timer container (collapsible)
+            parent.spawn((
+                Name::new("Bottom Timer Container"),
+                BottomTimerMarker,
+                Node {
+                    width: Val::Percent(100.0),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+            ));
+        });
+}
+
 #[cfg(feature = "chrome_extension")]
 fn setup_sidebar_ui_layout(mut commands: Commands) {
     commands
@@ -242,98 +352,3 @@ mod appearance_change_tests {
         }
     }
 }
-
-#[cfg(not(feature = "chrome_extension"))]
-fn setup_ui_layout(mut commands: Commands) {
-    // Root UI container - vertical layout
-    commands
-        .spawn((
-            Name::new("UI Root"),
-            Node {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                display: Display::Flex,
-                flex_direction: FlexDirection::Column,
-                ..default()
-            },
-            BackgroundColor(Color::NONE),
-        ))
-        .with_children(|parent| {
-            // Top controls container - narrow color panel only
-            parent
-                .spawn((
-                    Name::new("Top Controls Container"),
-                    TopControlsMarker,
-                    Node {
-                        width: Val::Percent(100.0),
-                        display: Display::Flex,
-                        flex_direction: FlexDirection::Column,
-                        padding: UiRect::all(Val::Px(2.0)),
-                        ..default()
-                    },
-                ))
-                .with_children(|parent| {
-                    // Color selection row - narrow and centered
-                    parent.spawn((
-                        Name::new("Color Row Container"),
-                        ColorRowMarker,
-                        Node {
-                            width: Val::Percent(100.0),
-                            height: Val::Px(25.0),
-                            display: Display::Flex,
-                            flex_direction: FlexDirection::Row,
-                            align_items: AlignItems::Center,
-                            justify_content: JustifyContent::Center,
-                            padding: UiRect::vertical(Val::Px(2.0)),
-                            overflow: Overflow::clip_x(),
-                            ..default()
-                        },
-                    ));
-                });
-
-            // Shape selection row - positioned directly under color panel
-            parent.spawn((
-                Name::new("Shape Row Container"),
-                ShapeRowMarker,
-                Node {
-                    width: Val::Percent(100.0),
-                    height: Val::Px(50.0),
-                    display: Display::Flex,
-                    flex_direction: FlexDirection::Row,
-                    align_items: AlignItems::Center,
-                    justify_content: JustifyContent::Center,
-                    padding: UiRect::vertical(Val::Px(2.0)),
-                    overflow: Overflow::clip_x(),
-                    ..default()
-                },
-            ));
-
-            // Center area (for hourglass) - takes remaining space
-            parent.spawn((
-                Name::new("Center Area"),
-                Node {
-                    width: Val::Percent(100.0),
-                    flex_grow: 1.0,
-                    display: Display::Flex,
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                BackgroundColor(Color::NONE),
-            ));
-
-            // Bottom timer container (collapsible)
-            parent.spawn((
-                Name::new("Bottom Timer Container"),
-                BottomTimerMarker,
-                Node {
-                    width: Val::Percent(100.0),
-                    display: Display::Flex,
-                    flex_direction: FlexDirection::Column,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                BackgroundColor(Color::NONE),
-            ));
-        });
-}
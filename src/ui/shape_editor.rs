@@ -0,0 +1,873 @@
+//! The shape editor: sliders that reshape a copy of the current shape. The
+//! draft is previewed on the main hourglass and in the shape row while it is
+//! edited, and can be saved under its own name or exported as a shape file.
+
+use crate::resources::{
+    AppearanceStateChanged, HourglassConfig, PendingFlip, ShapeMode, ShapePreview,
+};
+use crate::shapes::{ShapeBulb, ShapeDefinition, ShapeLibrary, ShapeNeck};
+use crate::timer::{TimerCommand, TimerSystems};
+use crate::ui::ShapeRowMarker;
+use crate::ui::color_panel::{hsv_to_rgb, rgb_to_hsv};
+use crate::ui::command_palette::command_palette_open;
+use crate::ui::extension_appearance_change_command;
+#[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+use crate::ui::toast::ShowToast;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use bevy::ui::{FocusPolicy, RelativeCursorPosition};
+use std::ops::RangeInclusive;
+
+const SLIDER_WIDTH: f32 = 160.0;
+const LABEL_WIDTH: f32 = 100.0;
+
+pub struct ShapeEditorPlugin;
+
+impl Plugin for ShapeEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShapeLibrary>()
+            .init_resource::<ShapeEditorState>()
+            .init_resource::<ShapePreview>()
+            .add_event::<ShapeEditorRequest>()
+            .add_systems(Startup, spawn_shape_editor)
+            .add_systems(PostStartup, spawn_shape_editor_toggle)
+            .add_systems(
+                Update,
+                (
+                    (
+                        handle_shape_editor_buttons,
+                        drag_shape_sliders,
+                        handle_shape_editor_keys.run_if(not(command_palette_open)),
+                    ),
+                    apply_shape_editor_requests,
+                    preview_shape_draft,
+                )
+                    .chain()
+                    .in_set(TimerSystems::Input),
+            )
+            .add_systems(Update, update_shape_editor_view.after(TimerSystems::Input));
+    }
+}
+
+/// One slider in the editor. All of them edit the main outline; the shape
+/// row scales it down for the mini.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum ShapeParam {
+    BulbCurvature,
+    BulbWidth,
+    NeckWidth,
+    NeckHeight,
+    NeckCurvature,
+    PlateWidth,
+    PlateHeight,
+    TintHue,
+    TintStrength,
+    GlassOpacity,
+}
+
+impl ShapeParam {
+    const ALL: [ShapeParam; 10] = [
+        ShapeParam::BulbCurvature,
+        ShapeParam::BulbWidth,
+        ShapeParam::NeckWidth,
+        ShapeParam::NeckHeight,
+        ShapeParam::NeckCurvature,
+        ShapeParam::PlateWidth,
+        ShapeParam::PlateHeight,
+        ShapeParam::TintHue,
+        ShapeParam::TintStrength,
+        ShapeParam::GlassOpacity,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            ShapeParam::BulbCurvature => "Bulb curve",
+            ShapeParam::BulbWidth => "Bulb width",
+            ShapeParam::NeckWidth => "Neck width",
+            ShapeParam::NeckHeight => "Neck height",
+            ShapeParam::NeckCurvature => "Neck curve",
+            ShapeParam::PlateWidth => "Plate width",
+            ShapeParam::PlateHeight => "Plate height",
+            ShapeParam::TintHue => "Tint hue",
+            ShapeParam::TintStrength => "Tint strength",
+            ShapeParam::GlassOpacity => "Glass opacity",
+        }
+    }
+
+    fn range(self) -> RangeInclusive<f32> {
+        match self {
+            ShapeParam::BulbCurvature | ShapeParam::NeckCurvature => 0.0..=3.0,
+            ShapeParam::BulbWidth => 0.4..=1.6,
+            ShapeParam::NeckWidth => 2.0..=40.0,
+            ShapeParam::NeckHeight => 4.0..=60.0,
+            ShapeParam::PlateWidth => 100.0..=500.0,
+            ShapeParam::PlateHeight => 2.0..=30.0,
+            ShapeParam::TintHue => 0.0..=360.0,
+            ShapeParam::TintStrength | ShapeParam::GlassOpacity => 0.0..=1.0,
+        }
+    }
+
+    fn get(self, definition: &ShapeDefinition) -> f32 {
+        let main = &definition.main;
+        let (hue, saturation, _) = rgb_to_hsv(definition.glass_color);
+        match self {
+            ShapeParam::BulbCurvature => match main.bulb {
+                ShapeBulb::Circular { curvature, .. } => curvature,
+                ShapeBulb::Straight { .. } => 0.0,
+            },
+            ShapeParam::BulbWidth => match main.bulb {
+                ShapeBulb::Circular { width_factor, .. } | ShapeBulb::Straight { width_factor } => {
+                    width_factor
+                }
+            },
+            ShapeParam::NeckWidth => match main.neck {
+                ShapeNeck::Straight { width, .. } | ShapeNeck::Curved { width, .. } => width,
+            },
+            ShapeParam::NeckHeight => match main.neck {
+                ShapeNeck::Straight { height, .. } | ShapeNeck::Curved { height, .. } => height,
+            },
+            ShapeParam::NeckCurvature => match main.neck {
+                ShapeNeck::Curved { curvature, .. } => curvature,
+                ShapeNeck::Straight { .. } => 0.0,
+            },
+            ShapeParam::PlateWidth => main.plate_width,
+            ShapeParam::PlateHeight => main.plate_height,
+            ShapeParam::TintHue => hue,
+            ShapeParam::TintStrength => saturation,
+            ShapeParam::GlassOpacity => definition.glass_color.alpha(),
+        }
+    }
+
+    /// Set this parameter, clamped to its range. Curving a straight bulb or
+    /// neck makes it round; a neck with no curve left becomes straight.
+    fn set(self, definition: &mut ShapeDefinition, value: f32) {
+        let value = value.clamp(*self.range().start(), *self.range().end());
+        let main = &mut definition.main;
+        match self {
+            ShapeParam::BulbCurvature => {
+                main.bulb = match main.bulb {
+                    ShapeBulb::Circular {
+                        width_factor,
+                        curve_resolution,
+                        ..
+                    } => ShapeBulb::Circular {
+                        curvature: value,
+                        width_factor,
+                        curve_resolution,
+                    },
+                    ShapeBulb::Straight { width_factor } => ShapeBulb::Circular {
+                        curvature: value,
+                        width_factor,
+                        curve_resolution: 20,
+                    },
+                }
+            }
+            ShapeParam::BulbWidth => match &mut main.bulb {
+                ShapeBulb::Circular { width_factor, .. } | ShapeBulb::Straight { width_factor } => {
+                    *width_factor = value
+                }
+            },
+            ShapeParam::NeckWidth => match &mut main.neck {
+                ShapeNeck::Straight { width, .. } | ShapeNeck::Curved { width, .. } => {
+                    *width = value
+                }
+            },
+            ShapeParam::NeckHeight => match &mut main.neck {
+                ShapeNeck::Straight { height, .. } | ShapeNeck::Curved { height, .. } => {
+                    *height = value
+                }
+            },
+            ShapeParam::NeckCurvature => {
+                let (width, height, curve_resolution) = match main.neck {
+                    ShapeNeck::Straight { width, height } => (width, height, 10),
+                    ShapeNeck::Curved {
+                        width,
+                        height,
+                        curve_resolution,
+                        ..
+                    } => (width, height, curve_resolution),
+                };
+                main.neck = if value <= 0.0 {
+                    ShapeNeck::Straight { width, height }
+                } else {
+                    ShapeNeck::Curved {
+                        curvature: value,
+                        width,
+                        height,
+                        curve_resolution,
+                    }
+                };
+            }
+            ShapeParam::PlateWidth => main.plate_width = value,
+            ShapeParam::PlateHeight => main.plate_height = value,
+            ShapeParam::TintHue | ShapeParam::TintStrength => {
+                let (hue, saturation, brightness) = rgb_to_hsv(definition.glass_color);
+                let (hue, saturation) = if self == ShapeParam::TintHue {
+                    (value, saturation)
+                } else {
+                    (hue, value)
+                };
+                definition.glass_color = hsv_to_rgb(hue, saturation, brightness)
+                    .with_alpha(definition.glass_color.alpha());
+            }
+            ShapeParam::GlassOpacity => {
+                definition.glass_color = definition.glass_color.with_alpha(value)
+            }
+        }
+        // The shape row scales the edited outline down rather than showing a
+        // stale hand-tuned mini.
+        definition.mini = None;
+    }
+
+    /// Where `value` sits along the slider, from 0 to 1.
+    fn fraction(self, value: f32) -> f32 {
+        let (start, end) = (*self.range().start(), *self.range().end());
+        ((value - start) / (end - start)).clamp(0.0, 1.0)
+    }
+}
+
+/// The shape editor. While it is open the draft is previewed on the main
+/// hourglass and in the shape row, and the selected shape stays as it was
+/// until Save.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub(crate) struct ShapeEditorState {
+    draft: Option<ShapeDefinition>,
+    /// Contents of the name field while it has keyboard focus.
+    text: Option<String>,
+    open: bool,
+}
+
+impl ShapeEditorState {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+/// Run condition: the editor's name field is consuming keyboard input.
+pub(crate) fn shape_editor_typing(state: Option<Res<ShapeEditorState>>) -> bool {
+    state.is_some_and(|state| state.text.is_some())
+}
+
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+enum ShapeEditorRequest {
+    Open,
+    Save,
+    #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+    Export,
+    Cancel,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum EditorButton {
+    /// The "Edit" chip in the shape row.
+    Toggle,
+    Name,
+    Save,
+    #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+    Export,
+    Cancel,
+}
+
+#[derive(Component)]
+struct ShapeEditorRoot;
+
+#[derive(Component)]
+struct EditorNameText;
+
+/// The filled part of a slider's track.
+#[derive(Component)]
+struct SliderFill(ShapeParam);
+
+#[derive(Component)]
+struct SliderValue(ShapeParam);
+
+fn editor_chip(label: &str, button: EditorButton) -> impl Bundle {
+    (
+        button,
+        Button,
+        Node {
+            flex_grow: 1.0,
+            height: Val::Px(22.0),
+            border: UiRect::all(Val::Px(1.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+        BorderColor(Color::WHITE),
+        children![(
+            Text::new(label),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )],
+    )
+}
+
+fn slider_row(param: ShapeParam) -> impl Bundle {
+    let small_text = |text: String| {
+        (
+            Text::new(text),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )
+    };
+    (
+        Node {
+            column_gap: Val::Px(6.0),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![
+            (
+                small_text(param.label().to_string()),
+                Node {
+                    width: Val::Px(LABEL_WIDTH),
+                    ..default()
+                },
+            ),
+            (
+                param,
+                Interaction::default(),
+                RelativeCursorPosition::default(),
+                Node {
+                    width: Val::Px(SLIDER_WIDTH),
+                    height: Val::Px(12.0),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.05, 0.05, 0.05)),
+                BorderColor(Color::srgb(0.65, 0.65, 0.65)),
+                children![(
+                    SliderFill(param),
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.75, 0.75, 0.75)),
+                )],
+            ),
+            (SliderValue(param), small_text(String::new())),
+        ],
+    )
+}
+
+fn spawn_shape_editor(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Shape Editor"),
+            ShapeEditorRoot,
+            Interaction::default(),
+            FocusPolicy::Block,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(90.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-(LABEL_WIDTH + SLIDER_WIDTH + 60.0) / 2.0)),
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(5.0),
+                padding: UiRect::all(Val::Px(7.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+            BorderColor(Color::srgb(0.65, 0.65, 0.65)),
+            ZIndex(190),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("Shape Editor Name Field"),
+                EditorButton::Name,
+                Button,
+                Node {
+                    height: Val::Px(24.0),
+                    border: UiRect::all(Val::Px(1.0)),
+                    padding: UiRect::horizontal(Val::Px(4.0)),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.05, 0.05, 0.05)),
+                BorderColor(Color::WHITE),
+                children![(
+                    EditorNameText,
+                    Text::new(""),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                )],
+            ));
+
+            for param in ShapeParam::ALL {
+                parent.spawn(slider_row(param));
+            }
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn(editor_chip("Save", EditorButton::Save));
+                    #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+                    row.spawn(editor_chip("Export", EditorButton::Export));
+                    row.spawn(editor_chip("Cancel", EditorButton::Cancel));
+                });
+        });
+}
+
+fn spawn_shape_editor_toggle(mut commands: Commands, query: Query<Entity, With<ShapeRowMarker>>) {
+    if let Ok(row) = query.single() {
+        commands.entity(row).with_child((
+            Name::new("Shape Editor Button"),
+            EditorButton::Toggle,
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(6.0),
+                top: Val::Percent(50.0),
+                margin: UiRect::top(Val::Px(-10.0)),
+                height: Val::Px(20.0),
+                padding: UiRect::horizontal(Val::Px(5.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+            BorderColor(Color::WHITE),
+            children![(
+                Text::new("Edit"),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            )],
+        ));
+    }
+}
+
+fn handle_shape_editor_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &EditorButton, &mut BorderColor),
+        Changed<Interaction>,
+    >,
+    mut state: ResMut<ShapeEditorState>,
+    mut requests: EventWriter<ShapeEditorRequest>,
+) {
+    for (interaction, button, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match button {
+                    EditorButton::Toggle if state.is_open() => {
+                        requests.write(ShapeEditorRequest::Cancel);
+                    }
+                    EditorButton::Toggle => {
+                        requests.write(ShapeEditorRequest::Open);
+                    }
+                    EditorButton::Name => {
+                        state.text = state.draft.as_ref().map(|draft| draft.name.clone());
+                    }
+                    EditorButton::Save => {
+                        requests.write(ShapeEditorRequest::Save);
+                    }
+                    #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+                    EditorButton::Export => {
+                        requests.write(ShapeEditorRequest::Export);
+                    }
+                    EditorButton::Cancel => {
+                        requests.write(ShapeEditorRequest::Cancel);
+                    }
+                }
+                *border_color = BorderColor(Color::srgb(0.0, 1.0, 0.0));
+            }
+            Interaction::Hovered => {
+                *border_color = BorderColor(Color::srgb(0.8, 0.8, 0.8));
+            }
+            Interaction::None => {
+                *border_color = BorderColor(Color::WHITE);
+            }
+        }
+    }
+}
+
+/// Set a parameter from where the mouse is held along its slider.
+fn drag_shape_sliders(
+    sliders: Query<(&Interaction, &RelativeCursorPosition, &ShapeParam)>,
+    mut state: ResMut<ShapeEditorState>,
+) {
+    for (interaction, cursor, &param) in &sliders {
+        let (Interaction::Pressed, Some(position)) = (interaction, cursor.normalized) else {
+            continue;
+        };
+        let Some(mut draft) = state.draft.clone() else {
+            return;
+        };
+        let (start, end) = (*param.range().start(), *param.range().end());
+        param.set(
+            &mut draft,
+            start + position.x.clamp(0.0, 1.0) * (end - start),
+        );
+        if state.draft.as_ref() != Some(&draft) {
+            state.draft = Some(draft);
+        }
+    }
+}
+
+/// Typing into the name field; otherwise Enter saves and Escape cancels the
+/// open editor.
+fn handle_shape_editor_keys(
+    mut keyboard: EventReader<KeyboardInput>,
+    mut state: ResMut<ShapeEditorState>,
+    mut requests: EventWriter<ShapeEditorRequest>,
+) {
+    if !state.is_open() {
+        keyboard.clear();
+        return;
+    }
+
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let state = &mut *state;
+        let (Some(text), Some(draft)) = (state.text.as_mut(), state.draft.as_mut()) else {
+            match event.logical_key {
+                Key::Enter => {
+                    requests.write(ShapeEditorRequest::Save);
+                }
+                Key::Escape => {
+                    requests.write(ShapeEditorRequest::Cancel);
+                }
+                _ => {}
+            }
+            continue;
+        };
+        match &event.logical_key {
+            Key::Enter if text.trim().is_empty() => warn!("Shapes need a name"),
+            Key::Enter => {
+                draft.name = text.trim().to_string();
+                state.text = None;
+            }
+            Key::Escape => state.text = None,
+            Key::Backspace => {
+                text.pop();
+            }
+            Key::Space => text.push(' '),
+            Key::Character(characters) => text.push_str(characters),
+            _ => {}
+        }
+    }
+}
+
+/// Opening copies the current shape under an unused name; Save keeps the
+/// draft in the library and selects it like a shape-row click, Cancel drops
+/// it. Exporting keeps the editor open.
+fn apply_shape_editor_requests(
+    mut requests: EventReader<ShapeEditorRequest>,
+    mut state: ResMut<ShapeEditorState>,
+    mut library: ResMut<ShapeLibrary>,
+    mut config: ResMut<HourglassConfig>,
+    mut pending_flip: ResMut<PendingFlip>,
+    mut timer_commands: EventWriter<TimerCommand>,
+    mut appearance_changed: EventWriter<AppearanceStateChanged>,
+    #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))] mut toasts: EventWriter<
+        ShowToast,
+    >,
+) {
+    for request in requests.read() {
+        match request {
+            ShapeEditorRequest::Open if !state.is_open() => {
+                let mut draft = library.get(&config.shape_type).clone();
+                draft.name = library.unused_name();
+                state.draft = Some(draft);
+                state.text = None;
+                state.open = true;
+            }
+            ShapeEditorRequest::Open => {}
+            ShapeEditorRequest::Save => {
+                if !std::mem::take(&mut state.open) {
+                    continue;
+                }
+                state.text = None;
+                let Some(draft) = state.draft.take() else {
+                    continue;
+                };
+                config.shape_type = draft.shape();
+                config.shape_mode = ShapeMode::Static;
+                library.save(draft);
+                library.set_draft(None);
+                if let Some(command) = extension_appearance_change_command(&mut pending_flip) {
+                    timer_commands.write(command);
+                }
+                appearance_changed.write_default();
+            }
+            #[cfg(all(feature = "shape_assets", not(target_arch = "wasm32")))]
+            ShapeEditorRequest::Export => {
+                let Some(draft) = &state.draft else {
+                    continue;
+                };
+                toasts.write(ShowToast(match crate::shapes::export_shape(draft) {
+                    Ok(path) => format!("Exported `{}` to {}", draft.name, path.display()),
+                    Err(error) => format!("Could not export `{}`: {error}", draft.name),
+                }));
+            }
+            ShapeEditorRequest::Cancel => {
+                if std::mem::take(&mut state.open) {
+                    state.draft = None;
+                    state.text = None;
+                    library.set_draft(None);
+                }
+            }
+        }
+    }
+}
+
+/// Show the draft on the hourglass and in the shape row while the editor is
+/// open. Like the colour picker, it goes through `ShapePreview` and leaves
+/// `HourglassConfig` alone, so persistence and the extension only see saved
+/// shapes.
+fn preview_shape_draft(
+    state: Res<ShapeEditorState>,
+    mut library: ResMut<ShapeLibrary>,
+    mut preview: ResMut<ShapePreview>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    let draft = state.draft.as_ref().filter(|_| state.is_open());
+    if let Some(draft) = draft {
+        library.set_draft(Some(draft.clone()));
+    }
+    preview.set_if_neq(ShapePreview(draft.map(ShapeDefinition::shape)));
+}
+
+fn update_shape_editor_view(
+    state: Res<ShapeEditorState>,
+    mut root_query: Query<&mut Node, With<ShapeEditorRoot>>,
+    mut fill_query: Query<(&SliderFill, &mut Node), Without<ShapeEditorRoot>>,
+    mut value_query: Query<(&SliderValue, &mut Text), Without<EditorNameText>>,
+    mut name_query: Query<&mut Text, With<EditorNameText>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    if let Ok(mut root) = root_query.single_mut() {
+        root.display = if state.is_open() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    let Some(draft) = state.draft.as_ref().filter(|_| state.is_open()) else {
+        return;
+    };
+
+    for (SliderFill(param), mut node) in &mut fill_query {
+        node.width = Val::Percent(param.fraction(param.get(draft)) * 100.0);
+    }
+    for (SliderValue(param), mut text) in &mut value_query {
+        let value = param.get(draft);
+        text.0 = match param {
+            ShapeParam::TintHue => format!("{value:.0}°"),
+            ShapeParam::BulbCurvature
+            | ShapeParam::BulbWidth
+            | ShapeParam::NeckCurvature
+            | ShapeParam::TintStrength
+            | ShapeParam::GlassOpacity => format!("{value:.2}"),
+            _ => format!("{value:.0}"),
+        };
+    }
+    if let Ok(mut text) = name_query.single_mut() {
+        text.0 = match &state.text {
+            Some(typed) => format!("{typed}_"),
+            None => draft.name.clone(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::HourglassShape;
+    use approx::assert_abs_diff_eq;
+
+    fn shape_editor_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(crate::timer::TimerPlugin)
+            .init_resource::<HourglassConfig>()
+            .init_resource::<crate::resources::TimerState>()
+            .init_resource::<PendingFlip>()
+            .add_event::<AppearanceStateChanged>()
+            .add_event::<KeyboardInput>()
+            .add_event::<crate::ui::toast::ShowToast>()
+            .add_plugins(ShapeEditorPlugin);
+        app.world_mut().spawn((ShapeRowMarker, Node::default()));
+        app.update();
+        app
+    }
+
+    fn appearance_changes(app: &App) -> usize {
+        app.world()
+            .resource::<Events<AppearanceStateChanged>>()
+            .len()
+    }
+
+    fn edit_draft(app: &mut App, param: ShapeParam, value: f32) {
+        let mut state = app.world_mut().resource_mut::<ShapeEditorState>();
+        param.set(state.draft.as_mut().unwrap(), value);
+        app.update();
+    }
+
+    fn type_into_editor(app: &mut App, keys: &[Key]) {
+        for key in keys {
+            app.world_mut().send_event(KeyboardInput {
+                key_code: KeyCode::Unidentified(bevy::input::keyboard::NativeKeyCode::Unidentified),
+                logical_key: key.clone(),
+                state: ButtonState::Pressed,
+                text: None,
+                repeat: false,
+                window: Entity::PLACEHOLDER,
+            });
+        }
+        app.update();
+    }
+
+    #[test]
+    fn params_read_back_what_they_set_within_their_range() {
+        let mut definition = ShapeDefinition::builtin()[1].clone();
+        for param in ShapeParam::ALL {
+            let middle = (param.range().start() + param.range().end()) / 2.0;
+            param.set(&mut definition, middle);
+            assert_abs_diff_eq!(param.get(&definition), middle, epsilon = 1e-3);
+            param.set(&mut definition, -1e6);
+            assert_abs_diff_eq!(
+                param.get(&definition),
+                *param.range().start(),
+                epsilon = 1e-3
+            );
+        }
+        assert_eq!(definition.mini, None);
+
+        // Modern's straight neck curves, and flattens back to straight.
+        let mut modern = ShapeDefinition::builtin()[1].clone();
+        ShapeParam::NeckCurvature.set(&mut modern, 1.0);
+        assert!(matches!(modern.main.neck, ShapeNeck::Curved { .. }));
+        ShapeParam::NeckCurvature.set(&mut modern, 0.0);
+        assert_eq!(
+            modern.main.neck,
+            ShapeNeck::Straight {
+                width: 12.0,
+                height: 32.0
+            }
+        );
+    }
+
+    #[test]
+    fn editing_previews_the_draft_then_saving_adds_a_named_shape() {
+        let mut app = shape_editor_app();
+        app.world_mut().resource_mut::<HourglassConfig>().shape_type = HourglassShape::SLIM;
+        app.world_mut().send_event(ShapeEditorRequest::Open);
+        app.update();
+
+        edit_draft(&mut app, ShapeParam::NeckWidth, 30.0);
+        let custom = HourglassShape::from_name("custom 1").unwrap();
+        assert_eq!(
+            app.world().resource::<ShapePreview>().0,
+            Some(custom.clone())
+        );
+        assert_eq!(
+            app.world().resource::<HourglassConfig>().shape_type,
+            HourglassShape::SLIM
+        );
+        let library = app.world().resource::<ShapeLibrary>();
+        let preview = library.get(&custom);
+        assert_eq!(
+            ShapeParam::BulbWidth.get(preview),
+            ShapeParam::BulbWidth.get(library.get(&HourglassShape::SLIM))
+        );
+        assert_eq!(ShapeParam::NeckWidth.get(preview), 30.0);
+        assert!(library.saved().is_empty());
+        assert_eq!(appearance_changes(&app), 0);
+
+        app.world_mut().resource_mut::<ShapeEditorState>().text = Some(String::new());
+        type_into_editor(&mut app, &[Key::Character("Vase".into()), Key::Enter]);
+        type_into_editor(&mut app, &[Key::Enter]);
+
+        let vase = HourglassShape::from_name("vase").unwrap();
+        assert_eq!(app.world().resource::<HourglassConfig>().shape_type, vase);
+        let library = app.world().resource::<ShapeLibrary>();
+        assert_eq!(library.saved().len(), 1);
+        assert_eq!(ShapeParam::NeckWidth.get(library.get(&vase)), 30.0);
+        assert!(library.find(&custom).is_none());
+        assert_eq!(app.world().resource::<ShapePreview>().0, None);
+        assert_eq!(appearance_changes(&app), 1);
+        assert!(!app.world().resource::<ShapeEditorState>().is_open());
+    }
+
+    #[test]
+    fn cancelling_the_shape_editor_restores_the_previous_shape() {
+        let mut app = shape_editor_app();
+        app.world_mut().resource_mut::<HourglassConfig>().shape_mode = ShapeMode::Morphing;
+        let original = app.world().resource::<HourglassConfig>().clone();
+        app.world_mut().send_event(ShapeEditorRequest::Open);
+        app.update();
+        edit_draft(&mut app, ShapeParam::PlateWidth, 200.0);
+        let config = app.world().resource::<HourglassConfig>();
+        assert_eq!(
+            app.world().resource::<ShapePreview>().shape(config).1,
+            ShapeMode::Static
+        );
+        assert_eq!(*config, original);
+
+        type_into_editor(&mut app, &[Key::Escape]);
+        assert_eq!(*app.world().resource::<HourglassConfig>(), original);
+        assert_eq!(app.world().resource::<ShapePreview>().0, None);
+        assert_eq!(
+            *app.world().resource::<ShapeLibrary>(),
+            ShapeLibrary::default()
+        );
+        assert_eq!(appearance_changes(&app), 0);
+    }
+
+    #[cfg(all(feature = "persistence", not(target_arch = "wasm32")))]
+    #[test]
+    fn timer_changes_while_editing_save_the_selected_shape() {
+        use crate::persistence::{PersistencePlugin, StateFilePath};
+        use crate::snapshot::SnapshotV1;
+
+        let dir = std::env::temp_dir().join(format!(
+            "hourglass-timer-test-{}-shape-editor",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("state.json");
+        let mut app = shape_editor_app();
+        app.insert_resource(StateFilePath(Some(path.clone())))
+            .add_plugins(PersistencePlugin);
+        app.world_mut().resource_mut::<HourglassConfig>().shape_type = HourglassShape::SLIM;
+        app.world_mut().send_event(ShapeEditorRequest::Open);
+        app.update();
+        edit_draft(&mut app, ShapeParam::NeckWidth, 30.0);
+
+        app.world_mut().send_event(TimerCommand::Toggle);
+        app.update();
+        let saved: SnapshotV1 =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.appearance.shape_type, HourglassShape::SLIM);
+        assert_eq!(saved.appearance.shape_mode, ShapeMode::Static);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
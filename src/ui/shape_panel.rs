@@ -1,14 +1,18 @@
 use crate::resources::{
     AppearanceStateChanged, HourglassConfig, HourglassShape, PendingFlip, SAND_COLOR, ShapeMode,
+    ShapePreview,
 };
 use crate::timer::{TimerCommand, TimerSystems};
 use crate::ui::{AppearancePanelVisible, ShapeRowMarker, extension_appearance_change_command};
 use bevy::asset::embedded_asset;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 use bevy_hourglass::{Hourglass, HourglassMeshBuilder, HourglassMeshSandConfig};
 use rand::Rng;
+use rand::seq::SliceRandom;
 
-use crate::hourglass::{get_mini_shape_config, within_click_radius};
+use crate::hourglass::{HourglassCamera, within_click_radius};
+use crate::shapes::ShapeLibrary;
 
 // Bevy's default font is an ASCII-only FiraMono subset. We embed Fira Sans
 // Regular into the binary so the shape-row buttons can render non-ASCII
@@ -21,41 +25,39 @@ impl Plugin for ShapePanelPlugin {
     fn build(&self, app: &mut App) {
         embedded_asset!(app, "fonts/FiraSans-Regular.ttf");
 
-        app.add_systems(
-            PostStartup,
-            (
-                spawn_shape_buttons,
-                spawn_random_shape_button,
-                spawn_morphing_button,
-            ),
-        )
-        .add_systems(
-            Update,
-            (
-                handle_shape_button_clicks,
-                handle_random_shape_button_clicks,
-                handle_morphing_button_clicks,
+        app.init_resource::<ShapeLibrary>()
+            .add_systems(
+                PostStartup,
+                (spawn_random_shape_button, spawn_morphing_button),
             )
-                .in_set(TimerSystems::Input),
-        )
-        .add_systems(
-            Update,
-            (
-                update_mini_hourglass_colors,
-                handle_hover_effects,
-                update_hourglass_layering,
-                update_hover_timers,
-                update_mini_hourglass_positions,
-                update_shape_panel_visibility,
-            ),
-        );
+            .add_systems(
+                Update,
+                (
+                    handle_shape_button_clicks,
+                    handle_random_shape_button_clicks,
+                    handle_morphing_button_clicks,
+                )
+                    .in_set(TimerSystems::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    rebuild_shape_buttons,
+                    update_mini_hourglass_colors,
+                    handle_hover_effects,
+                    update_hourglass_layering,
+                    update_hover_timers,
+                    update_mini_hourglass_positions,
+                    update_shape_panel_visibility,
+                ),
+            );
     }
 }
 
 fn handle_hover_effects(
     mut commands: Commands,
-    windows: Query<&Window>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<HourglassCamera>>,
     shape_row_query: Query<(&ComputedNode, &GlobalTransform), With<ShapeRowMarker>>,
     mini_hourglass_query: Query<(Entity, &Transform, &ShapeButton), With<MiniHourglass>>,
     morphing_button_query: Query<(Entity, &Transform), (With<MorphingButton>, With<MiniHourglass>)>,
@@ -69,77 +71,74 @@ fn handle_hover_effects(
     if !appearance_visible.0 {
         return;
     }
-    if let Ok(window) = windows.single() {
-        if let Some(cursor_position) = window.cursor_position() {
-            if !shape_pointer_interactions_enabled(cursor_position, &shape_row_query) {
-                for hovered_entity in hovered_query.iter() {
-                    commands.entity(hovered_entity).remove::<HoveredHourglass>();
-                }
-                return;
+    if let Ok(window) = windows.single()
+        && let Some(cursor_position) = window.cursor_position()
+    {
+        if !shape_pointer_interactions_enabled(cursor_position, &shape_row_query) {
+            for hovered_entity in hovered_query.iter() {
+                commands.entity(hovered_entity).remove::<HoveredHourglass>();
             }
+            return;
+        }
 
-            if let Ok((camera, camera_transform)) = camera_query.single() {
-                if let Ok(world_position) =
-                    camera.viewport_to_world_2d(camera_transform, cursor_position)
-                {
-                    let mut currently_hovered = None;
+        if let Ok((camera, camera_transform)) = camera_query.single()
+            && let Ok(world_position) =
+                camera.viewport_to_world_2d(camera_transform, cursor_position)
+        {
+            let mut currently_hovered = None;
 
-                    // Check if hovering over any mini hourglass
-                    for (entity, transform, _shape_button) in mini_hourglass_query.iter() {
-                        let distance = world_position.distance(transform.translation.truncate());
+            // Check if hovering over any mini hourglass
+            for (entity, transform, _shape_button) in mini_hourglass_query.iter() {
+                let distance = world_position.distance(transform.translation.truncate());
 
-                        // Adjust detection radius based on current scale
-                        let detection_radius = 30.0 * transform.scale.x;
+                // Adjust detection radius based on current scale
+                let detection_radius = 30.0 * transform.scale.x;
 
-                        if distance < detection_radius {
-                            currently_hovered = Some(entity);
-                            break;
-                        }
-                    }
+                if distance < detection_radius {
+                    currently_hovered = Some(entity);
+                    break;
+                }
+            }
 
-                    // Check if hovering over the random shape button
-                    if currently_hovered.is_none() {
-                        if let Ok((entity, transform)) = random_shape_button_query.single() {
-                            let distance =
-                                world_position.distance(transform.translation.truncate());
-                            let detection_radius = 20.0 * transform.scale.x;
-
-                            if distance < detection_radius {
-                                currently_hovered = Some(entity);
-                            }
-                        }
-                    }
+            // Check if hovering over the random shape button
+            if currently_hovered.is_none()
+                && let Ok((entity, transform)) = random_shape_button_query.single()
+            {
+                let distance = world_position.distance(transform.translation.truncate());
+                let detection_radius = 20.0 * transform.scale.x;
 
-                    // Check if hovering over the morphing button
-                    if currently_hovered.is_none() {
-                        if let Ok((entity, transform)) = morphing_button_query.single() {
-                            let distance =
-                                world_position.distance(transform.translation.truncate());
-                            let detection_radius = 20.0 * transform.scale.x;
-
-                            if distance < detection_radius {
-                                currently_hovered = Some(entity);
-                            }
-                        }
-                    }
+                if distance < detection_radius {
+                    currently_hovered = Some(entity);
+                }
+            }
 
-                    // Remove HoveredHourglass from all entities that are no longer hovered
-                    for hovered_entity in hovered_query.iter() {
-                        if Some(hovered_entity) != currently_hovered {
-                            commands.entity(hovered_entity).remove::<HoveredHourglass>();
-                        }
-                    }
+            // Check if hovering over the morphing button
+            if currently_hovered.is_none()
+                && let Ok((entity, transform)) = morphing_button_query.single()
+            {
+                let distance = world_position.distance(transform.translation.truncate());
+                let detection_radius = 20.0 * transform.scale.x;
 
-                    // Add HoveredHourglass to currently hovered entity if it doesn't have it
-                    if let Some(hovered_entity) = currently_hovered {
-                        if !hovered_query.contains(hovered_entity) {
-                            commands
-                                .entity(hovered_entity)
-                                .insert(HoveredHourglass { timer: 0.0 });
-                        }
-                    }
+                if distance < detection_radius {
+                    currently_hovered = Some(entity);
                 }
             }
+
+            // Remove HoveredHourglass from all entities that are no longer hovered
+            for hovered_entity in hovered_query.iter() {
+                if Some(hovered_entity) != currently_hovered {
+                    commands.entity(hovered_entity).remove::<HoveredHourglass>();
+                }
+            }
+
+            // Add HoveredHourglass to currently hovered entity if it doesn't have it
+            if let Some(hovered_entity) = currently_hovered
+                && !hovered_query.contains(hovered_entity)
+            {
+                commands
+                    .entity(hovered_entity)
+                    .insert(HoveredHourglass { timer: 0.0 });
+            }
         }
     }
 }
@@ -159,6 +158,7 @@ fn shape_button_scale(is_hovered: bool, is_selected: bool) -> f32 {
 
 fn update_hourglass_layering(
     config: Res<HourglassConfig>,
+    preview: Res<ShapePreview>,
     mut mini_hourglass_query: Query<(
         &mut Transform,
         &MiniHourglass,
@@ -178,12 +178,13 @@ fn update_hourglass_layering(
         ),
     >,
 ) {
+    let (shape, shape_mode) = preview.shape(&config);
     // Handle regular hourglass buttons
     for (mut transform, mini_hourglass, shape_button, hovered) in mini_hourglass_query.iter_mut() {
         let base_position = mini_hourglass.base_position;
 
         // Visual effects with scaling only
-        let scale = shape_button_scale(hovered.is_some(), config.shape_type == shape_button.shape);
+        let scale = shape_button_scale(hovered.is_some(), *shape == shape_button.shape);
 
         // Apply scale
         transform.scale = Vec3::splat(scale);
@@ -197,7 +198,7 @@ fn update_hourglass_layering(
         let base_position = mini_hourglass.base_position;
 
         // Visual effects with scaling only
-        let scale = shape_button_scale(hovered.is_some(), config.shape_mode == ShapeMode::Morphing);
+        let scale = shape_button_scale(hovered.is_some(), shape_mode == ShapeMode::Morphing);
 
         // Apply scale
         transform.scale = Vec3::splat(scale);
@@ -240,66 +241,66 @@ fn update_mini_hourglass_colors(
 }
 
 fn update_mini_hourglass_positions(
-    windows: Query<&Window>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<HourglassCamera>>,
     shape_row_query: Query<(&ComputedNode, &GlobalTransform), With<ShapeRowMarker>>,
     mut mini_hourglass_query: Query<(&mut Transform, &mut MiniHourglass), With<MiniHourglass>>,
 ) {
-    if let Ok((shape_row_node, shape_row_transform)) = shape_row_query.single() {
-        if let Ok(window) = windows.single() {
-            if let Ok((camera, camera_transform)) = camera_query.single() {
-                let window_width = window.width();
-                let horizontal_scale = if cfg!(feature = "chrome_extension") {
-                    ((window_width - 36.0) / 280.0).clamp(0.55, 1.0)
-                } else {
-                    1.0
-                };
-
-                #[cfg(feature = "chrome_extension")]
-                let Some(shape_row_screen_pos) =
-                    extension_shape_row_screen_position(shape_row_node, shape_row_transform)
-                else {
-                    return;
-                };
-
-                #[cfg(not(feature = "chrome_extension"))]
-                let shape_row_screen_pos = {
-                    let _ = (shape_row_node, shape_row_transform);
-                    Vec2::new(window_width / 2.0, 60.0)
-                };
-
-                if let Ok(shape_row_world_pos) =
-                    camera.viewport_to_world_2d(camera_transform, shape_row_screen_pos)
-                {
-                    // Update each mini hourglass position relative to the shape row
-                    for (mut transform, mut mini_hourglass) in mini_hourglass_query.iter_mut() {
-                        // Calculate new position based on original X offset from center
-                        let new_position = Vec3::new(
-                            shape_row_world_pos.x
-                                + if cfg!(feature = "chrome_extension") {
-                                    (mini_hourglass.original_x - 25.0) * horizontal_scale
-                                } else {
-                                    mini_hourglass.original_x
-                                },
-                            shape_row_world_pos.y,
-                            10.0, // Keep elevated Z position
-                        );
-
-                        // Update both current transform and stored base position
-                        transform.translation = new_position;
-                        mini_hourglass.base_position = new_position;
-                    }
-                }
+    if let Ok((shape_row_node, shape_row_transform)) = shape_row_query.single()
+        && let Ok(window) = windows.single()
+        && let Ok((camera, camera_transform)) = camera_query.single()
+    {
+        let window_width = window.width();
+        let row_width = if cfg!(feature = "chrome_extension") {
+            280.0
+        } else {
+            mini_hourglass_query
+                .iter()
+                .map(|(_, mini_hourglass)| 2.0 * mini_hourglass.original_x.abs() + 50.0)
+                .fold(0.0, f32::max)
+        };
+        let horizontal_scale = shape_row_spacing(window_width, row_width);
+
+        // Follow the row node, which moves down as the colour row wraps.
+        let Some(shape_row_screen_pos) =
+            shape_row_screen_position(shape_row_node, shape_row_transform)
+        else {
+            return;
+        };
+
+        if let Ok(shape_row_world_pos) =
+            camera.viewport_to_world_2d(camera_transform, shape_row_screen_pos)
+        {
+            // Update each mini hourglass position relative to the shape row
+            for (mut transform, mut mini_hourglass) in mini_hourglass_query.iter_mut() {
+                // Calculate new position based on original X offset from center
+                let new_position = Vec3::new(
+                    shape_row_world_pos.x
+                        + if cfg!(feature = "chrome_extension") {
+                            (mini_hourglass.original_x - 25.0) * horizontal_scale
+                        } else {
+                            mini_hourglass.original_x * horizontal_scale
+                        },
+                    shape_row_world_pos.y,
+                    10.0, // Keep elevated Z position
+                );
+
+                // Update both current transform and stored base position
+                transform.translation = new_position;
+                mini_hourglass.base_position = new_position;
             }
         }
     }
 }
 
-#[cfg(feature = "chrome_extension")]
-fn extension_shape_row_screen_position(
-    node: &ComputedNode,
-    transform: &GlobalTransform,
-) -> Option<Vec2> {
+/// How far to squeeze the shape row's spacing so a row `row_width` wide fits
+/// the window. The buttons overlap a little rather than shrinking below
+/// legibility.
+fn shape_row_spacing(window_width: f32, row_width: f32) -> f32 {
+    ((window_width - 36.0) / row_width.max(1.0)).clamp(0.55, 1.0)
+}
+
+fn shape_row_screen_position(node: &ComputedNode, transform: &GlobalTransform) -> Option<Vec2> {
     ui_node_logical_geometry(node, transform).map(|(center, _size)| center)
 }
 
@@ -377,24 +378,37 @@ struct HoveredHourglass {
     timer: f32, // Timer for hover effect duration
 }
 
-fn spawn_shape_buttons(
+/// Horizontal offset of a shape-row button from the row's centre: one mini
+/// per shape, then the random and morphing buttons, 50 apart.
+fn shape_row_offset(index: usize, shape_count: usize) -> f32 {
+    50.0 * index as f32 - 25.0 * shape_count as f32
+}
+
+/// Spawn one mini hourglass per shape in the library, replacing the previous
+/// set whenever shape files are added, edited or removed.
+fn rebuild_shape_buttons(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    library: Res<ShapeLibrary>,
+    appearance_visible: Res<AppearancePanelVisible>,
+    old_buttons: Query<Entity, With<ShapeButton>>,
+    mut random_button: Query<&mut MiniHourglass, With<RandomShapeButton>>,
+    mut morphing_button: Query<
+        &mut MiniHourglass,
+        (With<MorphingButton>, Without<RandomShapeButton>),
+    >,
 ) {
-    // Spawn mini hourglasses in 3D space positioned horizontally for the shape row
-    let shapes = [
-        HourglassShape::Classic,
-        HourglassShape::Modern,
-        HourglassShape::Slim,
-        HourglassShape::Wide,
-    ];
-
-    for (i, shape) in shapes.iter().enumerate() {
-        // Calculate offset from center for horizontal spacing
-        let x_offset = -100.0 + (i as f32 * 50.0); // Offset from center
+    if !library.is_changed() {
+        return;
+    }
+    for entity in &old_buttons {
+        commands.entity(entity).despawn();
+    }
 
-        let (body_config, plates_config) = get_mini_shape_config(*shape);
+    let shape_count = library.definitions().len();
+    for (i, definition) in library.definitions().iter().enumerate() {
+        let (body_config, plates_config) = definition.mini_configs();
 
         // Start with a temporary position - will be updated by update_mini_hourglass_positions
         let temp_position = Vec3::new(0.0, 0.0, 10.0);
@@ -412,23 +426,39 @@ fn spawn_shape_buttons(
         // Remove the Hourglass component from mini hourglasses since they should be static displays
         commands.entity(entity).remove::<Hourglass>();
 
+        let shape = definition.shape();
         commands.entity(entity).insert((
             MiniHourglass {
                 base_position: temp_position,
-                original_x: x_offset, // Store the offset from center
+                original_x: shape_row_offset(i, shape_count), // Store the offset from center
+            },
+            Name::new(format!("Mini Hourglass {}", shape.name())),
+            ShapeButton { shape }, // Make it clickable
+            if appearance_visible.0 {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
             },
-            ShapeButton { shape: *shape }, // Make it clickable
-            Name::new(format!("Mini Hourglass {shape:?}")),
         ));
     }
+
+    // The action buttons follow the last shape.
+    if let Ok(mut button) = random_button.single_mut() {
+        button.original_x = shape_row_offset(shape_count, shape_count);
+    }
+    if let Ok(mut button) = morphing_button.single_mut() {
+        button.original_x = shape_row_offset(shape_count + 1, shape_count);
+    }
 }
 
 fn spawn_morphing_button(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     asset_server: Res<AssetServer>,
+    library: Res<ShapeLibrary>,
 ) {
-    let x_offset = 150.0;
+    let shape_count = library.definitions().len();
+    let x_offset = shape_row_offset(shape_count + 1, shape_count);
 
     let temp_position = Vec3::new(0.0, 0.0, 10.0);
 
@@ -464,8 +494,10 @@ fn spawn_random_shape_button(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     asset_server: Res<AssetServer>,
+    library: Res<ShapeLibrary>,
 ) {
-    let x_offset = 100.0;
+    let shape_count = library.definitions().len();
+    let x_offset = shape_row_offset(shape_count, shape_count);
 
     let temp_position = Vec3::new(0.0, 0.0, 10.0);
 
@@ -497,79 +529,67 @@ fn spawn_random_shape_button(
     });
 }
 
-/// Pick a random hourglass shape different from `current`, re-rolling until it
-/// differs.
-fn pick_distinct_shape(current: HourglassShape, rng: &mut impl Rng) -> HourglassShape {
-    let shapes = [
-        HourglassShape::Classic,
-        HourglassShape::Modern,
-        HourglassShape::Slim,
-        HourglassShape::Wide,
-    ];
-    let mut new_shape = shapes[rng.gen_range(0..shapes.len())];
-    // Re-roll until we get a shape different from the current one
-    while new_shape == current {
-        new_shape = shapes[rng.gen_range(0..shapes.len())];
-    }
-    new_shape
+/// Pick a random shape from `shapes` other than `current`. Returns `current`
+/// if there is nothing else to pick.
+fn pick_distinct_shape(
+    current: &HourglassShape,
+    shapes: &[HourglassShape],
+    rng: &mut impl Rng,
+) -> HourglassShape {
+    let others: Vec<_> = shapes.iter().filter(|shape| *shape != current).collect();
+    others
+        .choose(rng)
+        .map_or_else(|| current.clone(), |shape| (*shape).clone())
 }
 
 fn handle_random_shape_button_clicks(
     mouse_input: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<HourglassCamera>>,
     shape_row_query: Query<(&ComputedNode, &GlobalTransform), With<ShapeRowMarker>>,
     random_shape_button_query: Query<&Transform, (With<RandomShapeButton>, With<MiniHourglass>)>,
+    library: Res<ShapeLibrary>,
     mut config: ResMut<HourglassConfig>,
-    mut pending_flip: ResMut<PendingFlip>,
-    mut timer_commands: EventWriter<TimerCommand>,
     mut appearance_changed: EventWriter<AppearanceStateChanged>,
     appearance_visible: Res<AppearancePanelVisible>,
 ) {
     if !appearance_visible.0 {
         return;
     }
-    if mouse_input.just_pressed(MouseButton::Left) {
-        if let Ok(window) = windows.single() {
-            if let Some(cursor_position) = window.cursor_position() {
-                if !shape_pointer_interactions_enabled(cursor_position, &shape_row_query) {
-                    return;
-                }
+    if mouse_input.just_pressed(MouseButton::Left)
+        && let Ok(window) = windows.single()
+        && let Some(cursor_position) = window.cursor_position()
+    {
+        if !shape_pointer_interactions_enabled(cursor_position, &shape_row_query) {
+            return;
+        }
 
-                if let Ok((camera, camera_transform)) = camera_query.single() {
-                    if let Ok(world_position) =
-                        camera.viewport_to_world_2d(camera_transform, cursor_position)
-                    {
-                        if let Ok(transform) = random_shape_button_query.single() {
-                            if within_click_radius(
-                                world_position,
-                                transform.translation.truncate(),
-                                20.0,
-                                transform.scale.x,
-                            ) {
-                                let mut rng = rand::thread_rng();
-                                let new_shape = pick_distinct_shape(config.shape_type, &mut rng);
-                                config.shape_type = new_shape;
-                                config.shape_mode = ShapeMode::Static;
-                                if let Some(command) =
-                                    extension_appearance_change_command(&mut pending_flip)
-                                {
-                                    timer_commands.write(command);
-                                }
-                                appearance_changed.write_default();
-                            }
-                        }
-                    }
-                }
-            }
+        if let Ok((camera, camera_transform)) = camera_query.single()
+            && let Ok(world_position) =
+                camera.viewport_to_world_2d(camera_transform, cursor_position)
+            && let Ok(transform) = random_shape_button_query.single()
+            && within_click_radius(
+                world_position,
+                transform.translation.truncate(),
+                20.0,
+                transform.scale.x,
+            )
+        {
+            let mut rng = rand::thread_rng();
+            let shapes: Vec<_> = library.shapes().collect();
+            let new_shape = pick_distinct_shape(&config.shape_type, &shapes, &mut rng);
+            // The hourglass tweens into the new shape; the run carries on.
+            config.shape_type = new_shape;
+            config.shape_mode = ShapeMode::Static;
+            appearance_changed.write_default();
         }
     }
 }
 
 fn handle_morphing_button_clicks(
     mouse_input: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<HourglassCamera>>,
     shape_row_query: Query<(&ComputedNode, &GlobalTransform), With<ShapeRowMarker>>,
     morphing_button_query: Query<&Transform, (With<MorphingButton>, With<MiniHourglass>)>,
     mut config: ResMut<HourglassConfig>,
@@ -581,41 +601,38 @@ fn handle_morphing_button_clicks(
     if !appearance_visible.0 {
         return;
     }
-    if mouse_input.just_pressed(MouseButton::Left) {
-        if let Ok(window) = windows.single() {
-            if let Some(cursor_position) = window.cursor_position() {
-                if !shape_pointer_interactions_enabled(cursor_position, &shape_row_query) {
-                    return;
-                }
+    if mouse_input.just_pressed(MouseButton::Left)
+        && let Ok(window) = windows.single()
+        && let Some(cursor_position) = window.cursor_position()
+    {
+        if !shape_pointer_interactions_enabled(cursor_position, &shape_row_query) {
+            return;
+        }
 
-                if let Ok((camera, camera_transform)) = camera_query.single() {
-                    // Convert screen coordinates to world coordinates
-                    if let Ok(world_position) =
-                        camera.viewport_to_world_2d(camera_transform, cursor_position)
-                    {
-                        // Check if click is near the morphing button
-                        if let Ok(transform) = morphing_button_query.single() {
-                            if within_click_radius(
-                                world_position,
-                                transform.translation.truncate(),
-                                20.0,
-                                transform.scale.x,
-                            ) {
-                                // Toggle morphing mode
-                                if config.shape_mode == ShapeMode::Static {
-                                    config.shape_mode = ShapeMode::Morphing;
-                                } else {
-                                    config.shape_mode = ShapeMode::Static;
-                                }
-                                if let Some(command) =
-                                    extension_appearance_change_command(&mut pending_flip)
-                                {
-                                    timer_commands.write(command);
-                                }
-                                appearance_changed.write_default();
-                            }
-                        }
+        if let Ok((camera, camera_transform)) = camera_query.single() {
+            // Convert screen coordinates to world coordinates
+            if let Ok(world_position) =
+                camera.viewport_to_world_2d(camera_transform, cursor_position)
+            {
+                // Check if click is near the morphing button
+                if let Ok(transform) = morphing_button_query.single()
+                    && within_click_radius(
+                        world_position,
+                        transform.translation.truncate(),
+                        20.0,
+                        transform.scale.x,
+                    )
+                {
+                    // Toggle morphing mode
+                    if config.shape_mode == ShapeMode::Static {
+                        config.shape_mode = ShapeMode::Morphing;
+                    } else {
+                        config.shape_mode = ShapeMode::Static;
+                    }
+                    if let Some(command) = extension_appearance_change_command(&mut pending_flip) {
+                        timer_commands.write(command);
                     }
+                    appearance_changed.write_default();
                 }
             }
         }
@@ -624,50 +641,43 @@ fn handle_morphing_button_clicks(
 
 fn handle_shape_button_clicks(
     mouse_input: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<HourglassCamera>>,
     shape_row_query: Query<(&ComputedNode, &GlobalTransform), With<ShapeRowMarker>>,
     mini_hourglass_query: Query<(&Transform, &ShapeButton), With<MiniHourglass>>,
     mut config: ResMut<HourglassConfig>,
-    mut pending_flip: ResMut<PendingFlip>,
-    mut timer_commands: EventWriter<TimerCommand>,
     mut appearance_changed: EventWriter<AppearanceStateChanged>,
     appearance_visible: Res<AppearancePanelVisible>,
 ) {
     if !appearance_visible.0 {
         return;
     }
-    if mouse_input.just_pressed(MouseButton::Left) {
-        if let Ok(window) = windows.single() {
-            if let Some(cursor_position) = window.cursor_position() {
-                if !shape_pointer_interactions_enabled(cursor_position, &shape_row_query) {
-                    return;
-                }
+    if mouse_input.just_pressed(MouseButton::Left)
+        && let Ok(window) = windows.single()
+        && let Some(cursor_position) = window.cursor_position()
+    {
+        if !shape_pointer_interactions_enabled(cursor_position, &shape_row_query) {
+            return;
+        }
 
-                if let Ok((camera, camera_transform)) = camera_query.single() {
-                    // Convert screen coordinates to world coordinates
-                    if let Ok(world_position) =
-                        camera.viewport_to_world_2d(camera_transform, cursor_position)
-                    {
-                        // Check if click is near any mini hourglass
-                        for (transform, shape_button) in mini_hourglass_query.iter() {
-                            if within_click_radius(
-                                world_position,
-                                transform.translation.truncate(),
-                                30.0,
-                                transform.scale.x,
-                            ) {
-                                config.shape_type = shape_button.shape;
-                                config.shape_mode = ShapeMode::Static; // Set to static when selecting a specific shape
-                                if let Some(command) =
-                                    extension_appearance_change_command(&mut pending_flip)
-                                {
-                                    timer_commands.write(command);
-                                }
-                                appearance_changed.write_default();
-                                break;
-                            }
-                        }
+        if let Ok((camera, camera_transform)) = camera_query.single() {
+            // Convert screen coordinates to world coordinates
+            if let Ok(world_position) =
+                camera.viewport_to_world_2d(camera_transform, cursor_position)
+            {
+                // Check if click is near any mini hourglass
+                for (transform, shape_button) in mini_hourglass_query.iter() {
+                    if within_click_radius(
+                        world_position,
+                        transform.translation.truncate(),
+                        30.0,
+                        transform.scale.x,
+                    ) {
+                        // The hourglass tweens into the new shape; the run carries on.
+                        config.shape_type = shape_button.shape.clone();
+                        config.shape_mode = ShapeMode::Static; // Set to static when selecting a specific shape
+                        appearance_changed.write_default();
+                        break;
                     }
                 }
             }
@@ -678,22 +688,19 @@ fn handle_shape_button_clicks(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy::asset::AssetPlugin;
+    use bevy::render::mesh::MeshPlugin;
     use rand::SeedableRng;
     use rand::rngs::StdRng;
 
-    const ALL_SHAPES: [HourglassShape; 4] = [
-        HourglassShape::Classic,
-        HourglassShape::Modern,
-        HourglassShape::Slim,
-        HourglassShape::Wide,
-    ];
+    const ALL_SHAPES: [HourglassShape; 4] = HourglassShape::BUILTIN;
 
     #[test]
     fn pick_distinct_shape_always_differs_from_current() {
         for current in ALL_SHAPES {
             for seed in 0..20 {
                 let mut rng = StdRng::seed_from_u64(seed);
-                let new_shape = pick_distinct_shape(current, &mut rng);
+                let new_shape = pick_distinct_shape(&current, &ALL_SHAPES, &mut rng);
                 assert_ne!(new_shape, current, "current {current:?}, seed {seed}");
             }
         }
@@ -703,7 +710,7 @@ mod tests {
     fn pick_distinct_shape_returns_valid_variant() {
         for current in ALL_SHAPES {
             let mut rng = StdRng::seed_from_u64(7);
-            let new_shape = pick_distinct_shape(current, &mut rng);
+            let new_shape = pick_distinct_shape(&current, &ALL_SHAPES, &mut rng);
             assert!(ALL_SHAPES.contains(&new_shape));
         }
     }
@@ -712,11 +719,70 @@ mod tests {
     fn pick_distinct_shape_is_deterministic_for_same_seed() {
         let mut rng_a = StdRng::seed_from_u64(99);
         let mut rng_b = StdRng::seed_from_u64(99);
-        let a = pick_distinct_shape(HourglassShape::Classic, &mut rng_a);
-        let b = pick_distinct_shape(HourglassShape::Classic, &mut rng_b);
+        let a = pick_distinct_shape(&HourglassShape::CLASSIC, &ALL_SHAPES, &mut rng_a);
+        let b = pick_distinct_shape(&HourglassShape::CLASSIC, &ALL_SHAPES, &mut rng_b);
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn pick_distinct_shape_keeps_current_when_it_is_the_only_shape() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let only = [HourglassShape::SLIM];
+        assert_eq!(
+            pick_distinct_shape(&HourglassShape::SLIM, &only, &mut rng),
+            HourglassShape::SLIM
+        );
+    }
+
+    #[test]
+    fn shape_row_keeps_its_layout_for_the_builtins_and_centres_more_shapes() {
+        let offsets: Vec<_> = (0..6).map(|index| shape_row_offset(index, 4)).collect();
+        assert_eq!(offsets, [-100.0, -50.0, 0.0, 50.0, 100.0, 150.0]);
+        // Extra shapes push the row left so it stays balanced around the same
+        // midpoint as before.
+        assert_eq!(shape_row_offset(0, 6), -150.0);
+        assert_eq!(shape_row_offset(7, 6), 200.0);
+    }
+
+    #[test]
+    fn shape_row_spacing_squeezes_only_narrow_windows() {
+        assert_eq!(shape_row_spacing(1280.0, 350.0), 1.0);
+        assert!((shape_row_spacing(300.0, 350.0) - 264.0 / 350.0).abs() < 1e-6);
+        assert_eq!(shape_row_spacing(120.0, 350.0), 0.55);
+    }
+
+    #[test]
+    fn shape_row_rebuilds_when_the_library_changes() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), MeshPlugin))
+            .init_asset::<ColorMaterial>()
+            .init_resource::<ShapeLibrary>()
+            .insert_resource(AppearancePanelVisible(true))
+            .add_systems(Update, rebuild_shape_buttons);
+        app.update();
+
+        let shapes = |app: &mut App| {
+            let world = app.world_mut();
+            let mut names: Vec<_> = world
+                .query::<&ShapeButton>()
+                .iter(world)
+                .map(|button| button.shape.name().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(shapes(&mut app), ["classic", "modern", "slim", "wide"]);
+
+        let mut tall = ShapeLibrary::default().get(&HourglassShape::SLIM).clone();
+        tall.name = "tall".to_string();
+        app.insert_resource(ShapeLibrary::with_loaded([&tall]));
+        app.update();
+        assert_eq!(
+            shapes(&mut app),
+            ["classic", "modern", "slim", "tall", "wide"]
+        );
+    }
+
     #[test]
     fn mini_shape_sand_ignores_selected_color() {
         let mut app = App::new();
@@ -753,8 +819,7 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "chrome_extension")]
-    fn extension_shape_row_converts_physical_center_to_logical_viewport_position() {
+    fn shape_row_converts_physical_center_to_logical_viewport_position() {
         let node = ComputedNode {
             size: Vec2::new(440.0, 104.0),
             inverse_scale_factor: 0.5,
@@ -763,18 +828,17 @@ mod tests {
         let transform = GlobalTransform::from_translation(Vec3::new(460.0, 164.0, 0.0));
 
         assert_eq!(
-            extension_shape_row_screen_position(&node, &transform),
+            shape_row_screen_position(&node, &transform),
             Some(Vec2::new(230.0, 82.0))
         );
     }
 
     #[test]
-    #[cfg(feature = "chrome_extension")]
-    fn extension_shape_row_waits_for_non_empty_layout() {
+    fn shape_row_waits_for_non_empty_layout() {
         let transform = GlobalTransform::from_translation(Vec3::new(460.0, 164.0, 0.0));
 
         assert_eq!(
-            extension_shape_row_screen_position(&ComputedNode::default(), &transform),
+            shape_row_screen_position(&ComputedNode::default(), &transform),
             None
         );
     }
@@ -821,6 +885,86 @@ mod tests {
         ));
     }
 
+    #[cfg(feature = "chrome_extension")]
+    #[test]
+    fn selecting_a_shape_keeps_the_run_going_in_the_extension() {
+        use crate::resources::TimerState;
+        use bevy::render::camera::CameraPlugin;
+        use bevy::window::WindowResolution;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            WindowPlugin {
+                primary_window: Some(Window {
+                    resolution: WindowResolution::new(400.0, 600.0),
+                    ..default()
+                }),
+                ..default()
+            },
+            CameraPlugin,
+            crate::timer::TimerPlugin,
+        ))
+        .init_asset::<Image>()
+        .init_resource::<ButtonInput<MouseButton>>()
+        .init_resource::<HourglassConfig>()
+        .init_resource::<PendingFlip>()
+        .insert_resource(TimerState {
+            duration: 60.0,
+            remaining: 40.0,
+            is_running: true,
+        })
+        .insert_resource(AppearancePanelVisible(true))
+        .add_event::<AppearanceStateChanged>()
+        .add_systems(
+            Update,
+            handle_shape_button_clicks.in_set(TimerSystems::Input),
+        );
+        app.world_mut().spawn((Camera2d, HourglassCamera));
+        app.world_mut().spawn((
+            ShapeRowMarker,
+            ComputedNode {
+                size: Vec2::new(400.0, 600.0),
+                inverse_scale_factor: 1.0,
+                ..default()
+            },
+            GlobalTransform::from_translation(Vec3::new(200.0, 300.0, 0.0)),
+        ));
+        app.world_mut().spawn((
+            MiniHourglass {
+                base_position: Vec3::ZERO,
+                original_x: 0.0,
+            },
+            ShapeButton {
+                shape: HourglassShape::SLIM,
+            },
+            Transform::default(),
+        ));
+        app.update();
+
+        let world = app.world_mut();
+        let mut window = world
+            .query_filtered::<&mut Window, With<PrimaryWindow>>()
+            .single_mut(world)
+            .unwrap();
+        window.set_cursor_position(Some(Vec2::new(200.0, 300.0)));
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+
+        let world = app.world();
+        assert_eq!(
+            world.resource::<HourglassConfig>().shape_type,
+            HourglassShape::SLIM
+        );
+        assert!(!world.resource::<PendingFlip>().0);
+        let timer = world.resource::<TimerState>();
+        assert!(timer.is_running);
+        assert!(timer.remaining <= 40.0 && timer.remaining > 39.0);
+    }
+
     // --- shape_button_scale -----------------------------------------------
 
     #[test]
@@ -0,0 +1,256 @@
+use crate::timer::{TimerCommand, TimerSystems};
+use crate::ui::color_panel::color_picker_typing;
+use crate::ui::command_palette::command_palette_open;
+use crate::ui::shape_editor::shape_editor_typing;
+use bevy::prelude::*;
+
+pub struct ShortcutsPlugin;
+
+impl Plugin for ShortcutsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KeyBindings>()
+            .register_type::<KeyBindings>()
+            .add_systems(
+                Update,
+                handle_timer_shortcuts
+                    .run_if(
+                        not(command_palette_open)
+                            .and(not(color_picker_typing))
+                            .and(not(shape_editor_typing)),
+                    )
+                    .in_set(TimerSystems::Input),
+            );
+    }
+}
+
+/// A key plus the modifiers that must be held with it. `ctrl` also accepts
+/// the Super/Command key so the same binding works on macOS.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyChord {
+    pub const fn key(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    pub const fn ctrl(key: KeyCode) -> Self {
+        Self {
+            ctrl: true,
+            ..Self::key(key)
+        }
+    }
+
+    /// Parse text such as `Ctrl+K`, `Shift+R`, `Space` or `F5`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let key_name = parts.pop().unwrap_or_default();
+        let key = key_code_from_name(key_name)
+            .ok_or_else(|| format!("unknown key `{key_name}` in `{text}`"))?;
+
+        let mut chord = Self::key(key);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" | "cmd" | "command" | "super" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" | "option" => chord.alt = true,
+                _ => return Err(format!("unknown modifier `{modifier}` in `{text}`")),
+            }
+        }
+        Ok(chord)
+    }
+
+    /// True on the frame the key goes down with exactly these modifiers held.
+    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        let ctrl = keys.any_pressed([
+            KeyCode::ControlLeft,
+            KeyCode::ControlRight,
+            KeyCode::SuperLeft,
+            KeyCode::SuperRight,
+        ]);
+        let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+        keys.just_pressed(self.key) && ctrl == self.ctrl && shift == self.shift && alt == self.alt
+    }
+}
+
+fn key_code_from_name(name: &str) -> Option<KeyCode> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::KeyA,
+        KeyCode::KeyB,
+        KeyCode::KeyC,
+        KeyCode::KeyD,
+        KeyCode::KeyE,
+        KeyCode::KeyF,
+        KeyCode::KeyG,
+        KeyCode::KeyH,
+        KeyCode::KeyI,
+        KeyCode::KeyJ,
+        KeyCode::KeyK,
+        KeyCode::KeyL,
+        KeyCode::KeyM,
+        KeyCode::KeyN,
+        KeyCode::KeyO,
+        KeyCode::KeyP,
+        KeyCode::KeyQ,
+        KeyCode::KeyR,
+        KeyCode::KeyS,
+        KeyCode::KeyT,
+        KeyCode::KeyU,
+        KeyCode::KeyV,
+        KeyCode::KeyW,
+        KeyCode::KeyX,
+        KeyCode::KeyY,
+        KeyCode::KeyZ,
+    ];
+    const DIGITS: [KeyCode; 10] = [
+        KeyCode::Digit0,
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+    const FUNCTION_KEYS: [KeyCode; 12] = [
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+    ];
+
+    let lower = name.to_ascii_lowercase();
+    let mut chars = lower.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        if ch.is_ascii_lowercase() {
+            return Some(LETTERS[(ch as u8 - b'a') as usize]);
+        }
+        if ch.is_ascii_digit() {
+            return Some(DIGITS[(ch as u8 - b'0') as usize]);
+        }
+    }
+    if let Some(number) = lower
+        .strip_prefix('f')
+        .and_then(|n| n.parse::<usize>().ok())
+    {
+        return FUNCTION_KEYS.get(number.checked_sub(1)?).copied();
+    }
+    Some(match lower.as_str() {
+        "space" => KeyCode::Space,
+        "enter" | "return" => KeyCode::Enter,
+        "escape" | "esc" => KeyCode::Escape,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "up" => KeyCode::ArrowUp,
+        "down" => KeyCode::ArrowDown,
+        "left" => KeyCode::ArrowLeft,
+        "right" => KeyCode::ArrowRight,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        _ => return None,
+    })
+}
+
+/// Keyboard shortcuts, overridable from the config file.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct KeyBindings {
+    pub command_palette: KeyChord,
+    pub start_pause: KeyChord,
+    pub reset: KeyChord,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            command_palette: KeyChord::ctrl(KeyCode::KeyK),
+            start_pause: KeyChord::key(KeyCode::Space),
+            reset: KeyChord::key(KeyCode::KeyR),
+        }
+    }
+}
+
+fn handle_timer_shortcuts(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut timer_commands: EventWriter<TimerCommand>,
+) {
+    if bindings.start_pause.just_pressed(&keys) {
+        timer_commands.write(TimerCommand::Toggle);
+    }
+    if bindings.reset.just_pressed(&keys) {
+        timer_commands.write(TimerCommand::Reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::TimerState;
+
+    #[test]
+    fn chords_parse_keys_and_modifiers() {
+        assert_eq!(KeyChord::parse("Ctrl+K"), Ok(KeyChord::ctrl(KeyCode::KeyK)));
+        assert_eq!(KeyChord::parse("space"), Ok(KeyChord::key(KeyCode::Space)));
+        assert_eq!(
+            KeyChord::parse("Shift + Alt + F5"),
+            Ok(KeyChord {
+                key: KeyCode::F5,
+                ctrl: false,
+                shift: true,
+                alt: true,
+            })
+        );
+        assert_eq!(KeyChord::parse("7"), Ok(KeyChord::key(KeyCode::Digit7)));
+        assert!(KeyChord::parse("Hyper+K").is_err());
+        assert!(KeyChord::parse("Ctrl+").is_err());
+        assert!(KeyChord::parse("F13").is_err());
+    }
+
+    #[test]
+    fn chords_require_exact_modifiers() {
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(KeyCode::KeyR);
+        assert!(KeyChord::key(KeyCode::KeyR).just_pressed(&keys));
+        assert!(!KeyChord::ctrl(KeyCode::KeyR).just_pressed(&keys));
+
+        keys.press(KeyCode::SuperLeft);
+        assert!(KeyChord::ctrl(KeyCode::KeyR).just_pressed(&keys));
+        assert!(!KeyChord::key(KeyCode::KeyR).just_pressed(&keys));
+    }
+
+    #[test]
+    fn start_pause_shortcut_toggles_the_timer() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, crate::timer::TimerPlugin))
+            .init_resource::<TimerState>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .add_plugins(ShortcutsPlugin);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        app.update();
+        assert!(app.world().resource::<TimerState>().is_running);
+    }
+}
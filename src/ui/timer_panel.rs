@@ -1,22 +1,59 @@
+use crate::presets::{PresetCommand, TimerPresets};
 use crate::resources::TimerState;
 use crate::timer::{TimerCommand, TimerSystems};
 use crate::ui::{BottomTimerMarker, TimerPanelVisible};
 use bevy::ecs::relationship::RelatedSpawnerCommands;
 use bevy::prelude::*;
+#[cfg(not(feature = "chrome_extension"))]
+use bevy::window::PrimaryWindow;
+
+/// The adjustment steps offered by the adjust buttons, smallest first.
+#[cfg(not(feature = "chrome_extension"))]
+const ADJUST_STEPS: [(&str, f32); 7] = [
+    ("1s", 1.0),
+    ("5s", 5.0),
+    ("15s", 15.0),
+    ("1m", 60.0),
+    ("5m", 300.0),
+    ("15m", 900.0),
+    ("1h", 3600.0),
+];
+/// The compact picker starts on one minute.
+#[cfg(not(feature = "chrome_extension"))]
+const COMPACT_DEFAULT_STEP: usize = 3;
+/// Below this window width the fourteen adjust buttons no longer fit beside
+/// the clock, so the compact picker replaces them.
+#[cfg(not(feature = "chrome_extension"))]
+const COMPACT_ADJUST_WIDTH: f32 = 1000.0;
 
 pub struct TimerPanelPlugin;
 
 impl Plugin for TimerPanelPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<TimerPresets>()
+            .add_event::<PresetCommand>();
+
         #[cfg(feature = "chrome_extension")]
         app.add_systems(PostStartup, spawn_sidebar_timer_controls);
 
         #[cfg(not(feature = "chrome_extension"))]
-        app.add_systems(PostStartup, spawn_timer_controls);
+        app.add_systems(PostStartup, spawn_timer_controls)
+            .add_systems(
+                Update,
+                (
+                    handle_compact_step_button.in_set(TimerSystems::Input),
+                    update_adjust_layout,
+                ),
+            );
 
         app.add_systems(
             Update,
-            (handle_timer_buttons, handle_control_buttons).in_set(TimerSystems::Input),
+            (
+                handle_timer_buttons,
+                handle_control_buttons,
+                handle_preset_buttons,
+            )
+                .in_set(TimerSystems::Input),
         )
         .add_systems(
             Update,
@@ -24,6 +61,7 @@ impl Plugin for TimerPanelPlugin {
                 update_time_display.after(TimerSystems::Tick),
                 handle_toggle_button,
                 update_timer_panel_visibility,
+                rebuild_preset_buttons,
             ),
         );
     }
@@ -52,6 +90,40 @@ struct ToggleButton;
 #[derive(Component)]
 struct TimerControlsContainer;
 
+/// Quick-pick row holding one button per preset plus a save button.
+#[derive(Component)]
+struct PresetRow;
+
+#[derive(Component)]
+struct PresetButton(String);
+
+#[derive(Component)]
+struct SavePresetButton;
+
+/// The full row of adjust buttons around the clock.
+#[cfg(not(feature = "chrome_extension"))]
+#[derive(Component)]
+struct WideAdjustRow;
+
+/// The narrow-window picker: minus, clock, plus and a step chooser.
+#[cfg(not(feature = "chrome_extension"))]
+#[derive(Component)]
+struct CompactAdjustRow;
+
+/// A compact minus (-1) or plus (+1) button, re-aimed at the chosen step.
+#[cfg(not(feature = "chrome_extension"))]
+#[derive(Component)]
+struct CompactAdjustSign(f32);
+
+/// Cycles the compact picker through `ADJUST_STEPS`.
+#[cfg(not(feature = "chrome_extension"))]
+#[derive(Component)]
+struct CompactStepButton(usize);
+
+#[cfg(not(feature = "chrome_extension"))]
+#[derive(Component)]
+struct CompactStepLabel;
+
 #[cfg(feature = "chrome_extension")]
 fn spawn_sidebar_timer_controls(
     mut commands: Commands,
@@ -116,6 +188,7 @@ fn spawn_sidebar_timer_controls(
                 ));
 
                 spawn_sidebar_playback_controls(parent);
+                spawn_preset_row(parent);
 
                 spawn_sidebar_adjustment_row(
                     parent,
@@ -303,9 +376,12 @@ fn spawn_timer_controls(mut commands: Commands, query: Query<Entity, With<Bottom
 
 #[cfg(not(feature = "chrome_extension"))]
 fn spawn_timer_controls_content(parent: &mut RelatedSpawnerCommands<ChildOf>) {
-    // Time controls row
+    spawn_preset_row(parent);
+
+    // Time controls row, swapped for the compact picker in narrow windows
     parent
         .spawn((
+            WideAdjustRow,
             Node {
                 width: Val::Percent(100.0),
                 display: Display::Flex,
@@ -319,99 +395,62 @@ fn spawn_timer_controls_content(parent: &mut RelatedSpawnerCommands<ChildOf>) {
         ))
         .with_children(|parent| {
             // Time adjustment buttons (negative)
-            let negative_adjustments = [
-                ("-1h", -3600.0),
-                ("-15m", -900.0),
-                ("-5m", -300.0),
-                ("-1m", -60.0),
-                ("-15s", -15.0),
-                ("-5s", -5.0),
-                ("-1s", -1.0),
-            ];
-
-            for (label, adjustment) in negative_adjustments {
-                parent
-                    .spawn((
-                        TimeAdjustButton { adjustment },
-                        Button,
-                        Node {
-                            width: Val::Px(50.0),
-                            height: Val::Px(40.0),
-                            margin: UiRect::horizontal(Val::Px(3.0)),
-                            justify_content: JustifyContent::Center,
-                            align_items: AlignItems::Center,
-                            border: UiRect::all(Val::Px(1.0)),
-                            ..default()
-                        },
-                        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
-                        BorderColor(Color::srgb(0.5, 0.5, 0.5)),
-                    ))
-                    .with_children(|parent| {
-                        parent.spawn((
-                            Text::new(label),
-                            TextFont {
-                                font_size: 14.0,
-                                ..default()
-                            },
-                            TextColor(Color::WHITE),
-                        ));
-                    });
+            for &(label, step) in ADJUST_STEPS.iter().rev() {
+                parent.spawn(adjust_button(&format!("-{label}"), -step, 50.0));
             }
 
-            // Time display
+            parent.spawn(time_display());
+
+            // Time adjustment buttons (positive)
+            for &(label, step) in &ADJUST_STEPS {
+                parent.spawn(adjust_button(&format!("+{label}"), step, 50.0));
+            }
+        });
+
+    // Compact picker: one step chooser between a minus and a plus button
+    let (label, step) = ADJUST_STEPS[COMPACT_DEFAULT_STEP];
+    parent
+        .spawn((
+            CompactAdjustRow,
+            Node {
+                width: Val::Percent(100.0),
+                display: Display::None,
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::NONE),
+        ))
+        .with_children(|parent| {
+            parent.spawn((CompactAdjustSign(-1.0), adjust_button("-", -step, 40.0)));
+            parent.spawn(time_display());
+            parent.spawn((CompactAdjustSign(1.0), adjust_button("+", step, 40.0)));
             parent.spawn((
-                TimeDisplay,
-                Text::new("00:03:00"),
-                TextFont {
-                    font_size: 32.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
+                CompactStepButton(COMPACT_DEFAULT_STEP),
+                Button,
                 Node {
-                    margin: UiRect::horizontal(Val::Px(20.0)),
+                    width: Val::Px(56.0),
+                    height: Val::Px(40.0),
+                    margin: UiRect::left(Val::Px(6.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Val::Px(1.0)),
                     ..default()
                 },
+                BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                BorderColor(Color::WHITE),
+                children![(
+                    CompactStepLabel,
+                    Text::new(label),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                )],
             ));
-
-            // Time adjustment buttons (positive)
-            let positive_adjustments = [
-                ("+1s", 1.0),
-                ("+5s", 5.0),
-                ("+15s", 15.0),
-                ("+1m", 60.0),
-                ("+5m", 300.0),
-                ("+15m", 900.0),
-                ("+1h", 3600.0),
-            ];
-
-            for (label, adjustment) in positive_adjustments {
-                parent
-                    .spawn((
-                        TimeAdjustButton { adjustment },
-                        Button,
-                        Node {
-                            width: Val::Px(50.0),
-                            height: Val::Px(40.0),
-                            margin: UiRect::horizontal(Val::Px(3.0)),
-                            justify_content: JustifyContent::Center,
-                            align_items: AlignItems::Center,
-                            border: UiRect::all(Val::Px(1.0)),
-                            ..default()
-                        },
-                        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
-                        BorderColor(Color::srgb(0.5, 0.5, 0.5)),
-                    ))
-                    .with_children(|parent| {
-                        parent.spawn((
-                            Text::new(label),
-                            TextFont {
-                                font_size: 14.0,
-                                ..default()
-                            },
-                            TextColor(Color::WHITE),
-                        ));
-                    });
-            }
         });
 
     // Control buttons row
@@ -511,6 +550,149 @@ fn spawn_timer_controls_content(parent: &mut RelatedSpawnerCommands<ChildOf>) {
         });
 }
 
+#[cfg(not(feature = "chrome_extension"))]
+fn adjust_button(label: &str, adjustment: f32, width: f32) -> impl Bundle {
+    (
+        TimeAdjustButton { adjustment },
+        Button,
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(40.0),
+            margin: UiRect::horizontal(Val::Px(3.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+        BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+        children![(
+            Text::new(label),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )],
+    )
+}
+
+#[cfg(not(feature = "chrome_extension"))]
+fn time_display() -> impl Bundle {
+    (
+        TimeDisplay,
+        Text::new("00:03:00"),
+        TextFont {
+            font_size: 32.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            margin: UiRect::horizontal(Val::Px(20.0)),
+            ..default()
+        },
+    )
+}
+
+fn spawn_preset_row(parent: &mut RelatedSpawnerCommands<ChildOf>) {
+    parent.spawn((
+        Name::new("Preset Row"),
+        PresetRow,
+        Node {
+            width: Val::Percent(100.0),
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            justify_content: JustifyContent::Center,
+            margin: UiRect::bottom(Val::Px(6.0)),
+            ..default()
+        },
+    ));
+}
+
+fn preset_chip(label: &str, accent: Color) -> impl Bundle {
+    (
+        Button,
+        Node {
+            height: Val::Px(24.0),
+            margin: UiRect::all(Val::Px(2.0)),
+            padding: UiRect::horizontal(Val::Px(8.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+        BorderColor(accent),
+        children![(
+            Text::new(label),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )],
+    )
+}
+
+/// Refill the quick-pick row when it is spawned and whenever the presets
+/// change. Each chip is outlined in its preset's sand colour.
+fn rebuild_preset_buttons(
+    mut commands: Commands,
+    presets: Res<TimerPresets>,
+    rows: Query<(Entity, Ref<PresetRow>)>,
+) {
+    for (row, marker) in &rows {
+        if !presets.is_changed() && !marker.is_added() {
+            continue;
+        }
+        commands
+            .entity(row)
+            .despawn_related::<Children>()
+            .with_children(|parent| {
+                for preset in &presets.0 {
+                    parent.spawn((
+                        PresetButton(preset.name.clone()),
+                        preset_chip(&preset.name, preset.appearance.color),
+                    ));
+                }
+                parent.spawn((
+                    SavePresetButton,
+                    preset_chip("+ Save", Color::srgb(0.55, 0.55, 0.55)),
+                ));
+            });
+    }
+}
+
+fn handle_preset_buttons(
+    mut interaction_query: Query<
+        (&Interaction, Option<&PresetButton>, &mut BackgroundColor),
+        (
+            Changed<Interaction>,
+            Or<(With<PresetButton>, With<SavePresetButton>)>,
+        ),
+    >,
+    mut preset_commands: EventWriter<PresetCommand>,
+) {
+    for (interaction, preset, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                preset_commands.write(match preset {
+                    Some(preset) => PresetCommand::Apply(preset.0.clone()),
+                    None => PresetCommand::SaveCurrent(None),
+                });
+                *bg_color = BackgroundColor(Color::srgb(0.5, 0.5, 0.5));
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(Color::srgb(0.4, 0.4, 0.4));
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(Color::srgb(0.3, 0.3, 0.3));
+            }
+        }
+    }
+}
+
 fn handle_timer_buttons(
     mut interaction_query: Query<
         (&Interaction, &TimeAdjustButton, &mut BackgroundColor),
@@ -636,6 +818,68 @@ fn handle_toggle_button(
     }
 }
 
+/// Advance the compact picker to the next step, wrapping after an hour, and
+/// point its minus and plus buttons at it.
+#[cfg(not(feature = "chrome_extension"))]
+fn handle_compact_step_button(
+    mut step_buttons: Query<
+        (&Interaction, &mut CompactStepButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut sign_buttons: Query<(&CompactAdjustSign, &mut TimeAdjustButton)>,
+    mut labels: Query<&mut Text, With<CompactStepLabel>>,
+) {
+    for (interaction, mut step, mut bg_color) in &mut step_buttons {
+        match *interaction {
+            Interaction::Pressed => {
+                step.0 = (step.0 + 1) % ADJUST_STEPS.len();
+                let (label, seconds) = ADJUST_STEPS[step.0];
+                for (sign, mut button) in &mut sign_buttons {
+                    button.adjustment = sign.0 * seconds;
+                }
+                for mut text in &mut labels {
+                    **text = label.to_string();
+                }
+                *bg_color = BackgroundColor(Color::srgb(0.5, 0.5, 0.5));
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(Color::srgb(0.4, 0.4, 0.4));
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(Color::srgb(0.3, 0.3, 0.3));
+            }
+        }
+    }
+}
+
+/// Show the full adjust row in wide windows and the compact picker otherwise.
+#[cfg(not(feature = "chrome_extension"))]
+fn update_adjust_layout(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut wide_rows: Query<&mut Node, (With<WideAdjustRow>, Without<CompactAdjustRow>)>,
+    mut compact_rows: Query<&mut Node, With<CompactAdjustRow>>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let compact = window.width() < COMPACT_ADJUST_WIDTH;
+    let (wide, narrow) = if compact {
+        (Display::None, Display::Flex)
+    } else {
+        (Display::Flex, Display::None)
+    };
+    for mut node in &mut wide_rows {
+        if node.display != wide {
+            node.display = wide;
+        }
+    }
+    for mut node in &mut compact_rows {
+        if node.display != narrow {
+            node.display = narrow;
+        }
+    }
+}
+
 fn update_timer_panel_visibility(
     panel_visible: Res<TimerPanelVisible>,
     mut query: Query<&mut Node, With<TimerControlsContainer>>,
@@ -791,6 +1035,68 @@ mod tests {
         assert!(!ts.is_running);
     }
 
+    // --- preset quick-pick row ------------------------------------------
+
+    #[test]
+    fn preset_row_lists_presets_and_follows_changes() {
+        let mut app = App::new();
+        app.init_resource::<TimerPresets>();
+        app.world_mut().spawn(PresetRow);
+        app.add_systems(Update, rebuild_preset_buttons);
+        app.update();
+
+        let labels = |app: &mut App| -> Vec<String> {
+            let world = app.world_mut();
+            world
+                .query_filtered::<&PresetButton, With<Button>>()
+                .iter(world)
+                .map(|button| button.0.clone())
+                .collect()
+        };
+        assert_eq!(labels(&mut app), ["Tea", "Standup", "Focus"]);
+
+        app.world_mut().resource_mut::<TimerPresets>().0.remove(0);
+        app.update();
+        assert_eq!(labels(&mut app), ["Standup", "Focus"]);
+        let world = app.world_mut();
+        assert_eq!(
+            world
+                .query_filtered::<(), With<SavePresetButton>>()
+                .iter(world)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn preset_buttons_send_preset_commands() {
+        let mut app = App::new();
+        app.add_event::<PresetCommand>();
+        app.world_mut().spawn((
+            PresetButton("Tea".to_string()),
+            Button,
+            Interaction::Pressed,
+        ));
+        app.world_mut()
+            .spawn((SavePresetButton, Button, Interaction::Pressed));
+        app.add_systems(Update, handle_preset_buttons);
+        app.update();
+
+        let mut sent: Vec<PresetCommand> = app
+            .world_mut()
+            .resource_mut::<Events<PresetCommand>>()
+            .drain()
+            .collect();
+        sent.sort_by_key(|command| matches!(command, PresetCommand::SaveCurrent(_)));
+        assert_eq!(
+            sent,
+            [
+                PresetCommand::Apply("Tea".to_string()),
+                PresetCommand::SaveCurrent(None)
+            ]
+        );
+    }
+
     // --- handle_toggle_button ---------------------------------------------
 
     /// Press the toggle button once against a `TimerPanelVisible(initial)`.
@@ -920,4 +1226,89 @@ mod tests {
         let toggle = toggle_query.single(world).unwrap();
         assert_ne!(world.get::<ChildOf>(toggle).unwrap().parent(), container);
     }
+
+    // --- compact adjust picker --------------------------------------------
+
+    /// Spawn the native timer controls under a window `width` pixels wide and
+    /// run the layout and step systems once.
+    #[cfg(not(feature = "chrome_extension"))]
+    fn native_controls_app(width: f32) -> App {
+        let mut app = App::new();
+        app.world_mut().spawn(BottomTimerMarker);
+        app.world_mut().spawn((
+            Window {
+                resolution: bevy::window::WindowResolution::new(width, 720.0),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        app.add_systems(Startup, spawn_timer_controls)
+            .add_systems(Update, (handle_compact_step_button, update_adjust_layout));
+        app.update();
+        app
+    }
+
+    #[cfg(not(feature = "chrome_extension"))]
+    fn row_display<R: Component>(app: &mut App) -> Display {
+        let world = app.world_mut();
+        world
+            .query_filtered::<&Node, With<R>>()
+            .single(world)
+            .unwrap()
+            .display
+    }
+
+    #[test]
+    #[cfg(not(feature = "chrome_extension"))]
+    fn narrow_windows_swap_the_adjust_row_for_the_compact_picker() {
+        let mut wide = native_controls_app(1280.0);
+        assert_eq!(row_display::<WideAdjustRow>(&mut wide), Display::Flex);
+        assert_eq!(row_display::<CompactAdjustRow>(&mut wide), Display::None);
+
+        let mut narrow = native_controls_app(480.0);
+        assert_eq!(row_display::<WideAdjustRow>(&mut narrow), Display::None);
+        assert_eq!(row_display::<CompactAdjustRow>(&mut narrow), Display::Flex);
+    }
+
+    #[test]
+    #[cfg(not(feature = "chrome_extension"))]
+    fn compact_step_button_cycles_the_plus_and_minus_steps() {
+        let mut app = native_controls_app(480.0);
+        let adjustments = |app: &mut App| {
+            let world = app.world_mut();
+            let mut adjustments: Vec<f32> = world
+                .query_filtered::<&TimeAdjustButton, With<CompactAdjustSign>>()
+                .iter(world)
+                .map(|button| button.adjustment)
+                .collect();
+            adjustments.sort_by(f32::total_cmp);
+            adjustments
+        };
+        assert_eq!(adjustments(&mut app), [-60.0, 60.0]);
+
+        let press = |app: &mut App, interaction| {
+            let world = app.world_mut();
+            let step = world
+                .query_filtered::<Entity, With<CompactStepButton>>()
+                .single(world)
+                .unwrap();
+            world.entity_mut(step).insert(interaction);
+            app.update();
+        };
+        for _ in 0..3 {
+            press(&mut app, Interaction::Pressed);
+            press(&mut app, Interaction::None);
+        }
+        assert_eq!(adjustments(&mut app), [-3600.0, 3600.0]);
+        let world = app.world_mut();
+        let label = world
+            .query_filtered::<&Text, With<CompactStepLabel>>()
+            .single(world)
+            .unwrap();
+        assert_eq!(label.0, "1h");
+
+        // It wraps back to the smallest step.
+        press(&mut app, Interaction::Pressed);
+        assert_eq!(adjustments(&mut app), [-1.0, 1.0]);
+    }
 }
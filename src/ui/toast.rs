@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+
+/// How long a toast stays on screen.
+const TOAST_SECONDS: f32 = 6.0;
+
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShowToast>()
+            .add_systems(Update, (show_toasts, expire_toasts).chain());
+    }
+}
+
+/// Show a short, non-blocking message near the bottom of the window. A newer
+/// toast replaces the one on screen.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ShowToast(pub String);
+
+#[derive(Component)]
+struct Toast {
+    lifetime: Timer,
+}
+
+fn show_toasts(
+    mut commands: Commands,
+    mut requests: EventReader<ShowToast>,
+    existing: Query<Entity, With<Toast>>,
+) {
+    let Some(ShowToast(message)) = requests.read().last() else {
+        return;
+    };
+    for toast in &existing {
+        commands.entity(toast).despawn();
+    }
+
+    commands
+        .spawn((
+            Name::new("Toast"),
+            Toast {
+                lifetime: Timer::from_seconds(TOAST_SECONDS, TimerMode::Once),
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                bottom: Val::Px(16.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ZIndex(250),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        max_width: Val::Percent(90.0),
+                        padding: UiRect::axes(Val::Px(10.0), Val::Px(6.0)),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.15, 0.05, 0.05, 0.92)),
+                    BorderColor(Color::srgb(0.8, 0.3, 0.3)),
+                ))
+                .with_children(|bubble| {
+                    bubble.spawn((
+                        Text::new(message.clone()),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+fn expire_toasts(mut commands: Commands, time: Res<Time>, mut toasts: Query<(Entity, &mut Toast)>) {
+    for (entity, mut toast) in &mut toasts {
+        if toast.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn toast_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ToastPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                250,
+            )));
+        app
+    }
+
+    fn toast_texts(app: &mut App) -> Vec<String> {
+        let world = app.world_mut();
+        world
+            .query::<&Text>()
+            .iter(world)
+            .map(|text| text.0.clone())
+            .collect()
+    }
+
+    #[test]
+    fn newest_toast_replaces_the_previous_one() {
+        let mut app = toast_app();
+        app.world_mut().send_event(ShowToast("first".into()));
+        app.update();
+        app.world_mut().send_event(ShowToast("second".into()));
+        app.update();
+        assert_eq!(toast_texts(&mut app), vec!["second".to_string()]);
+    }
+
+    #[test]
+    fn toasts_expire() {
+        let mut app = toast_app();
+        app.world_mut().send_event(ShowToast("hello".into()));
+        app.update();
+        for _ in 0..=(TOAST_SECONDS * 4.0) as usize {
+            app.update();
+        }
+        assert!(toast_texts(&mut app).is_empty());
+    }
+}
@@ -0,0 +1,369 @@
+//! Last-seconds urgency. Once a running countdown enters its final seconds
+//! the hourglass pulses gently, the sand splashes faster and the glass warms
+//! towards red, all growing until the deadline.
+
+use crate::hourglass::MainHourglass;
+use crate::morph_mesh::MorphMeshes;
+use crate::resources::{TimerState, UrgencySettings};
+use crate::timer::TimerSystems;
+use bevy::prelude::*;
+use bevy_hourglass::{
+    Hourglass, HourglassMeshBody, HourglassMeshSandState, SandSplash, SandSplashConfig,
+};
+use std::f32::consts::TAU;
+
+/// How far the hourglass grows at the top of a pulse at full urgency.
+const PULSE_AMPLITUDE: f32 = 0.04;
+/// Pulses a second as the final seconds begin, and at the deadline.
+const CALM_PULSE_HZ: f32 = 1.0;
+const URGENT_PULSE_HZ: f32 = 2.5;
+/// At full urgency the sand splashes this many times as often.
+const URGENT_SPLASH_RATE: f32 = 3.0;
+/// The glass colour at full urgency.
+const URGENT_GLASS_COLOR: Color = Color::srgba(1.0, 0.4, 0.3, 0.4);
+
+pub struct UrgencyPlugin;
+
+impl Plugin for UrgencyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UrgencySettings>()
+            .init_resource::<Urgency>()
+            .register_type::<UrgencySettings>()
+            .add_systems(
+                Update,
+                update_urgency
+                    .after(TimerSystems::Tick)
+                    .before(TimerSystems::Observe),
+            )
+            // The shape systems write the untinted glass colour, so tint it
+            // once they are done.
+            .add_systems(
+                Update,
+                (apply_urgency_splash, apply_urgency_tint).after(TimerSystems::Observe),
+            );
+    }
+}
+
+/// How urgent the countdown is right now, read by the systems that scale and
+/// hit-test the main hourglass.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Urgency {
+    /// 0 outside the final seconds, rising to the configured intensity at
+    /// the deadline.
+    pub level: f32,
+    /// Factor on top of the hourglass's fitted scale.
+    pub pulse: f32,
+    pulsing: bool,
+    /// Position within the current pulse, from 0 to 1.
+    phase: f32,
+}
+
+impl Default for Urgency {
+    fn default() -> Self {
+        Self {
+            level: 0.0,
+            pulse: 1.0,
+            pulsing: false,
+            phase: 0.0,
+        }
+    }
+}
+
+impl Urgency {
+    pub(crate) fn is_pulsing(&self) -> bool {
+        self.pulsing
+    }
+
+    /// `base` glass colour shifted towards red by the current urgency.
+    fn glass_color(&self, base: Color) -> Color {
+        if self.level <= 0.0 {
+            base
+        } else {
+            base.mix(&URGENT_GLASS_COLOR, self.level)
+        }
+    }
+}
+
+/// Urgency from 0 to `intensity` over the last `threshold_secs` of a running
+/// countdown. A run shorter than the threshold ramps up over its whole length.
+fn urgency_level(timer_state: &TimerState, settings: &UrgencySettings) -> f32 {
+    let threshold = settings.threshold_secs.min(timer_state.duration);
+    if !timer_state.is_running || timer_state.remaining <= 0.0 || timer_state.remaining >= threshold
+    {
+        return 0.0;
+    }
+    (1.0 - timer_state.remaining / threshold) * settings.intensity.clamp(0.0, 1.0)
+}
+
+/// Scale of the pulse at `phase`, growing from and shrinking back to 1.
+fn pulse_scale(level: f32, phase: f32) -> f32 {
+    1.0 + PULSE_AMPLITUDE * level * (0.5 - 0.5 * (TAU * phase).cos())
+}
+
+fn update_urgency(
+    time: Res<Time>,
+    timer_state: Res<TimerState>,
+    settings: Res<UrgencySettings>,
+    hourglass: Query<&Hourglass, With<MainHourglass>>,
+    mut urgency: ResMut<Urgency>,
+) {
+    let level = urgency_level(&timer_state, &settings);
+    // The flip owns the hourglass while it turns, so the pulse waits for it
+    // and then starts again from rest.
+    let flipping = hourglass.iter().any(|hourglass| hourglass.flipping);
+    let mut next = Urgency { level, ..default() };
+    if level > 0.0 && !settings.reduced_motion && !flipping {
+        let hz = CALM_PULSE_HZ + (URGENT_PULSE_HZ - CALM_PULSE_HZ) * level;
+        next.phase = (urgency.phase + time.delta_secs() * hz).fract();
+        next.pulse = pulse_scale(level, next.phase);
+        next.pulsing = true;
+    }
+    urgency.set_if_neq(next);
+}
+
+/// Splash sand faster as the deadline nears. The main hourglass is built
+/// with the default splash interval.
+fn apply_urgency_splash(
+    urgency: Res<Urgency>,
+    settings: Res<UrgencySettings>,
+    mut splashes: Query<&mut SandSplash, With<MainHourglass>>,
+) {
+    let rate = if settings.reduced_motion {
+        1.0
+    } else {
+        1.0 + (URGENT_SPLASH_RATE - 1.0) * urgency.level
+    };
+    let interval = SandSplashConfig::default().spawn_interval / rate;
+    for mut splash in &mut splashes {
+        if splash.config.spawn_interval != interval {
+            splash.config.spawn_interval = interval;
+        }
+    }
+}
+
+/// Tint the glass of the main hourglass, static or morphing, from the colour
+/// its current shape gives it.
+fn apply_urgency_tint(
+    urgency: Res<Urgency>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    hourglass: Query<
+        (
+            &HourglassMeshSandState,
+            Option<&Children>,
+            Option<&MorphMeshes>,
+        ),
+        With<MainHourglass>,
+    >,
+    glass_query: Query<&MeshMaterial2d<ColorMaterial>, With<HourglassMeshBody>>,
+) {
+    for (sand_state, children, morph_meshes) in &hourglass {
+        let color = urgency.glass_color(sand_state.body_config.color);
+        let glass_materials = glass_query
+            .iter_many(children.into_iter().flatten())
+            .map(|material| &material.0)
+            .chain(morph_meshes.map(|parts| &parts.glass_material));
+        for material in glass_materials {
+            // Look before writing so an idle hourglass does not re-upload
+            // its material every frame.
+            if materials
+                .get(material)
+                .is_some_and(|material| material.color != color)
+                && let Some(material) = materials.get_mut(material)
+            {
+                material.color = color;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HourglassTimerPlugin;
+    use approx::assert_abs_diff_eq;
+    use bevy::asset::AssetPlugin;
+    use bevy::input::InputPlugin;
+    use bevy::render::mesh::MeshPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn running(duration: f32, remaining: f32) -> TimerState {
+        TimerState {
+            duration,
+            remaining,
+            is_running: true,
+        }
+    }
+
+    /// A headless timer with `remaining` of 60 seconds left, stepping 100 ms
+    /// a frame, with the hourglass under a root so its scale is not fitted to
+    /// a window. Starting it running turns the hourglass over first.
+    fn urgency_app(remaining: f32, settings: UrgencySettings) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            MeshPlugin,
+            TransformPlugin,
+        ))
+        .init_asset::<ColorMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .insert_resource(settings)
+        .insert_resource(running(60.0, remaining));
+        let root = app.world_mut().spawn(Transform::default()).id();
+        app.add_plugins(HourglassTimerPlugin {
+            ui: false,
+            root: Some(root),
+            ..default()
+        });
+        app
+    }
+
+    fn main_hourglass(app: &mut App) -> (&Transform, &SandSplash, &HourglassMeshSandState) {
+        let world = app.world_mut();
+        world
+            .query_filtered::<(&Transform, &SandSplash, &HourglassMeshSandState), With<MainHourglass>>()
+            .single(world)
+            .unwrap()
+    }
+
+    /// The one second flip plus a little.
+    fn run_past_the_first_flip(app: &mut App) {
+        for _ in 0..12 {
+            app.update();
+        }
+    }
+
+    fn glass_color(app: &mut App) -> Color {
+        let world = app.world_mut();
+        let material = world
+            .query_filtered::<&MeshMaterial2d<ColorMaterial>, With<HourglassMeshBody>>()
+            .single(world)
+            .unwrap()
+            .0
+            .clone();
+        world
+            .resource::<Assets<ColorMaterial>>()
+            .get(&material)
+            .unwrap()
+            .color
+    }
+
+    #[test]
+    fn urgency_grows_over_the_final_seconds() {
+        let settings = UrgencySettings::default();
+        assert_eq!(urgency_level(&running(60.0, 30.0), &settings), 0.0);
+        assert_eq!(urgency_level(&running(60.0, 10.0), &settings), 0.0);
+        assert_abs_diff_eq!(urgency_level(&running(60.0, 5.0), &settings), 0.5);
+        assert_abs_diff_eq!(urgency_level(&running(60.0, 1.0), &settings), 0.9);
+        // Paused or finished countdowns stay calm.
+        let paused = TimerState {
+            is_running: false,
+            ..running(60.0, 5.0)
+        };
+        assert_eq!(urgency_level(&paused, &settings), 0.0);
+        assert_eq!(urgency_level(&running(60.0, 0.0), &settings), 0.0);
+    }
+
+    #[test]
+    fn intensity_and_short_runs_scale_the_urgency() {
+        let gentle = UrgencySettings {
+            intensity: 0.5,
+            ..default()
+        };
+        assert_abs_diff_eq!(urgency_level(&running(60.0, 5.0), &gentle), 0.25);
+        // A four second run is urgent throughout.
+        let settings = UrgencySettings::default();
+        assert_abs_diff_eq!(urgency_level(&running(4.0, 3.0), &settings), 0.25);
+        let off = UrgencySettings {
+            threshold_secs: 0.0,
+            ..default()
+        };
+        assert_eq!(urgency_level(&running(60.0, 1.0), &off), 0.0);
+    }
+
+    #[test]
+    fn pulse_starts_and_ends_at_rest() {
+        assert_abs_diff_eq!(pulse_scale(1.0, 0.0), 1.0);
+        assert_abs_diff_eq!(pulse_scale(1.0, 0.5), 1.0 + PULSE_AMPLITUDE);
+        assert_abs_diff_eq!(pulse_scale(0.5, 0.5), 1.0 + PULSE_AMPLITUDE / 2.0);
+        assert_abs_diff_eq!(pulse_scale(1.0, 1.0), 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn final_seconds_pulse_tint_and_hurry_the_splash() {
+        let mut app = urgency_app(30.0, UrgencySettings::default());
+        run_past_the_first_flip(&mut app);
+        let calm_glass = glass_color(&mut app);
+        let default_interval = SandSplashConfig::default().spawn_interval;
+        assert_eq!(
+            main_hourglass(&mut app).1.config.spawn_interval,
+            default_interval
+        );
+
+        app.world_mut().resource_mut::<TimerState>().remaining = 2.0;
+        app.update();
+        app.update();
+        let urgency = *app.world().resource::<Urgency>();
+        assert!(urgency.level > 0.7 && urgency.is_pulsing());
+        assert!(urgency.pulse > 1.0);
+        let (transform, splash, _) = main_hourglass(&mut app);
+        assert_abs_diff_eq!(transform.scale.x, urgency.pulse);
+        assert!(splash.config.spawn_interval < default_interval);
+        assert_ne!(glass_color(&mut app), calm_glass);
+
+        // Resetting puts everything back.
+        app.world_mut().insert_resource(TimerState {
+            is_running: false,
+            ..running(60.0, 60.0)
+        });
+        app.update();
+        app.update();
+        let (transform, splash, _) = main_hourglass(&mut app);
+        assert_eq!(transform.scale, Vec3::ONE);
+        assert_eq!(splash.config.spawn_interval, default_interval);
+        assert_eq!(glass_color(&mut app), calm_glass);
+    }
+
+    #[test]
+    fn reduced_motion_keeps_only_the_colour_shift() {
+        let mut app = urgency_app(
+            30.0,
+            UrgencySettings {
+                reduced_motion: true,
+                ..default()
+            },
+        );
+        run_past_the_first_flip(&mut app);
+        app.world_mut().resource_mut::<TimerState>().remaining = 2.0;
+        app.update();
+        let urgency = *app.world().resource::<Urgency>();
+        assert!(urgency.level > 0.0 && !urgency.is_pulsing());
+        let (transform, splash, sand_state) = main_hourglass(&mut app);
+        assert_eq!(transform.scale, Vec3::ONE);
+        assert_eq!(
+            splash.config.spawn_interval,
+            SandSplashConfig::default().spawn_interval
+        );
+        let base = sand_state.body_config.color;
+        assert_ne!(glass_color(&mut app), base);
+    }
+
+    #[test]
+    fn flipping_holds_the_pulse_at_rest() {
+        // Starting in the final seconds turns the hourglass over first.
+        let mut app = urgency_app(2.0, UrgencySettings::default());
+        app.update();
+        app.update();
+        let urgency = *app.world().resource::<Urgency>();
+        assert!(urgency.level > 0.0 && !urgency.is_pulsing());
+        assert_eq!(urgency.pulse, 1.0);
+        assert_eq!(main_hourglass(&mut app).0.scale, Vec3::ONE);
+
+        run_past_the_first_flip(&mut app);
+        assert!(app.world().resource::<Urgency>().is_pulsing());
+    }
+}
@@ -0,0 +1,664 @@
+//! 3D view. With `HourglassConfig::view` set to `ViewMode::ThreeD` the main
+//! hourglass is drawn as a lit glass turned from the same outline, between
+//! wooden end caps, and seen through a camera of its own that the right mouse
+//! button orbits. The flat hourglass stays behind it, hidden, and keeps
+//! timing, flipping and hit-testing, so the timer behaves the same in both
+//! views: the solid one only copies its place, shape, sand and turn.
+
+use crate::hourglass::{HourglassCamera, HourglassPlacement, MainHourglass, ShapeTransition};
+use crate::morph_mesh::{ShapeProfile, glass_lathe_mesh, sand_volume_mesh};
+use crate::resources::{HourglassConfig, HourglassShape, ShapeMode, ShapePreview, ViewMode};
+use crate::shapes::ShapeLibrary;
+use crate::timer::TimerSystems;
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit};
+use bevy::prelude::*;
+use bevy::render::view::NoFrustumCulling;
+use bevy::window::PrimaryWindow;
+use bevy_hourglass::{
+    Hourglass, HourglassMeshPlatesConfig, HourglassMeshSandState, SandSplashParticle,
+};
+use std::f32::consts::FRAC_PI_4;
+
+/// Thickness of each wooden end cap.
+const CAP_THICKNESS: f32 = 24.0;
+const WOOD_COLOR: Color = Color::srgb(0.45, 0.28, 0.15);
+/// Widest the falling stream gets, whatever the neck.
+const STREAM_RADIUS: f32 = 2.0;
+/// Vertical field of view of the 3D camera.
+const FIELD_OF_VIEW: f32 = FRAC_PI_4;
+/// Window height assumed without a window, as in headless apps.
+const FALLBACK_WINDOW_HEIGHT: f32 = 720.0;
+/// Radians the camera orbits per logical pixel dragged.
+const ORBIT_SPEED: f32 = 0.01;
+/// Steepest the camera looks down or up at the glass.
+const MAX_PITCH: f32 = 1.2;
+/// Nearest and farthest the camera zooms, as fractions of its resting
+/// distance.
+const MIN_ZOOM: f32 = 0.5;
+const MAX_ZOOM: f32 = 2.0;
+/// Zoom per line scrolled, and the pixels counted as a line on touchpads.
+const ZOOM_STEP: f32 = 0.1;
+const PIXELS_PER_LINE: f32 = 40.0;
+
+pub struct View3dPlugin;
+
+impl Plugin for View3dPlugin {
+    fn build(&self, app: &mut App) {
+        // Apps without PBR rendering, such as headless ones, stay in 2D.
+        app.add_systems(
+            Update,
+            (
+                switch_view,
+                (update_solid_hourglass, orbit_camera).run_if(in_3d_view),
+            )
+                .chain()
+                .after(TimerSystems::Observe)
+                .run_if(resource_exists::<Assets<StandardMaterial>>),
+        )
+        .add_systems(PostUpdate, hide_sand_splash.run_if(in_3d_view));
+    }
+}
+
+fn in_3d_view(config: Res<HourglassConfig>) -> bool {
+    config.view == ViewMode::ThreeD
+}
+
+/// The 3D camera, orbited `yaw` about the vertical and `pitch` about the
+/// horizontal from straight in front, `zoom` times its resting distance.
+#[derive(Component)]
+struct OrbitCamera {
+    yaw: f32,
+    pitch: f32,
+    zoom: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.0,
+            zoom: 1.0,
+        }
+    }
+}
+
+#[derive(Component)]
+struct SolidHourglass;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+enum SolidPart {
+    Glass,
+    TopSand,
+    BottomSand,
+    Stream,
+    /// The end cap above (1) or below (-1) the glass.
+    Cap(i8),
+}
+
+/// Build the 3D scene when the view turns to 3D and take it down when it
+/// turns back. Meanwhile the flat hourglass is hidden and the
+/// `HourglassCamera` stops clearing the window, so it draws the controls over
+/// the 3D camera's picture.
+fn switch_view(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<HourglassConfig>,
+    placement: Res<HourglassPlacement>,
+    scene: Query<Entity, Or<(With<SolidHourglass>, With<OrbitCamera>)>>,
+    mut flat: Query<(Entity, Option<&mut Visibility>), With<MainHourglass>>,
+    mut flat_cameras: Query<&mut Camera, With<HourglassCamera>>,
+) {
+    let solid = config.view == ViewMode::ThreeD;
+    for (entity, visibility) in &mut flat {
+        match visibility {
+            Some(mut visibility) => {
+                visibility.set_if_neq(if solid {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                });
+            }
+            // The builder leaves the visibility of the parts to the parts.
+            None if solid => {
+                commands.entity(entity).insert(Visibility::Hidden);
+            }
+            None => {}
+        }
+    }
+    for mut camera in &mut flat_cameras {
+        if solid != matches!(camera.clear_color, ClearColorConfig::None) {
+            camera.clear_color = if solid {
+                ClearColorConfig::None
+            } else {
+                ClearColorConfig::Default
+            };
+        }
+    }
+
+    if solid != scene.is_empty() {
+        return;
+    }
+    if !solid {
+        for entity in &scene {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    commands
+        .spawn((
+            Name::new("3D Camera"),
+            OrbitCamera::default(),
+            Camera3d::default(),
+            // Under the `HourglassCamera`, which draws the controls.
+            Camera {
+                order: -1,
+                ..default()
+            },
+            Projection::Perspective(PerspectiveProjection {
+                fov: FIELD_OF_VIEW,
+                far: 10_000.0,
+                ..default()
+            }),
+            // Keep the sand colours as they are in 2D.
+            Tonemapping::None,
+            AmbientLight {
+                brightness: 300.0,
+                ..default()
+            },
+            placement.render_layers.clone(),
+        ))
+        .with_children(|camera| {
+            // A key light from above the viewer's left shoulder and a weaker
+            // one from behind to pick out the rim of the glass.
+            camera.spawn((
+                DirectionalLight::default(),
+                Transform::default().looking_to(Vec3::new(0.4, -0.5, -1.0), Vec3::Y),
+                placement.render_layers.clone(),
+            ));
+            camera.spawn((
+                DirectionalLight {
+                    illuminance: 3_000.0,
+                    ..default()
+                },
+                Transform::default().looking_to(Vec3::new(-0.3, -0.2, 1.0), Vec3::Y),
+                placement.render_layers.clone(),
+            ));
+        });
+
+    let glass = materials.add(StandardMaterial {
+        alpha_mode: AlphaMode::Blend,
+        perceptual_roughness: 0.08,
+        reflectance: 0.6,
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    });
+    let sand = materials.add(StandardMaterial {
+        perceptual_roughness: 0.9,
+        ..default()
+    });
+    let wood = materials.add(StandardMaterial {
+        base_color: WOOD_COLOR,
+        perceptual_roughness: 0.7,
+        ..default()
+    });
+    let cylinder = meshes.add(Cylinder::new(1.0, 1.0));
+    let parts = [
+        (SolidPart::BottomSand, meshes.add(sand_volume_mesh()), &sand),
+        (SolidPart::TopSand, meshes.add(sand_volume_mesh()), &sand),
+        (SolidPart::Stream, cylinder.clone(), &sand),
+        (SolidPart::Glass, meshes.add(glass_lathe_mesh()), &glass),
+        (SolidPart::Cap(1), cylinder.clone(), &wood),
+        (SolidPart::Cap(-1), cylinder, &wood),
+    ];
+    commands
+        .spawn((
+            Name::new("Solid Hourglass"),
+            SolidHourglass,
+            Transform::default(),
+            Visibility::default(),
+            placement.render_layers.clone(),
+        ))
+        .with_children(|hourglass| {
+            for (part, mesh, material) in parts {
+                hourglass.spawn((
+                    part,
+                    Mesh3d(mesh),
+                    MeshMaterial3d(material.clone()),
+                    Transform::default(),
+                    // Vertices move with the sand and the shape, so bounds
+                    // would go stale.
+                    NoFrustumCulling,
+                    placement.render_layers.clone(),
+                ));
+            }
+        });
+}
+
+/// Keep the solid hourglass on the flat one: in its place and at its size,
+/// turned over about the horizontal axis while it flips, and holding as much
+/// sand in the same colour. The glass and caps are shaped again when the
+/// shape changes and every frame while morphing or changing shape.
+fn update_solid_hourglass(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<HourglassConfig>,
+    preview: Res<ShapePreview>,
+    library: Res<ShapeLibrary>,
+    flat: Query<
+        (
+            &Hourglass,
+            Ref<HourglassMeshSandState>,
+            &GlobalTransform,
+            Has<ShapeTransition>,
+        ),
+        With<MainHourglass>,
+    >,
+    mut solids: Query<&mut Transform, With<SolidHourglass>>,
+    mut parts: Query<
+        (
+            Ref<SolidPart>,
+            &Mesh3d,
+            &MeshMaterial3d<StandardMaterial>,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<SolidHourglass>,
+    >,
+    mut profile: Local<Option<(HourglassShape, ShapeProfile)>>,
+) {
+    let Ok((hourglass, sand_state, flat_transform, transitioning)) = flat.single() else {
+        return;
+    };
+    let (scale, _, translation) = flat_transform.to_scale_rotation_translation();
+    for mut transform in &mut solids {
+        transform.set_if_neq(Transform {
+            translation,
+            rotation: Quat::from_rotation_x(hourglass.current_rotation),
+            scale,
+        });
+    }
+
+    let (shape, shape_mode) = preview.shape(&config);
+    let resample = shape_mode == ShapeMode::Morphing
+        || transitioning
+        || library.is_changed()
+        || parts.iter().any(|(part, ..)| part.is_added())
+        || profile.as_ref().is_none_or(|(last, _)| last != shape);
+    if resample {
+        *profile = Some((
+            shape.clone(),
+            ShapeProfile::new(
+                &sand_state.body_config,
+                &HourglassMeshPlatesConfig::default(),
+            ),
+        ));
+    }
+    let Some((_, profile)) = profile.as_ref() else {
+        return;
+    };
+    let fill = sand_state.fill_percent;
+    let pile = profile.bottom_fill_line(fill);
+
+    for (part, mesh, material, mut transform, mut visibility) in &mut parts {
+        let color = match *part {
+            SolidPart::Glass => {
+                if !resample {
+                    continue;
+                }
+                if let Some(mesh) = meshes.get_mut(&mesh.0) {
+                    profile.write_glass_lathe(mesh);
+                }
+                profile.glass_color
+            }
+            SolidPart::Cap(side) => {
+                let radius = profile.plate_size.x / 2.0;
+                let y = f32::from(side) * (profile.total_height + CAP_THICKNESS) / 2.0;
+                transform.set_if_neq(Transform::from_xyz(0.0, y, 0.0).with_scale(Vec3::new(
+                    radius,
+                    CAP_THICKNESS,
+                    radius,
+                )));
+                continue;
+            }
+            SolidPart::TopSand | SolidPart::BottomSand => {
+                if (resample || sand_state.is_changed())
+                    && let Some(mesh) = meshes.get_mut(&mesh.0)
+                {
+                    let (low, high) = if *part == SolidPart::TopSand {
+                        (0.0, profile.top_fill_line(fill))
+                    } else {
+                        (-profile.total_height / 2.0, pile)
+                    };
+                    profile.write_sand_volume(mesh, low, high);
+                }
+                hourglass.sand_color
+            }
+            SolidPart::Stream => {
+                // Falling whenever the flat hourglass would splash.
+                let falling =
+                    hourglass.running && hourglass.upper_chamber > 0.0 && !hourglass.flipping;
+                visibility.set_if_neq(if falling {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                });
+                let radius = profile.cavity_at(0.0).min(STREAM_RADIUS);
+                transform.set_if_neq(
+                    Transform::from_xyz(0.0, pile / 2.0, 0.0)
+                        .with_scale(Vec3::new(radius, -pile, radius)),
+                );
+                hourglass.sand_color
+            }
+        };
+        if materials
+            .get(&material.0)
+            .is_some_and(|m| m.base_color != color)
+            && let Some(material) = materials.get_mut(&material.0)
+        {
+            material.base_color = color;
+        }
+    }
+}
+
+/// Orbit the 3D camera about the hourglass while the right mouse button is
+/// dragged, and zoom with the scroll wheel. At rest it sees the hourglass
+/// where the `HourglassCamera` would, at the same size, so clicks and drags
+/// on it work as in 2D.
+fn orbit_camera(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    scroll: Res<AccumulatedMouseScroll>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    flat_cameras: Query<&GlobalTransform, With<HourglassCamera>>,
+    solids: Query<&Transform, (With<SolidHourglass>, Without<OrbitCamera>)>,
+    mut cameras: Query<(&mut OrbitCamera, &mut Transform)>,
+) {
+    let Ok((mut orbit, mut transform)) = cameras.single_mut() else {
+        return;
+    };
+    if mouse_buttons.pressed(MouseButton::Right) && motion.delta != Vec2::ZERO {
+        orbit.yaw -= motion.delta.x * ORBIT_SPEED;
+        orbit.pitch = (orbit.pitch - motion.delta.y * ORBIT_SPEED).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+    let lines = match scroll.unit {
+        MouseScrollUnit::Line => scroll.delta.y,
+        MouseScrollUnit::Pixel => scroll.delta.y / PIXELS_PER_LINE,
+    };
+    if lines != 0.0 {
+        orbit.zoom = (orbit.zoom * (1.0 - lines * ZOOM_STEP)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    // Far enough back that one unit on the glass's plane is one pixel, as it
+    // is for the `HourglassCamera`.
+    let height = windows
+        .single()
+        .map_or(FALLBACK_WINDOW_HEIGHT, |window| window.height());
+    let distance = height / 2.0 / (FIELD_OF_VIEW / 2.0).tan() * orbit.zoom;
+    let center = flat_cameras
+        .single()
+        .map_or(Vec2::ZERO, |camera| camera.translation().truncate());
+    let pivot = solids
+        .single()
+        .map_or(Vec3::ZERO, |hourglass| hourglass.translation);
+    let mut orbited = Transform::from_translation(center.extend(distance));
+    orbited.rotate_around(
+        pivot,
+        Quat::from_euler(EulerRot::YXZ, orbit.yaw, orbit.pitch, 0.0),
+    );
+    transform.set_if_neq(orbited);
+}
+
+/// The flat hourglass's splash would land in front of the 3D glass, so it is
+/// hidden while the view is 3D.
+fn hide_sand_splash(mut particles: Query<&mut Visibility, Added<SandSplashParticle>>) {
+    for mut visibility in &mut particles {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HourglassTimerPlugin;
+    use crate::resources::TimerState;
+    use crate::timer::TimerCommand;
+    use approx::assert_abs_diff_eq;
+    use bevy::asset::AssetPlugin;
+    use bevy::input::InputPlugin;
+    use bevy::input::mouse::{MouseMotion, MouseWheel};
+    use bevy::render::mesh::{MeshPlugin, VertexAttributeValues};
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    /// A headless app in `view`, with a `HourglassCamera` and 100 ms frames.
+    fn view_app(view: ViewMode) -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            MeshPlugin,
+            TransformPlugin,
+        ))
+        .init_asset::<ColorMaterial>()
+        .init_asset::<StandardMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .insert_resource(HourglassConfig { view, ..default() })
+        .add_plugins(HourglassTimerPlugin {
+            ui: false,
+            ..default()
+        });
+        app.world_mut().spawn((Camera::default(), HourglassCamera));
+        app.update();
+        app.update();
+        app
+    }
+
+    fn flat_hidden(app: &mut App) -> bool {
+        let world = app.world_mut();
+        world
+            .query_filtered::<Option<&Visibility>, With<MainHourglass>>()
+            .single(world)
+            .unwrap()
+            == Some(&Visibility::Hidden)
+    }
+
+    fn flat_camera_clears(app: &mut App) -> bool {
+        let world = app.world_mut();
+        let camera = world
+            .query_filtered::<&Camera, With<HourglassCamera>>()
+            .single(world)
+            .unwrap();
+        !matches!(camera.clear_color, ClearColorConfig::None)
+    }
+
+    fn part_positions(app: &mut App, wanted: SolidPart) -> Vec<[f32; 3]> {
+        let world = app.world_mut();
+        let mesh = world
+            .query::<(&SolidPart, &Mesh3d)>()
+            .iter(world)
+            .find(|(part, _)| **part == wanted)
+            .map(|(_, mesh)| mesh.0.clone())
+            .unwrap();
+        match world
+            .resource::<Assets<Mesh>>()
+            .get(&mesh)
+            .unwrap()
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+        {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions.clone(),
+            _ => panic!("part has no positions"),
+        }
+    }
+
+    fn solid_transform(app: &mut App) -> Transform {
+        let world = app.world_mut();
+        *world
+            .query_filtered::<&Transform, With<SolidHourglass>>()
+            .single(world)
+            .unwrap()
+    }
+
+    fn camera_transform(app: &mut App) -> Transform {
+        let world = app.world_mut();
+        *world
+            .query_filtered::<&Transform, With<OrbitCamera>>()
+            .single(world)
+            .unwrap()
+    }
+
+    fn flat_hourglass(app: &mut App) -> Hourglass {
+        let world = app.world_mut();
+        world
+            .query_filtered::<&Hourglass, With<MainHourglass>>()
+            .single(world)
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn switching_views_swaps_the_flat_hourglass_for_a_solid_one() {
+        let mut app = view_app(ViewMode::TwoD);
+        assert!(!flat_hidden(&mut app));
+        assert!(flat_camera_clears(&mut app));
+        let world = app.world_mut();
+        assert_eq!(world.query::<&SolidPart>().iter(world).count(), 0);
+
+        app.world_mut().resource_mut::<HourglassConfig>().view = ViewMode::ThreeD;
+        app.update();
+        app.update();
+        assert!(flat_hidden(&mut app));
+        assert!(!flat_camera_clears(&mut app));
+        let world = app.world_mut();
+        assert_eq!(world.query::<&SolidPart>().iter(world).count(), 6);
+        assert_eq!(world.query::<&OrbitCamera>().iter(world).count(), 1);
+
+        // The glass is turned from the flat one's outline: as wide across
+        // as it is deep, and as tall.
+        let glass = part_positions(&mut app, SolidPart::Glass);
+        let widest = |axis: usize| glass.iter().map(|p| p[axis]).fold(f32::MIN, f32::max);
+        let world = app.world_mut();
+        let body = world
+            .query_filtered::<&HourglassMeshSandState, With<MainHourglass>>()
+            .single(world)
+            .unwrap()
+            .body_config
+            .clone();
+        assert!(widest(0) > 0.0);
+        assert_abs_diff_eq!(widest(0), widest(2), epsilon = 1e-3);
+        assert_abs_diff_eq!(widest(1), body.total_height / 2.0, epsilon = 1e-3);
+
+        app.world_mut().resource_mut::<HourglassConfig>().view = ViewMode::TwoD;
+        app.update();
+        assert!(!flat_hidden(&mut app));
+        assert!(flat_camera_clears(&mut app));
+        let world = app.world_mut();
+        assert_eq!(world.query::<&SolidPart>().iter(world).count(), 0);
+        assert_eq!(world.query::<&OrbitCamera>().iter(world).count(), 0);
+    }
+
+    #[test]
+    fn solid_hourglass_turns_over_and_drains_with_the_flat_one() {
+        let mut app = view_app(ViewMode::ThreeD);
+        let full = part_positions(&mut app, SolidPart::TopSand)
+            .iter()
+            .map(|p| p[1])
+            .fold(f32::MIN, f32::max);
+
+        // Starting turns the hourglass over, now about the horizontal axis.
+        app.world_mut().send_event(TimerCommand::Start);
+        for _ in 0..4 {
+            app.update();
+        }
+        let hourglass = flat_hourglass(&mut app);
+        assert!(hourglass.flipping && hourglass.current_rotation > 0.0);
+        let turned = solid_transform(&mut app).rotation;
+        let expected = Quat::from_rotation_x(hourglass.current_rotation);
+        assert!(turned.angle_between(expected) < 0.2, "{turned:?}");
+
+        // Upright again, the top bulb drains.
+        for _ in 0..10 {
+            app.update();
+        }
+        app.world_mut().resource_mut::<TimerState>().remaining = 90.0;
+        app.update();
+        app.update();
+        assert!(!flat_hourglass(&mut app).flipping);
+        assert_eq!(solid_transform(&mut app).rotation, Quat::IDENTITY);
+        let draining = part_positions(&mut app, SolidPart::TopSand)
+            .iter()
+            .map(|p| p[1])
+            .fold(f32::MIN, f32::max);
+        assert!(draining < full);
+    }
+
+    #[test]
+    fn timer_runs_the_same_in_either_view() {
+        let mut apps = [view_app(ViewMode::TwoD), view_app(ViewMode::ThreeD)];
+        for app in &mut apps {
+            app.world_mut().send_event(TimerCommand::SetDuration(5.0));
+            app.world_mut().send_event(TimerCommand::Start);
+            for _ in 0..30 {
+                app.update();
+            }
+        }
+        let [flat, solid] = &mut apps;
+        assert_eq!(
+            *flat.world().resource::<TimerState>(),
+            *solid.world().resource::<TimerState>()
+        );
+        let (flat, solid) = (flat_hourglass(flat), flat_hourglass(solid));
+        assert_eq!(flat.remaining_time, solid.remaining_time);
+        assert_eq!(flat.upper_chamber, solid.upper_chamber);
+    }
+
+    #[test]
+    fn right_drag_orbits_about_the_hourglass_and_scrolling_zooms() {
+        let mut app = view_app(ViewMode::ThreeD);
+        let pivot = solid_transform(&mut app).translation;
+        let resting = camera_transform(&mut app);
+        assert_eq!(resting.forward(), Dir3::NEG_Z);
+        let distance = resting.translation.distance(pivot);
+
+        // Moving without the button held leaves the camera where it is.
+        app.world_mut().send_event(MouseMotion {
+            delta: Vec2::new(60.0, 0.0),
+        });
+        app.update();
+        assert_eq!(camera_transform(&mut app), resting);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world_mut().send_event(MouseMotion {
+            delta: Vec2::new(60.0, 20.0),
+        });
+        app.update();
+        let orbited = camera_transform(&mut app);
+        assert!(orbited.translation.x < pivot.x && orbited.translation.y > pivot.y);
+        assert_abs_diff_eq!(
+            orbited.translation.distance(pivot),
+            distance,
+            epsilon = 1e-2
+        );
+        // Still looking at the hourglass.
+        let to_pivot = (pivot - orbited.translation).normalize();
+        assert!(orbited.forward().dot(to_pivot) > 0.999);
+
+        app.world_mut().send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 2.0,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        let zoomed = camera_transform(&mut app).translation.distance(pivot);
+        assert_abs_diff_eq!(zoomed, distance * 0.8, epsilon = 1e-2);
+    }
+}